@@ -0,0 +1,468 @@
+//! 平衡模拟器
+//!
+//! 不启动窗口，按配置文件批量模拟整局游戏：`cargo run -- simulate [选项]`
+//! - 每局使用独立的种子，关卡布局、商店货架与价格和游戏中的种子模式一致
+//! - 钩子由机器人 (`demo::bot`) 操作：按每秒收益选择目标，回收低价值重物时使用炸药，商店按优先级购买
+//! - 摆动、下放、回收与结算显示时间按钩子参数计算；移动实体按生成位置处理，不模拟连击
+//! - 汇总每关通过率、金钱分布、商店花费与购买最多的道具，输出 CSV 或 JSON (见 `report`)
+
+pub mod report;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+use bevy::prelude::*;
+use rand::RngCore;
+use serde::de::DeserializeOwned;
+
+use crate::config::{
+    EntitiesConfig, EntityDescriptor, GameplayConfig, LevelDescriptor, LevelsConfig,
+    ProgressionConfig,
+};
+use crate::demo::bot::{HookView, Target, shop_choice};
+use crate::demo::entity::Carrying;
+use crate::demo::hook::{
+    BONUS_DISPLAY_DURATION, HOOK_GRAB_SPEED, HOOK_MAX_ANGLE, HOOK_MIN_ANGLE, Hook, HookStats,
+};
+use crate::demo::level::level_layout;
+use crate::demo::payout::{PayoutContext, PayoutEffect, PayoutPipeline};
+use crate::demo::player::PlayerResource;
+use crate::screens::gameplay::STOPWATCH_BONUS_SECONDS;
+use crate::screens::shop::{PropType, roll_shop_items};
+use crate::screens::stats::{Difficulty, LevelStats, RngStream};
+use crate::utils::love_to_bevy_coords;
+use report::Report;
+
+/// 模拟使用的配置文件，与游戏加载的 `assets/config` 相同
+pub struct SimConfig {
+    pub entities: EntitiesConfig,
+    pub levels: LevelsConfig,
+    pub progression: ProgressionConfig,
+    pub gameplay: GameplayConfig,
+}
+
+impl SimConfig {
+    /// 从配置目录读取 YAML 配置
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        fn read<T: DeserializeOwned>(dir: &Path, name: &str) -> anyhow::Result<T> {
+            let path = dir.join(name);
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            serde_yaml::from_str(&content)
+                .with_context(|| format!("failed to parse {}", path.display()))
+        }
+
+        Ok(Self {
+            entities: read(dir, "entities.yaml")?,
+            levels: read(dir, "levels.yaml")?,
+            progression: read(dir, "progression.yaml")?,
+            gameplay: read(dir, "gameplay.yaml")?,
+        })
+    }
+}
+
+/// 模拟参数
+#[derive(Debug, Clone)]
+pub struct SimOptions {
+    pub runs: u32,
+    /// 第一局的种子，之后每局由它派生
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    /// 每局最多进行的关卡数，机器人一直通关时在此结束
+    pub max_levels: u32,
+}
+
+impl Default for SimOptions {
+    fn default() -> Self {
+        Self {
+            runs: 1000,
+            seed: 0,
+            difficulty: Difficulty::Normal,
+            max_levels: 30,
+        }
+    }
+}
+
+/// 一关的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelResult {
+    pub level: u32,
+    /// 关卡布局 (例如 "L4_2")
+    pub layout: String,
+    pub goal: u32,
+    /// 本关结束时的总金钱
+    pub money: u32,
+    pub cleared: bool,
+    /// 通关后在商店的花费
+    pub shop_spend: u32,
+    pub purchases: Vec<PropType>,
+}
+
+/// 一局的结果，最后一关未通过或达到关卡上限时结束
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunResult {
+    pub seed: u64,
+    pub levels: Vec<LevelResult>,
+}
+
+/// 按参数模拟所有局
+pub fn simulate(config: &SimConfig, options: &SimOptions) -> Vec<RunResult> {
+    let pipeline = PayoutPipeline::builtin();
+    (0..options.runs)
+        .map(|run| {
+            let seed = options
+                .seed
+                .wrapping_add((run as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+            simulate_run(config, &pipeline, options, seed)
+        })
+        .collect()
+}
+
+/// 以指定种子模拟一局：与游戏流程相同，过关后进入商店，再计算下一关的目标
+pub fn simulate_run(
+    config: &SimConfig,
+    pipeline: &PayoutPipeline,
+    options: &SimOptions,
+    seed: u64,
+) -> RunResult {
+    let curve = &config.progression.goal;
+    let scaling = options
+        .difficulty
+        .scaling(&config.gameplay.custom_difficulty);
+    let mut stats = LevelStats::new_run(options.difficulty, scaling, curve);
+    stats.seed = Some(seed);
+    let mut rng = stats.rng(RngStream::Level);
+    stats.calculate_real_level(&config.progression, &mut rng);
+    stats.update_goal(curve);
+
+    let mut player = PlayerResource::default();
+    let mut hook_stats = HookStats::default();
+    let mut run = RunResult {
+        seed,
+        levels: Vec::new(),
+    };
+
+    while run.levels.len() < options.max_levels as usize {
+        if player.has_stopwatch {
+            stats.add_time(STOPWATCH_BONUS_SECONDS);
+        }
+        let layout = level_layout(
+            &stats.real_level_str,
+            &stats,
+            &config.progression,
+            Some(&config.levels),
+            &config.entities,
+        );
+        if let Some(layout) = &layout {
+            play_level(
+                config,
+                pipeline,
+                layout,
+                &mut stats,
+                &mut player,
+                &hook_stats,
+            );
+        }
+        player.reset_level_effects();
+
+        let mut result = LevelResult {
+            level: stats.level,
+            layout: stats.real_level_str.clone(),
+            goal: stats.goal,
+            money: stats.money,
+            cleared: stats.reach_goal(),
+            shop_spend: 0,
+            purchases: Vec::new(),
+        };
+        if !result.cleared {
+            run.levels.push(result);
+            break;
+        }
+
+        // 过关：进入下一关并打开商店
+        stats.level += 1;
+        let mut rng = stats.rng(RngStream::Level);
+        stats.calculate_real_level(&config.progression, &mut rng);
+        stats.reset_timer();
+
+        let mut items = roll_shop_items(&stats, &hook_stats);
+        loop {
+            let shelf: Vec<(PropType, u32)> = items
+                .iter()
+                .map(|item| (item.prop_type, item.price))
+                .collect();
+            let Some(index) = shop_choice(&shelf, stats.money, &player) else {
+                break;
+            };
+            let item = items.remove(index);
+            stats.money -= item.price;
+            item.prop_type.apply(&mut player, &mut hook_stats);
+            result.shop_spend += item.price;
+            result.purchases.push(item.prop_type);
+        }
+
+        stats.update_goal(curve);
+        run.levels.push(result);
+    }
+
+    run
+}
+
+/// 关卡中的一个物品
+struct SimEntity {
+    target: Target,
+    entity_id: String,
+    descriptor: EntityDescriptor,
+    carrying: Option<Carrying>,
+}
+
+/// 机器人按每秒收益依次抓取，直到时间耗尽或没有值得抓的物品
+fn play_level(
+    config: &SimConfig,
+    pipeline: &PayoutPipeline,
+    layout: &LevelDescriptor,
+    stats: &mut LevelStats,
+    player: &mut PlayerResource,
+    hook_stats: &HookStats,
+) {
+    let mut entities: Vec<SimEntity> = layout
+        .entities
+        .iter()
+        .enumerate()
+        .filter_map(|(index, level_entity)| {
+            let descriptor = config.entities.entities.get(&level_entity.entity_id)?;
+            let carrying = level_entity.carries.as_ref().and_then(|carries| {
+                Some(Carrying {
+                    entity_id: carries.clone(),
+                    descriptor: config.entities.entities.get(carries)?.clone(),
+                })
+            });
+            let pos = love_to_bevy_coords(level_entity.pos.x, level_entity.pos.y);
+            let entity = Entity::from_raw_u32(index as u32)?;
+            let ctx = PayoutContext {
+                entity_id: &level_entity.entity_id,
+                descriptor,
+                player,
+                scaling: &stats.scaling,
+            };
+            Some(SimEntity {
+                target: Target::new(entity, pos, pipeline, &ctx, carrying.as_ref()),
+                entity_id: level_entity.entity_id.clone(),
+                descriptor: descriptor.clone(),
+                carrying,
+            })
+        })
+        .collect();
+
+    let mut hook = Hook::default();
+    let mut rng = stats.rng(RngStream::Payout);
+    let swing_speed = hook_stats.rotate_speed() * stats.scaling.swing_scale;
+    let mut remaining = stats.timer;
+
+    loop {
+        // 道具与力量会改变收益，每次发射前重新估算
+        for entity in &mut entities {
+            let ctx = PayoutContext {
+                entity_id: &entity.entity_id,
+                descriptor: &entity.descriptor,
+                player,
+                scaling: &stats.scaling,
+            };
+            entity.target = Target::new(
+                entity.target.entity,
+                entity.target.pos,
+                pipeline,
+                &ctx,
+                entity.carrying.as_ref(),
+            );
+        }
+        let targets: Vec<Target> = entities
+            .iter()
+            .map(|entity| entity.target.clone())
+            .collect();
+        let view = HookView::new(&hook, hook_stats, player);
+        let Some((target, angle, length, _)) = view.best_shot(&targets) else {
+            break;
+        };
+
+        let (wait, rotate_right) = swing_wait(hook.angle, hook.rotate_right, angle, swing_speed);
+        hook.angle = angle;
+        hook.rotate_right = rotate_right;
+        remaining -= wait + length / hook_stats.grab_speed();
+        if remaining <= 0.0 {
+            break;
+        }
+
+        let index = entities
+            .iter()
+            .position(|entity| entity.target.entity == target.entity)
+            .expect("target comes from entities");
+        if player.dynamite_count > 0 && view.should_use_dynamite(target, length) {
+            player.dynamite_count -= 1;
+            entities.remove(index);
+            remaining -= length / HOOK_GRAB_SPEED;
+            continue;
+        }
+
+        remaining -= length / view.reel_speed(target.mass);
+        if remaining <= 0.0 {
+            break;
+        }
+        let entity = entities.remove(index);
+        collect(pipeline, &entity, stats, player, &mut rng);
+        remaining -= BONUS_DISPLAY_DURATION;
+    }
+}
+
+/// 与钩子结算相同：依次执行结算修饰器，携带物品单独结算后累加
+fn collect(
+    pipeline: &PayoutPipeline,
+    entity: &SimEntity,
+    stats: &mut LevelStats,
+    player: &mut PlayerResource,
+    rng: &mut dyn RngCore,
+) {
+    let context = |entity_id, descriptor| PayoutContext {
+        entity_id,
+        descriptor,
+        player,
+        scaling: &stats.scaling,
+    };
+    let mut payout = pipeline.evaluate(&context(&entity.entity_id, &entity.descriptor), rng);
+    if let Some(carrying) = &entity.carrying {
+        payout.merge(pipeline.evaluate(&context(&carrying.entity_id, &carrying.descriptor), rng));
+    }
+
+    if payout.has_effects() {
+        for effect in &payout.effects {
+            match effect {
+                PayoutEffect::AddDynamite => {
+                    player.dynamite_count = (player.dynamite_count + 1).min(12);
+                }
+                PayoutEffect::Strengthen => player.strengthen(),
+            }
+        }
+    } else {
+        stats.money += payout.bonus.max(0) as u32;
+    }
+}
+
+/// 钩子从当前角度摆到目标角度所需的秒数，以及之后的摆动方向
+fn swing_wait(angle: f32, rotate_right: bool, target: f32, speed: f32) -> (f32, bool) {
+    let (distance, rotate_right) = match rotate_right {
+        true if target >= angle => (target - angle, true),
+        true => (2.0 * HOOK_MAX_ANGLE - angle - target, false),
+        false if target <= angle => (angle - target, false),
+        false => (angle + target - 2.0 * HOOK_MIN_ANGLE, true),
+    };
+    (distance / speed, rotate_right)
+}
+
+/// 报告格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Csv,
+    Json,
+}
+
+/// `simulate` 命令：解析参数，运行模拟并输出报告
+pub fn run_cli(args: &[String]) -> anyhow::Result<()> {
+    let mut options = SimOptions::default();
+    let mut format = Format::Csv;
+    let mut out: Option<PathBuf> = None;
+    let mut config_dir = PathBuf::from("assets/config");
+
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .with_context(|| format!("missing value for {flag}"))
+        };
+        match flag.as_str() {
+            "--runs" => options.runs = value()?.parse().context("invalid --runs")?,
+            "--seed" => options.seed = value()?.parse().context("invalid --seed")?,
+            "--max-levels" => {
+                options.max_levels = value()?.parse().context("invalid --max-levels")?;
+            }
+            "--difficulty" => {
+                let name = value()?;
+                options.difficulty = Difficulty::ALL
+                    .into_iter()
+                    .find(|difficulty| difficulty.label().eq_ignore_ascii_case(name))
+                    .with_context(|| format!("unknown difficulty {name}"))?;
+            }
+            "--format" => {
+                format = match value()?.as_str() {
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    other => bail!("unknown format {other} (expected csv or json)"),
+                };
+            }
+            "--out" => out = Some(PathBuf::from(value()?)),
+            "--config" => config_dir = PathBuf::from(value()?),
+            other => bail!(
+                "unknown option {other}\nusage: goldminer simulate [--runs N] [--seed N] \
+                 [--difficulty Easy|Normal|Hard|Custom] [--max-levels N] [--format csv|json] \
+                 [--out FILE] [--config DIR]"
+            ),
+        }
+    }
+
+    let config = SimConfig::load(&config_dir)?;
+    let runs = simulate(&config, &options);
+    let report = Report::new(&options, &runs);
+    let content = match format {
+        Format::Csv => report.to_csv(),
+        Format::Json => report.to_json()?,
+    };
+
+    match out {
+        Some(path) => fs::write(&path, content)
+            .with_context(|| format!("failed to write {}", path.display()))?,
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swing_wait_follows_the_pendulum() {
+        // 向右摆：目标在前方直接到达，在后方需要先摆到右端再折返
+        assert_eq!(swing_wait(0.0, true, 30.0, 10.0), (3.0, true));
+        assert_eq!(swing_wait(30.0, true, 0.0, 10.0), (12.0, false));
+        assert_eq!(swing_wait(0.0, false, -75.0, 15.0), (5.0, false));
+        assert_eq!(swing_wait(-60.0, false, 60.0, 15.0), (10.0, true));
+    }
+
+    #[test]
+    fn seeded_runs_are_reproducible() {
+        let config = SimConfig::load(Path::new("assets/config")).unwrap();
+        let options = SimOptions {
+            runs: 1,
+            max_levels: 5,
+            ..default()
+        };
+        // 内置结算规则的福袋效果也使用种子随机数
+        let pipeline = PayoutPipeline::builtin();
+        let first = simulate_run(&config, &pipeline, &options, 42);
+        assert_eq!(first, simulate_run(&config, &pipeline, &options, 42));
+
+        let level = &first.levels[0];
+        assert_eq!(level.level, 1);
+        assert!(level.money > 0);
+        // 通关的关卡记录商店花费，未通关的关卡结束本局
+        for level in &first.levels[..first.levels.len() - 1] {
+            assert!(level.cleared);
+        }
+        assert!(
+            first
+                .levels
+                .iter()
+                .all(|level| level.money >= level.shop_spend)
+        );
+    }
+}
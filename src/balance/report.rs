@@ -0,0 +1,227 @@
+//! 模拟报告：按关卡汇总通过率、金钱分布与商店购买
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::{RunResult, SimOptions};
+use crate::screens::shop::PropType;
+
+/// 一关的汇总
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LevelReport {
+    pub level: u32,
+    /// 到达该关的局数
+    pub runs: u32,
+    /// 通过该关的局数
+    pub clears: u32,
+    pub clear_rate: f32,
+    pub goal_mean: f32,
+    /// 本关结束时的总金钱
+    pub money_mean: f32,
+    pub money_p10: u32,
+    pub money_p50: u32,
+    pub money_p90: u32,
+    /// 通关后每次商店的平均花费
+    pub shop_spend_mean: f32,
+    /// 该关商店中购买最多的道具
+    pub top_item: Option<String>,
+}
+
+/// 道具的购买次数
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ItemCount {
+    pub item: String,
+    pub count: u32,
+}
+
+/// 整个模拟的报告
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Report {
+    pub runs: u32,
+    pub seed: u64,
+    pub difficulty: String,
+    /// 每局平均通过的关卡数
+    pub levels_cleared_mean: f32,
+    /// 每局在商店的平均总花费
+    pub shop_spend_per_run: f32,
+    pub levels: Vec<LevelReport>,
+    /// 按购买次数从多到少排列
+    pub items: Vec<ItemCount>,
+}
+
+impl Report {
+    pub fn new(options: &SimOptions, runs: &[RunResult]) -> Self {
+        let run_count = runs.len().max(1) as f32;
+        let max_level = runs
+            .iter()
+            .flat_map(|run| &run.levels)
+            .map(|level| level.level)
+            .max()
+            .unwrap_or(0);
+
+        let levels = (1..=max_level)
+            .map(|level| {
+                let results: Vec<_> = runs
+                    .iter()
+                    .flat_map(|run| &run.levels)
+                    .filter(|result| result.level == level)
+                    .collect();
+                let count = results.len().max(1) as f32;
+                let clears = results.iter().filter(|result| result.cleared).count() as u32;
+                let mut money: Vec<u32> = results.iter().map(|result| result.money).collect();
+                money.sort_unstable();
+
+                LevelReport {
+                    level,
+                    runs: results.len() as u32,
+                    clears,
+                    clear_rate: clears as f32 / count,
+                    goal_mean: results.iter().map(|result| result.goal as f32).sum::<f32>() / count,
+                    money_mean: money.iter().map(|money| *money as f32).sum::<f32>() / count,
+                    money_p10: percentile(&money, 0.1),
+                    money_p50: percentile(&money, 0.5),
+                    money_p90: percentile(&money, 0.9),
+                    shop_spend_mean: if clears == 0 {
+                        0.0
+                    } else {
+                        results
+                            .iter()
+                            .map(|result| result.shop_spend as f32)
+                            .sum::<f32>()
+                            / clears as f32
+                    },
+                    top_item: item_counts(results.iter().flat_map(|result| &result.purchases))
+                        .into_iter()
+                        .next()
+                        .map(|count| count.item),
+                }
+            })
+            .collect();
+
+        let all_levels = || runs.iter().flat_map(|run| &run.levels);
+        Self {
+            runs: runs.len() as u32,
+            seed: options.seed,
+            difficulty: options.difficulty.label().to_string(),
+            levels_cleared_mean: all_levels().filter(|level| level.cleared).count() as f32
+                / run_count,
+            shop_spend_per_run: all_levels()
+                .map(|level| level.shop_spend as f32)
+                .sum::<f32>()
+                / run_count,
+            levels,
+            items: item_counts(all_levels().flat_map(|level| &level.purchases)),
+        }
+    }
+
+    /// 每关一行的 CSV
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "level,runs,clears,clear_rate,goal_mean,money_mean,money_p10,money_p50,money_p90,\
+             shop_spend_mean,top_item\n",
+        );
+        for level in &self.levels {
+            csv.push_str(&format!(
+                "{},{},{},{:.3},{:.0},{:.0},{},{},{},{:.0},{}\n",
+                level.level,
+                level.runs,
+                level.clears,
+                level.clear_rate,
+                level.goal_mean,
+                level.money_mean,
+                level.money_p10,
+                level.money_p50,
+                level.money_p90,
+                level.shop_spend_mean,
+                level.top_item.as_deref().unwrap_or(""),
+            ));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self).map(|json| json + "\n")
+    }
+}
+
+/// 已排序数据的百分位数 (最近秩)
+fn percentile(sorted: &[u32], p: f32) -> u32 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (p * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// 按购买次数从多到少排列，次数相同时按名称排列
+fn item_counts<'a>(purchases: impl Iterator<Item = &'a PropType>) -> Vec<ItemCount> {
+    let mut counts: HashMap<&'static str, u32> = HashMap::new();
+    for prop in purchases {
+        *counts.entry(prop.name()).or_default() += 1;
+    }
+    let mut counts: Vec<ItemCount> = counts
+        .into_iter()
+        .map(|(item, count)| ItemCount {
+            item: item.to_string(),
+            count,
+        })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.item.cmp(&b.item)));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::balance::LevelResult;
+
+    fn level(level: u32, money: u32, cleared: bool, purchases: Vec<PropType>) -> LevelResult {
+        LevelResult {
+            level,
+            layout: format!("L{level}_1"),
+            goal: 650,
+            money,
+            cleared,
+            shop_spend: if cleared { 100 } else { 0 },
+            purchases,
+        }
+    }
+
+    #[test]
+    fn report_aggregates_levels_and_items() {
+        let runs = [
+            RunResult {
+                seed: 1,
+                levels: vec![
+                    level(1, 700, true, vec![PropType::Dynamite, PropType::Stopwatch]),
+                    level(2, 900, false, Vec::new()),
+                ],
+            },
+            RunResult {
+                seed: 2,
+                levels: vec![level(1, 300, false, Vec::new())],
+            },
+        ];
+        let report = Report::new(&SimOptions::default(), &runs);
+
+        assert_eq!(report.levels.len(), 2);
+        let first = &report.levels[0];
+        assert_eq!((first.runs, first.clears), (2, 1));
+        assert_eq!(first.clear_rate, 0.5);
+        assert_eq!((first.money_p10, first.money_p90), (300, 700));
+        assert_eq!(first.shop_spend_mean, 100.0);
+        assert_eq!(first.top_item.as_deref(), Some("Dynamite"));
+        assert_eq!(report.levels_cleared_mean, 0.5);
+        assert_eq!(report.items[1].item, "Stopwatch");
+
+        let csv = report.to_csv();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(
+            csv.lines()
+                .nth(1)
+                .unwrap()
+                .starts_with("1,2,1,0.500,650,500,300,")
+        );
+    }
+}
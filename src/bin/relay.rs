@@ -0,0 +1,313 @@
+//! 联网竞速的中继服务器
+//!
+//! 用法：`cargo run --bin relay [地址]`，默认监听 `127.0.0.1:7878`。
+//! - 一方发送 `Host` 创建房间并得到房间码，另一方用房间码 `Join`
+//! - 双方到齐后下发同一个种子，之后把各自的 `RunEvent` 转发给对手
+//! - 任意一方断开时通知对手并关闭房间
+
+#[path = "../online/protocol.rs"]
+mod protocol;
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use protocol::{
+    ClientMessage, DEFAULT_RELAY_ADDRESS, LOBBY_CODE_CHARS, LOBBY_CODE_LEN, ServerMessage,
+};
+
+/// 连接编号
+type ConnId = u64;
+
+/// 一个房间：房主与加入的对手
+struct Lobby {
+    host: ConnId,
+    guest: Option<ConnId>,
+}
+
+/// 房间与转发规则，不涉及网络读写
+struct Relay {
+    lobbies: HashMap<String, Lobby>,
+    rng: StdRng,
+}
+
+impl Relay {
+    fn new(rng: StdRng) -> Self {
+        Self {
+            lobbies: HashMap::new(),
+            rng,
+        }
+    }
+
+    fn new_code(&mut self) -> String {
+        loop {
+            let code: String = (0..LOBBY_CODE_LEN)
+                .map(|_| LOBBY_CODE_CHARS[self.rng.random_range(0..LOBBY_CODE_CHARS.len())] as char)
+                .collect();
+            if !self.lobbies.contains_key(&code) {
+                return code;
+            }
+        }
+    }
+
+    /// 连接所在的房间码
+    fn lobby_of(&self, conn: ConnId) -> Option<String> {
+        self.lobbies
+            .iter()
+            .find(|(_, lobby)| lobby.host == conn || lobby.guest == Some(conn))
+            .map(|(code, _)| code.clone())
+    }
+
+    /// 处理一条消息，返回需要发送的 (接收方, 消息)
+    fn handle(&mut self, from: ConnId, message: ClientMessage) -> Vec<(ConnId, ServerMessage)> {
+        match message {
+            ClientMessage::Host => {
+                // 同一连接重复创建时关闭之前的房间
+                let mut replies = self.disconnect(from);
+                let code = self.new_code();
+                self.lobbies.insert(
+                    code.clone(),
+                    Lobby {
+                        host: from,
+                        guest: None,
+                    },
+                );
+                replies.push((from, ServerMessage::LobbyCreated { code }));
+                replies
+            }
+            ClientMessage::Join { code } => {
+                // 房间码不区分大小写
+                let code = code.trim().to_ascii_uppercase();
+                let seed = self.rng.random();
+                match self.lobbies.get_mut(&code) {
+                    Some(lobby) if lobby.guest.is_none() && lobby.host != from => {
+                        lobby.guest = Some(from);
+                        vec![
+                            (lobby.host, ServerMessage::Start { seed }),
+                            (from, ServerMessage::Start { seed }),
+                        ]
+                    }
+                    Some(_) => vec![(
+                        from,
+                        ServerMessage::Error {
+                            message: "Lobby is full".to_string(),
+                        },
+                    )],
+                    None => vec![(
+                        from,
+                        ServerMessage::Error {
+                            message: format!("No lobby {code}"),
+                        },
+                    )],
+                }
+            }
+            ClientMessage::Event(event) => {
+                let Some(lobby) = self.lobby_of(from).and_then(|code| self.lobbies.get(&code))
+                else {
+                    return Vec::new();
+                };
+                let peer = if lobby.host == from {
+                    lobby.guest
+                } else {
+                    Some(lobby.host)
+                };
+                peer.map(|peer| (peer, ServerMessage::Peer(event)))
+                    .into_iter()
+                    .collect()
+            }
+        }
+    }
+
+    /// 连接断开：关闭所在房间并通知对手
+    fn disconnect(&mut self, conn: ConnId) -> Vec<(ConnId, ServerMessage)> {
+        let Some(lobby) = self
+            .lobby_of(conn)
+            .and_then(|code| self.lobbies.remove(&code))
+        else {
+            return Vec::new();
+        };
+        [Some(lobby.host), lobby.guest]
+            .into_iter()
+            .flatten()
+            .filter(|other| *other != conn)
+            .map(|other| (other, ServerMessage::PeerLeft))
+            .collect()
+    }
+}
+
+/// 房间状态与各连接的写入端
+struct Shared {
+    relay: Relay,
+    writers: HashMap<ConnId, TcpStream>,
+}
+
+impl Shared {
+    fn send(&mut self, replies: Vec<(ConnId, ServerMessage)>) {
+        for (conn, message) in replies {
+            if let Some(stream) = self.writers.get_mut(&conn)
+                && let Err(err) = stream.write_all(protocol::encode(&message).as_bytes())
+            {
+                eprintln!("relay: failed to write to {conn}: {err}");
+            }
+        }
+    }
+}
+
+fn handle_connection(shared: Arc<Mutex<Shared>>, conn: ConnId, stream: TcpStream) {
+    let Ok(writer) = stream.try_clone() else {
+        return;
+    };
+    shared.lock().unwrap().writers.insert(conn, writer);
+
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let Some(message) = protocol::decode::<ClientMessage>(&line) else {
+            eprintln!("relay: ignoring malformed message from {conn}");
+            continue;
+        };
+        let mut shared = shared.lock().unwrap();
+        let replies = shared.relay.handle(conn, message);
+        shared.send(replies);
+    }
+
+    let mut shared = shared.lock().unwrap();
+    shared.writers.remove(&conn);
+    let replies = shared.relay.disconnect(conn);
+    shared.send(replies);
+}
+
+/// 接受连接，每个连接一个线程
+fn serve(listener: TcpListener) {
+    let shared = Arc::new(Mutex::new(Shared {
+        relay: Relay::new(StdRng::from_os_rng()),
+        writers: HashMap::new(),
+    }));
+
+    for (conn, stream) in (0..).zip(listener.incoming()) {
+        match stream {
+            Ok(stream) => {
+                let shared = shared.clone();
+                thread::spawn(move || handle_connection(shared, conn, stream));
+            }
+            Err(err) => eprintln!("relay: failed to accept connection: {err}"),
+        }
+    }
+}
+
+fn main() {
+    let address = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_RELAY_ADDRESS.to_string());
+    let listener = match TcpListener::bind(&address) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("relay: failed to bind {address}: {err}");
+            std::process::exit(1);
+        }
+    };
+    println!("relay: listening on {address}");
+    serve(listener);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use protocol::RunEvent;
+
+    fn lobby_code(replies: &[(ConnId, ServerMessage)]) -> String {
+        match replies.last() {
+            Some((_, ServerMessage::LobbyCreated { code })) => code.clone(),
+            other => panic!("expected LobbyCreated, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn lobby_pairs_players_and_forwards_events() {
+        let mut relay = Relay::new(StdRng::seed_from_u64(1));
+        let code = lobby_code(&relay.handle(1, ClientMessage::Host));
+
+        let replies = relay.handle(
+            2,
+            ClientMessage::Join {
+                code: code.to_lowercase(),
+            },
+        );
+        let seeds: Vec<_> = replies
+            .iter()
+            .map(|(conn, message)| match message {
+                ServerMessage::Start { seed } => (*conn, *seed),
+                other => panic!("expected Start, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(seeds.len(), 2);
+        assert_eq!(seeds[0].1, seeds[1].1);
+
+        // 房间已满
+        let replies = relay.handle(3, ClientMessage::Join { code });
+        assert!(matches!(replies[..], [(3, ServerMessage::Error { .. })]));
+
+        let event = RunEvent::LevelCleared {
+            level: 1,
+            money: 700,
+        };
+        assert_eq!(
+            relay.handle(2, ClientMessage::Event(event.clone())),
+            vec![(1, ServerMessage::Peer(event))]
+        );
+
+        assert_eq!(relay.disconnect(1), vec![(2, ServerMessage::PeerLeft)]);
+        assert!(relay.lobbies.is_empty());
+    }
+
+    #[test]
+    fn relays_over_localhost() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve(listener));
+
+        let connect = || {
+            let stream = TcpStream::connect(address).unwrap();
+            let reader = BufReader::new(stream.try_clone().unwrap());
+            (stream, reader.lines())
+        };
+        let send = |stream: &mut TcpStream, message: &ClientMessage| {
+            stream
+                .write_all(protocol::encode(message).as_bytes())
+                .unwrap();
+        };
+        let receive = |lines: &mut std::io::Lines<BufReader<TcpStream>>| {
+            protocol::decode::<ServerMessage>(&lines.next().unwrap().unwrap()).unwrap()
+        };
+
+        let (mut host, mut host_lines) = connect();
+        send(&mut host, &ClientMessage::Host);
+        let ServerMessage::LobbyCreated { code } = receive(&mut host_lines) else {
+            panic!("expected LobbyCreated");
+        };
+
+        let (mut guest, mut guest_lines) = connect();
+        send(&mut guest, &ClientMessage::Join { code });
+        let host_start = receive(&mut host_lines);
+        assert!(matches!(host_start, ServerMessage::Start { .. }));
+        assert_eq!(receive(&mut guest_lines), host_start);
+
+        let event = RunEvent::EntityCollected {
+            entity_id: "BigGold".to_string(),
+            bonus: 500,
+            money: 500,
+        };
+        send(&mut guest, &ClientMessage::Event(event.clone()));
+        assert_eq!(receive(&mut host_lines), ServerMessage::Peer(event));
+
+        drop(guest_lines);
+        guest.shutdown(std::net::Shutdown::Both).unwrap();
+        assert_eq!(receive(&mut host_lines), ServerMessage::PeerLeft);
+    }
+}
@@ -8,6 +8,7 @@ use bevy::prelude::ReflectResource;
 use bevy::prelude::{FromWorld, Reflect, Resource, World};
 use bevy_common_assets::yaml::YamlAssetPlugin;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 pub struct ConfigPlugin;
 
@@ -16,6 +17,8 @@ impl Plugin for ConfigPlugin {
         app.add_plugins((
             YamlAssetPlugin::<LevelsConfig>::new(&["config/levels.yaml"]),
             YamlAssetPlugin::<EntitiesConfig>::new(&["config/entities.yaml"]),
+            YamlAssetPlugin::<GameplayConfig>::new(&["config/gameplay.yaml"]),
+            YamlAssetPlugin::<ProgressionConfig>::new(&["config/progression.yaml"]),
         ));
         app.load_resource::<ImageAssets>();
     }
@@ -62,16 +65,95 @@ pub struct EntityDescriptor {
 
     /// 移动实体（如地鼠）：移动速度
     pub speed: Option<f32>,
-    /// 移动实体：左右巡逻的范围距离
+    /// 移动实体：未配置路径时左右巡逻的范围距离
     pub move_range: Option<f32>,
 
+    /// 钻地实体：在地面停留多少秒后钻入地下（未配置则不会钻地）
+    pub burrow_interval: Option<f32>,
+    /// 钻地实体：每次在地下停留的秒数
+    pub burrow_duration: Option<f32>,
+
     /// 爆炸实体（如TNT）：被摧毁后显示的精灵图类型
     pub destroyed_type: Option<String>,
     /// 爆炸实体：被抓取的判定是否使用小型钩子动画
     pub is_destroyed_tiny: Option<bool>,
+    /// 爆炸实体：爆炸范围半径
+    pub blast_radius: Option<f32>,
+    /// 爆炸实体：被其他爆炸波及后延迟多少秒引爆
+    pub chain_delay: Option<f32>,
+    /// 爆炸实体：带有这些标签的实体不会被炸毁 (如 "gem")
+    #[serde(default)]
+    pub immune_tags: Vec<String>,
+
+    /// 计时物品：抓取后增加的秒数
+    pub time_bonus: Option<f32>,
 
     /// 实体的碰撞半径 (可选)
     pub collision_radius: Option<f32>,
+
+    /// 被炸毁后散落的碎片 (如大金块炸成小金块)
+    #[serde(default)]
+    pub debris: Vec<DebrisDescriptor>,
+
+    /// 实体标签：结算修饰器按标签匹配规则 (如 "rock", "gem")
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// 未配置或配置了未知背景时使用的关卡背景
+pub const DEFAULT_BACKGROUND: &str = "LevelA";
+
+/// 未配置 collisionRadius 时的碰撞半径 (与钩子的默认值一致)
+pub const DEFAULT_COLLISION_RADIUS: f32 = 6.0;
+
+impl EntityDescriptor {
+    /// 是否带有指定标签
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// 碰撞半径，未配置时使用默认值
+    pub fn radius(&self) -> f32 {
+        self.collision_radius.unwrap_or(DEFAULT_COLLISION_RADIUS)
+    }
+
+    /// 预期分值：随机物品取倍率的中间值，用于估算关卡总价值
+    pub fn expected_bonus(&self) -> i32 {
+        match self.entity_type {
+            EntityType::TimeBonus => 0,
+            EntityType::RandomEffect => {
+                let ratio_min = self.random_bonus_ratio_min.unwrap_or(1);
+                let ratio_max = self.random_bonus_ratio_max.unwrap_or(ratio_min);
+                self.bonus_base.unwrap_or(0) * (ratio_min + ratio_max) / 2
+            }
+            _ => self.bonus.unwrap_or(0),
+        }
+    }
+}
+
+#[cfg(test)]
+impl EntityDescriptor {
+    /// 测试用的基础实体：只设置标签，其他字段按需覆盖
+    pub fn with_tags(tags: &[&str]) -> Self {
+        serde_json::from_value(serde_json::json!({
+            "type": "Basic",
+            "tags": tags,
+        }))
+        .unwrap()
+    }
+}
+
+/// 碎片描述：被炸毁后生成的低价值实体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebrisDescriptor {
+    /// 碎片实体，引用 Entities.config 中的 Key (例如 "MiniGold")
+    #[serde(rename = "type")]
+    pub entity_id: String,
+    /// 碎片数量，默认 1 个
+    pub count: Option<usize>,
+    /// 碎片价值相对其自身分值的倍率，默认 1.0
+    pub bonus_ratio: Option<f32>,
 }
 
 /// 实体行为分类
@@ -85,6 +167,378 @@ pub enum EntityType {
     MoveAround,
     /// 爆炸物：碰撞后会销毁周围物体
     Explosive,
+    /// 计时物品：抓取后增加关卡剩余时间而非金钱
+    TimeBonus,
+}
+
+// --- progression.yaml 对应的结构 ---
+
+/// 关卡进度：目标金额曲线、关卡池与背景
+#[derive(Debug, Clone, Serialize, Deserialize, bevy::asset::Asset, bevy::reflect::TypePath)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProgressionConfig {
+    /// 目标金额曲线
+    pub goal: GoalCurve,
+    /// 进度阶段，按起始关卡升序排列，最后一个阶段一直持续
+    pub stages: Vec<ProgressionStage>,
+    /// 关卡布局编号对应的背景，取不大于该编号的最大一项
+    pub backgrounds: BTreeMap<u32, String>,
+    /// 程序化关卡生成
+    pub generator: GeneratorConfig,
+    /// 限时挑战模式依次进行的关卡 (例如 "L1_1")
+    pub time_attack: Vec<String>,
+}
+
+impl Default for ProgressionConfig {
+    /// 与原版一致：1-3 关依次进行，之后在 3-9 之间循环
+    fn default() -> Self {
+        Self {
+            goal: GoalCurve::default(),
+            stages: vec![
+                ProgressionStage {
+                    from: 1,
+                    pool: vec![1, 2, 3],
+                    ..Default::default()
+                },
+                ProgressionStage {
+                    from: 4,
+                    pool: vec![4, 5, 6, 7, 8, 9, 3],
+                    ..Default::default()
+                },
+            ],
+            backgrounds: BTreeMap::from([
+                (1, "LevelA".to_string()),
+                (3, "LevelB".to_string()),
+                (5, "LevelC".to_string()),
+                (7, "LevelD".to_string()),
+                (9, "LevelE".to_string()),
+            ]),
+            generator: GeneratorConfig::default(),
+            time_attack: ["L1_1", "L2_1", "L3_1", "L4_1", "L5_1"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+impl ProgressionConfig {
+    /// 第 level 关所属的阶段
+    pub fn stage_for(&self, level: u32) -> Option<&ProgressionStage> {
+        self.stages
+            .iter()
+            .filter(|stage| stage.from <= level)
+            .max_by_key(|stage| stage.from)
+    }
+
+    /// 为第 level 关选择关卡布局 (例如 "L4_2")，
+    /// 达到生成起始关卡后返回带种子的生成关卡 (例如 "GEN_12345")
+    pub fn pick_layout(&self, level: u32, rng: &mut impl rand::Rng) -> String {
+        if self
+            .generator
+            .from_level
+            .is_some_and(|from_level| level >= from_level)
+        {
+            return format!("{GENERATED_LEVEL_PREFIX}{}", rng.random::<u32>());
+        }
+
+        let Some(stage) = self.stage_for(level).filter(|stage| !stage.pool.is_empty()) else {
+            return "L1_1".to_string();
+        };
+
+        let layout = match stage.order {
+            PoolOrder::Cycle => stage.pool[(level - stage.from) as usize % stage.pool.len()],
+            PoolOrder::Random => stage.pool[rng.random_range(0..stage.pool.len())],
+        };
+        let variant = stage.pick_variant(rng);
+        format!("L{layout}_{variant}")
+    }
+
+    /// 关卡布局编号对应的背景图片 ID
+    pub fn background_for(&self, layout: u32) -> &str {
+        self.backgrounds
+            .range(..=layout)
+            .next_back()
+            .or_else(|| self.backgrounds.iter().next())
+            .map(|(_, image)| image.as_str())
+            .unwrap_or(DEFAULT_BACKGROUND)
+    }
+}
+
+/// 生成关卡 ID 的前缀，后接随机种子
+pub const GENERATED_LEVEL_PREFIX: &str = "GEN_";
+
+/// 程序化关卡生成参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GeneratorConfig {
+    /// 从第几关开始使用生成关卡，未配置时只使用手工关卡
+    pub from_level: Option<u32>,
+    /// 场上总价值 = 本关目标增幅 × valueRatio
+    pub value_ratio: f32,
+    /// 实体数量上限
+    pub max_entities: usize,
+    /// 两个实体碰撞半径之外的最小间距
+    pub min_spacing: f32,
+    /// 预期分值不低于该值的物品视为贵重物品
+    pub valuable_bonus: i32,
+    /// 在贵重物品旁布置 TNT 的概率
+    pub tnt_cluster_chance: f32,
+    /// 每处 TNT 的数量上限
+    pub tnt_per_cluster: usize,
+    /// 生成关卡可用的背景
+    pub backgrounds: Vec<String>,
+    /// 按深度划分的实体权重，按 untilY 升序排列
+    pub layers: Vec<DepthLayer>,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        let layer = |until_y: f32, weights: &[(&str, u32)]| DepthLayer {
+            until_y,
+            weights: weights
+                .iter()
+                .map(|(entity_id, weight)| (entity_id.to_string(), *weight))
+                .collect(),
+        };
+
+        Self {
+            from_level: None,
+            value_ratio: 1.3,
+            max_entities: 24,
+            min_spacing: 10.0,
+            valuable_bonus: 500,
+            tnt_cluster_chance: 0.35,
+            tnt_per_cluster: 2,
+            backgrounds: vec![
+                "LevelC".to_string(),
+                "LevelD".to_string(),
+                "LevelE".to_string(),
+            ],
+            layers: vec![
+                layer(
+                    120.0,
+                    &[
+                        ("MiniGold", 4),
+                        ("NormalGold", 3),
+                        ("MiniRock", 3),
+                        ("NormalRock", 2),
+                        ("QuestionBag", 1),
+                    ],
+                ),
+                layer(
+                    180.0,
+                    &[
+                        ("NormalGold", 2),
+                        ("NormalGoldPlus", 3),
+                        ("NormalRock", 2),
+                        ("BigRock", 2),
+                        ("QuestionBag", 2),
+                        ("Mole", 2),
+                        ("Skull", 1),
+                    ],
+                ),
+                layer(
+                    MINE_BOTTOM,
+                    &[
+                        ("BigGold", 3),
+                        ("Diamond", 2),
+                        ("BigRock", 2),
+                        ("Mole", 1),
+                        ("Bone", 1),
+                    ],
+                ),
+            ],
+        }
+    }
+}
+
+/// 深度层：y 不超过 untilY 的位置按权重抽取实体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepthLayer {
+    pub until_y: f32,
+    /// 实体 ID 与权重
+    pub weights: BTreeMap<String, u32>,
+}
+
+/// 目标金额曲线：
+/// 第 1 关 goal = initial + initialAddon，
+/// 之后每关 addon 增加 addonStep (直到 addonGrowthUntil 关)，goal += addon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GoalCurve {
+    pub initial: u32,
+    pub initial_addon: u32,
+    pub addon_step: u32,
+    pub addon_growth_until: u32,
+    /// 指定关卡的目标金额，覆盖公式结果
+    pub overrides: BTreeMap<u32, u32>,
+}
+
+impl Default for GoalCurve {
+    fn default() -> Self {
+        Self {
+            initial: 375,
+            initial_addon: 275,
+            addon_step: 270,
+            addon_growth_until: 9,
+            overrides: BTreeMap::new(),
+        }
+    }
+}
+
+/// 进度阶段：从 from 关开始使用的关卡池
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProgressionStage {
+    /// 起始关卡 (包含)
+    pub from: u32,
+    /// 可选的关卡布局编号 (对应 levels.yaml 中的 L{n}_x)
+    pub pool: Vec<u32>,
+    /// 关卡池的取用顺序
+    pub order: PoolOrder,
+    /// 变体 1..=n 的权重
+    pub variant_weights: Vec<u32>,
+}
+
+impl Default for ProgressionStage {
+    fn default() -> Self {
+        Self {
+            from: 1,
+            pool: Vec::new(),
+            order: PoolOrder::Cycle,
+            variant_weights: vec![1, 1, 1],
+        }
+    }
+}
+
+impl ProgressionStage {
+    /// 按权重随机选择变体编号 (从 1 开始)
+    pub fn pick_variant(&self, rng: &mut impl rand::Rng) -> usize {
+        let total: u32 = self.variant_weights.iter().sum();
+        if total == 0 {
+            return 1;
+        }
+
+        let mut roll = rng.random_range(0..total);
+        for (index, weight) in self.variant_weights.iter().enumerate() {
+            if roll < *weight {
+                return index + 1;
+            }
+            roll -= weight;
+        }
+        1
+    }
+}
+
+/// 关卡池取用顺序
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PoolOrder {
+    /// 按关卡依次循环
+    #[default]
+    Cycle,
+    /// 每关随机抽取
+    Random,
+}
+
+// --- gameplay.yaml 对应的结构 ---
+
+/// 玩法参数：不属于单个实体或关卡的全局数值
+#[derive(
+    Debug, Clone, Default, Serialize, Deserialize, bevy::asset::Asset, bevy::reflect::TypePath,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GameplayConfig {
+    /// 抛掷炸药
+    #[serde(default)]
+    pub thrown_dynamite: ThrownDynamiteConfig,
+    /// 连击倍率
+    #[serde(default)]
+    pub combo: ComboConfig,
+    /// 自定义难度的缩放系数
+    #[serde(default)]
+    pub custom_difficulty: DifficultyScaling,
+}
+
+/// 难度缩放系数：1.0 表示与原版一致
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DifficultyScaling {
+    /// 关卡时长倍率
+    pub time_scale: f32,
+    /// 目标金额增幅倍率
+    pub goal_scale: f32,
+    /// 商店价格倍率
+    pub price_scale: f32,
+    /// 钩子摆动速度倍率
+    pub swing_scale: f32,
+    /// 福袋额外效果概率倍率
+    pub bag_luck: f32,
+}
+
+impl Default for DifficultyScaling {
+    fn default() -> Self {
+        Self {
+            time_scale: 1.0,
+            goal_scale: 1.0,
+            price_scale: 1.0,
+            swing_scale: 1.0,
+            bag_luck: 1.0,
+        }
+    }
+}
+
+/// 抛掷炸药参数：钩子空闲时沿钩子角度扔出炸药
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ThrownDynamiteConfig {
+    /// 飞行速度 (像素/秒)
+    pub speed: f32,
+    /// 最大飞行距离，超过后在空中引爆
+    pub range: f32,
+    /// 爆炸范围半径
+    pub blast_radius: f32,
+    /// 炸药自身的碰撞半径
+    pub collision_radius: f32,
+}
+
+/// 连击参数：连续抓取贵重物品时提升结算倍率
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ComboConfig {
+    /// 是否启用连击
+    pub enabled: bool,
+    /// 计入连击的实体标签
+    pub tags: Vec<String>,
+    /// 打断连击的实体标签
+    pub break_tags: Vec<String>,
+    /// 每次连击增加的倍率
+    pub step: f32,
+    /// 倍率上限
+    pub max_multiplier: f32,
+}
+
+impl Default for ComboConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            tags: vec!["gold".to_string(), "gem".to_string()],
+            break_tags: vec!["rock".to_string(), "junk".to_string()],
+            step: 0.25,
+            max_multiplier: 2.0,
+        }
+    }
+}
+
+impl Default for ThrownDynamiteConfig {
+    fn default() -> Self {
+        Self {
+            speed: 180.0,
+            range: 230.0,
+            blast_radius: 17.5,
+            collision_radius: 3.0,
+        }
+    }
 }
 
 // --- levels.yaml 对应的结构 ---
@@ -99,6 +553,88 @@ pub struct LevelDescriptor {
     pub entities: Vec<LevelEntity>,
 }
 
+/// 矿区范围 (LÖVE 坐标)：顶部为地面，左右允许少量越界 (巡逻实体从屏幕外走入)
+pub const MINE_TOP: f32 = 50.0;
+pub const MINE_BOTTOM: f32 = 240.0;
+pub const MINE_LEFT: f32 = -20.0;
+pub const MINE_RIGHT: f32 = 340.0;
+
+impl LevelDescriptor {
+    /// 检查关卡布局，手工关卡与生成关卡使用同一套规则，返回发现的问题
+    /// - 实体与携带物品必须在 entities.yaml 中定义
+    /// - 坐标必须位于矿区范围内
+    /// - 携带物品与移动路径只对 MoveAround 实体有效
+    /// - 实体中心不能落在另一个实体的碰撞范围内
+    /// - 关卡中至少有一个有价值的物品
+    pub fn validate(&self, entities: &EntitiesConfig) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        for (index, level_entity) in self.entities.iter().enumerate() {
+            let Some(descriptor) = entities.entities.get(&level_entity.entity_id) else {
+                issues.push(format!(
+                    "#{index}: unknown entity {}",
+                    level_entity.entity_id
+                ));
+                continue;
+            };
+
+            let pos = &level_entity.pos;
+            if !(MINE_LEFT..=MINE_RIGHT).contains(&pos.x)
+                || !(MINE_TOP..=MINE_BOTTOM).contains(&pos.y)
+            {
+                issues.push(format!(
+                    "#{index}: {} at ({}, {}) is outside the mine",
+                    level_entity.entity_id, pos.x, pos.y
+                ));
+            }
+
+            let is_movable = descriptor.entity_type == EntityType::MoveAround;
+            if let Some(carries) = &level_entity.carries {
+                if !is_movable {
+                    issues.push(format!(
+                        "#{index}: {} cannot carry items",
+                        level_entity.entity_id
+                    ));
+                }
+                if !entities.entities.contains_key(carries) {
+                    issues.push(format!("#{index}: unknown carried entity {carries}"));
+                }
+            }
+            if level_entity.path.is_some() && !is_movable {
+                issues.push(format!(
+                    "#{index}: {} cannot follow a path",
+                    level_entity.entity_id
+                ));
+            }
+
+            for (other_index, other) in self.entities.iter().enumerate().skip(index + 1) {
+                let Some(other_descriptor) = entities.entities.get(&other.entity_id) else {
+                    continue;
+                };
+                let distance = (pos.x - other.pos.x).hypot(pos.y - other.pos.y);
+                if distance < descriptor.radius().max(other_descriptor.radius()) {
+                    issues.push(format!(
+                        "#{index}: {} overlaps #{other_index}: {}",
+                        level_entity.entity_id, other.entity_id
+                    ));
+                }
+            }
+        }
+
+        let has_value = self.entities.iter().any(|level_entity| {
+            entities
+                .entities
+                .get(&level_entity.entity_id)
+                .is_some_and(|descriptor| descriptor.expected_bonus() > 0)
+        });
+        if !has_value {
+            issues.push("level has no valuable entities".to_string());
+        }
+
+        issues
+    }
+}
+
 /// 关卡中的实体实例
 #[derive(Debug, Clone, Component, Serialize, Deserialize)]
 pub struct LevelEntity {
@@ -110,7 +646,47 @@ pub struct LevelEntity {
     pub pos: Position,
 
     /// 可选的移动方向（仅对 MoveAround 类型有效）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dir: Option<Direction>,
+
+    /// 可选的携带物品，引用 Entities.config 中的 Key（仅对 MoveAround 类型有效）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carries: Option<String>,
+
+    /// 可选的移动路径（仅对 MoveAround 类型有效），未配置时沿 dir 左右巡逻
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PatrolPath>,
+}
+
+/// 移动路径：从生成坐标出发，依次经过各个路点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatrolPath {
+    /// 到达终点后的行为（默认原路折返）
+    #[serde(default)]
+    pub mode: PathMode,
+    /// 路点列表（不包含生成坐标）
+    pub points: Vec<Waypoint>,
+}
+
+/// 路径循环方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PathMode {
+    /// 到达终点后回到起点，首尾相连
+    Loop,
+    /// 到达终点后原路折返
+    #[default]
+    PingPong,
+}
+
+/// 路点：坐标与到达该点的移动参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Waypoint {
+    pub x: f32,
+    pub y: f32,
+    /// 通往该路点的路段速度，未配置时使用实体的 speed
+    pub speed: Option<f32>,
+    /// 到达该路点后的停留秒数，默认不停留
+    pub pause: Option<f32>,
 }
 
 /// 坐标位置
@@ -195,7 +771,7 @@ pub struct ImageAssets {
     #[dependency]
     mole_sheet: Handle<Image>,
     #[dependency]
-    mole_with_diamond_sheet: Handle<Image>,
+    clock: Handle<Image>,
     #[dependency]
     bigger_explosive_fx: Handle<Image>,
     #[dependency]
@@ -258,7 +834,7 @@ impl FromWorld for ImageAssets {
             rock_collector_book: assets.load("images/rock_collectors_book.png"),
             gem_polish: assets.load("images/gem_polish.png"),
             mole_sheet: assets.load("images/mole_sheet.png"),
-            mole_with_diamond_sheet: assets.load("images/mole_with_diamond_sheet.png"),
+            clock: assets.load("images/clock.png"),
             bigger_explosive_fx: assets.load("images/bigger_explosive_fx_sheet.png"),
             gold_big_fx: assets.load("images/gold_big_fx_sheet.png"),
             explosive_fx: assets.load("images/explosive_fx_sheet.png"),
@@ -308,8 +884,8 @@ impl ImageAssets {
             "LuckyClover" => Some(self.lucky_colver.clone()),
             "RockCollectorsBook" => Some(self.rock_collector_book.clone()),
             "GemPolish" => Some(self.gem_polish.clone()),
-            "Mole" => Some(self.mole_sheet.clone()),
-            "MoleWithDiamond" => Some(self.mole_with_diamond_sheet.clone()),
+            "Mole" | "BurrowingMole" => Some(self.mole_sheet.clone()),
+            "Clock" | "Stopwatch" => Some(self.clock.clone()),
             "BiggerExplosiveFX" => Some(self.bigger_explosive_fx.clone()),
             "BigGoldFX" => Some(self.gold_big_fx.clone()),
             "ExplosiveFX" => Some(self.explosive_fx.clone()),
@@ -0,0 +1,440 @@
+//! 自动操作 (机器人)
+//!
+//! 代替键盘与手柄操作钩子，用于无人值守的平衡测试与演示：
+//! - 估算每个可见物品的每秒收益：结算管线的预期金额 ÷ (下放时间 + 回收时间)，
+//!   回收速度与游戏中相同，由质量、力量与 `HOOK_GRAB_SPEED` 决定
+//! - 钩子摆到的角度上第一个会碰到的物品接近整轮摆动中的最优收益时发射
+//! - 回收低价值的重物 (例如岩石) 时使用炸药
+//! - 商店中按优先级购买负担得起的道具 (见 `shop_choice`)
+//! - 每个玩家槽位可以单独切换为机器人：F5 切换 1P，F6 切换 2P
+
+use bevy::prelude::*;
+
+use crate::AppSystems;
+use crate::config::{EntityDescriptor, EntityType, LevelEntity};
+use crate::demo::entity::{Burrowed, Carrying};
+use crate::demo::hook::{
+    HOOK_COLLISION_OFFSET, HOOK_GRAB_SPEED, HOOK_MAX_ANGLE, HOOK_MIN_ANGLE, Hook, HookStats,
+    HookUpgrade,
+};
+use crate::demo::payout::{PayoutContext, PayoutPipeline};
+use crate::demo::player::{Inventories, PlayerResource, PlayerSlot};
+use crate::screens::Screen;
+use crate::screens::shop::PropType;
+use crate::screens::stats::LevelStats;
+
+/// 当前射线上的收益达到最优收益的该比例即发射
+const FIRE_THRESHOLD: f32 = 0.9;
+/// 回收收益低于该值 (金钱/秒) 的物品值得用炸药炸掉
+const LOW_VALUE_RATE: f32 = 10.0;
+/// 剩余回收时间超过该秒数才使用炸药
+const MIN_DYNAMITE_REEL_SECONDS: f32 = 1.5;
+/// 每件道具最多花费当前金钱的比例
+const MAX_SPEND_RATIO: f32 = 1.0 / 3.0;
+/// 炸药库存低于该数量时才购买
+const DYNAMITE_STOCK: i32 = 3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Controllers>();
+    app.init_resource::<BotIntents>();
+    app.add_systems(
+        Update,
+        (
+            toggle_controllers,
+            record_bot_intents.run_if(in_state(Screen::Gameplay)),
+        )
+            .chain()
+            .in_set(AppSystems::RecordInput),
+    );
+}
+
+/// 操作来源
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Controller {
+    /// 键盘与手柄
+    #[default]
+    Human,
+    /// 机器人
+    Bot,
+}
+
+/// 各玩家槽位的操作来源
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Controllers(pub [Controller; 2]);
+
+impl Controllers {
+    pub fn is_bot(&self, slot: PlayerSlot) -> bool {
+        self.0[slot.0] == Controller::Bot
+    }
+
+    pub fn set(&mut self, slot: PlayerSlot, controller: Controller) {
+        self.0[slot.0] = controller;
+    }
+
+    /// 是否有任意槽位由机器人操作
+    pub fn any_bot(&self) -> bool {
+        self.0.contains(&Controller::Bot)
+    }
+}
+
+/// 一个钩子在本帧的操作
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HookIntent {
+    pub fire: bool,
+    pub use_dynamite: bool,
+}
+
+/// 机器人在本帧的决定，由钩子输入系统读取
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BotIntents {
+    pub hooks: [HookIntent; 2],
+    /// 已达成目标且没有值得抓的物品时跳过关卡
+    pub skip: bool,
+}
+
+/// 机器人眼中的一个物品
+#[derive(Debug, Clone, PartialEq)]
+pub struct Target {
+    pub entity: Entity,
+    pub pos: Vec2,
+    pub radius: f32,
+    /// 预期收益 (已计入道具加成)，爆炸物等不值得抓的物品为 0
+    pub value: f32,
+    pub mass: f32,
+    /// 没有石头收藏书时的岩石
+    pub is_rock: bool,
+}
+
+impl Target {
+    /// 按结算管线估算收益 (与实际结算使用同一组修饰器)，携带物品单独估算后累加
+    pub fn new(
+        entity: Entity,
+        pos: Vec2,
+        pipeline: &PayoutPipeline,
+        ctx: &PayoutContext,
+        carrying: Option<&Carrying>,
+    ) -> Self {
+        let descriptor = ctx.descriptor;
+        let player = ctx.player;
+        let mut value = match descriptor.entity_type {
+            EntityType::Explosive => 0.0,
+            _ => pipeline.expected_payout(ctx),
+        };
+        if let Some(carrying) = carrying {
+            value += pipeline.expected_payout(&PayoutContext {
+                entity_id: &carrying.entity_id,
+                descriptor: &carrying.descriptor,
+                ..*ctx
+            });
+        }
+
+        Self {
+            entity,
+            pos,
+            radius: descriptor.radius(),
+            value,
+            mass: descriptor.mass.unwrap_or(1.0),
+            is_rock: descriptor.has_tag("rock") && !player.has_rock_collectors_book,
+        }
+    }
+}
+
+/// 机器人眼中的钩子与玩家
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookView {
+    pub origin: Vec2,
+    pub angle: f32,
+    pub max_length: f32,
+    pub claw_radius: f32,
+    /// 下放速度
+    pub descent_speed: f32,
+    pub strength: f32,
+    pub has_strength_drink: bool,
+}
+
+impl HookView {
+    pub fn new(hook: &Hook, hook_stats: &HookStats, player: &PlayerResource) -> Self {
+        Self {
+            origin: hook.origin,
+            angle: hook.angle,
+            max_length: hook_stats.max_length(),
+            claw_radius: hook_stats.collision_radius(),
+            descent_speed: hook_stats.grab_speed(),
+            strength: player.strength,
+            has_strength_drink: player.has_strength_drink,
+        }
+    }
+
+    /// 回收速度，与钩子回收逻辑一致
+    pub fn reel_speed(&self, mass: f32) -> f32 {
+        let mass = if self.has_strength_drink {
+            mass / 1.5
+        } else {
+            mass
+        };
+        HOOK_GRAB_SPEED * self.strength / mass
+    }
+
+    /// 瞄准物品所需的钩子角度 (度)
+    pub fn aim_angle(&self, target: &Target) -> f32 {
+        let offset = target.pos - self.origin;
+        offset.x.atan2(-offset.y).to_degrees()
+    }
+
+    /// 沿指定角度发射时第一个碰到的物品，以及碰到时的绳长
+    pub fn first_hit<'a>(&self, angle: f32, targets: &'a [Target]) -> Option<(&'a Target, f32)> {
+        let angle_rad = angle.to_radians();
+        let dir = Vec2::new(angle_rad.sin(), -angle_rad.cos());
+
+        targets
+            .iter()
+            .filter_map(|target| {
+                let offset = target.pos - self.origin;
+                let along = offset.dot(dir);
+                let across = offset.perp_dot(dir).abs();
+                let reach = self.claw_radius + target.radius;
+                if across >= reach {
+                    return None;
+                }
+                // 碰撞圆心 (末端 + 偏移) 进入物品半径时的绳长
+                let length =
+                    (along - (reach * reach - across * across).sqrt() - HOOK_COLLISION_OFFSET)
+                        .max(0.0);
+                (along > 0.0 && length <= self.max_length).then_some((target, length))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+
+    /// 每秒收益：收益 ÷ (下放时间 + 回收时间)
+    pub fn value_rate(&self, target: &Target, length: f32) -> f32 {
+        let seconds = length / self.descent_speed + length / self.reel_speed(target.mass);
+        target.value / seconds.max(0.1)
+    }
+
+    /// 整轮摆动中能直接抓到的每秒收益最高的一次发射：(物品, 角度, 绳长, 每秒收益)
+    pub fn best_shot<'a>(&self, targets: &'a [Target]) -> Option<(&'a Target, f32, f32, f32)> {
+        targets
+            .iter()
+            .filter_map(|target| {
+                let angle = self.aim_angle(target);
+                if !(HOOK_MIN_ANGLE..=HOOK_MAX_ANGLE).contains(&angle) {
+                    return None;
+                }
+                let (hit, length) = self.first_hit(angle, targets)?;
+                (hit.entity == target.entity)
+                    .then(|| (hit, angle, length, self.value_rate(hit, length)))
+            })
+            .filter(|(.., rate)| *rate > 0.0)
+            .max_by(|(.., a), (.., b)| a.total_cmp(b))
+    }
+
+    /// 整轮摆动中能直接抓到的物品的最高每秒收益
+    pub fn best_rate(&self, targets: &[Target]) -> f32 {
+        self.best_shot(targets).map_or(0.0, |(.., rate)| rate)
+    }
+
+    /// 当前角度上第一个碰到的物品接近最优收益时发射
+    pub fn should_fire(&self, targets: &[Target]) -> bool {
+        let Some((hit, length)) = self.first_hit(self.angle, targets) else {
+            return false;
+        };
+        let rate = self.value_rate(hit, length);
+        rate > 0.0 && rate >= self.best_rate(targets) * FIRE_THRESHOLD
+    }
+
+    /// 回收中的物品是否值得炸掉：岩石或收益过低，且还要拉很久
+    pub fn should_use_dynamite(&self, grabbed: &Target, length: f32) -> bool {
+        let remaining = length / self.reel_speed(grabbed.mass);
+        let rate = grabbed.value / remaining.max(0.1);
+        remaining > MIN_DYNAMITE_REEL_SECONDS && (grabbed.is_rock || rate < LOW_VALUE_RATE)
+    }
+}
+
+/// 商店中的购买优先级，数值越小越优先，不购买的道具返回 None
+fn shop_priority(prop: PropType, player: &PlayerResource) -> Option<u32> {
+    match prop {
+        PropType::StrengthDrink => Some(0),
+        PropType::HookUpgrade(HookUpgrade::FasterDescent) => Some(1),
+        PropType::Dynamite if player.dynamite_count < DYNAMITE_STOCK => Some(2),
+        PropType::HookUpgrade(_) => Some(3),
+        PropType::Stopwatch => Some(4),
+        PropType::GemPolish => Some(5),
+        PropType::LuckyClover => Some(6),
+        PropType::Dynamite | PropType::RockCollectorsBook => None,
+    }
+}
+
+/// 机器人要购买的商品序号：优先级最高且价格不超过金钱一定比例的商品
+pub fn shop_choice(
+    items: &[(PropType, u32)],
+    money: u32,
+    player: &PlayerResource,
+) -> Option<usize> {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, price))| *price as f32 <= money as f32 * MAX_SPEND_RATIO)
+        .filter_map(|(index, (prop, _))| Some((shop_priority(*prop, player)?, index)))
+        .min()
+        .map(|(_, index)| index)
+}
+
+/// F5 / F6 切换 1P / 2P 的操作来源
+fn toggle_controllers(input: Res<ButtonInput<KeyCode>>, mut controllers: ResMut<Controllers>) {
+    for (key, slot) in [(KeyCode::F5, PlayerSlot(0)), (KeyCode::F6, PlayerSlot(1))] {
+        if input.just_pressed(key) {
+            let controller = if controllers.is_bot(slot) {
+                Controller::Human
+            } else {
+                Controller::Bot
+            };
+            controllers.set(slot, controller);
+            info!("{} controller: {controller:?}", slot.label());
+        }
+    }
+}
+
+fn record_bot_intents(
+    controllers: Res<Controllers>,
+    mut intents: ResMut<BotIntents>,
+    inventories: Inventories,
+    hook_stats: Res<HookStats>,
+    stats: Res<LevelStats>,
+    pipeline: Res<PayoutPipeline>,
+    q_hook: Query<(&Hook, &PlayerSlot)>,
+    q_entities: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &LevelEntity,
+            &EntityDescriptor,
+            Option<&Carrying>,
+        ),
+        (Without<Hook>, Without<Burrowed>),
+    >,
+) {
+    *intents = BotIntents::default();
+    if !controllers.any_bot() {
+        return;
+    }
+
+    let grabbed: Vec<Entity> = q_hook
+        .iter()
+        .filter_map(|(hook, _)| hook.grabed_entity)
+        .collect();
+    let mut best_rate: f32 = 0.0;
+
+    for (hook, slot) in &q_hook {
+        if !controllers.is_bot(*slot) {
+            continue;
+        }
+        let player = inventories.get(*slot);
+        let targets: Vec<Target> = q_entities
+            .iter()
+            .map(|(entity, transform, level_entity, descriptor, carrying)| {
+                let pos = transform.translation().truncate();
+                let ctx = PayoutContext {
+                    entity_id: &level_entity.entity_id,
+                    descriptor,
+                    player,
+                    scaling: &stats.scaling,
+                };
+                Target::new(entity, pos, &pipeline, &ctx, carrying)
+            })
+            .collect();
+        let view = HookView::new(hook, &hook_stats, player);
+        let intent = &mut intents.hooks[slot.0];
+
+        if hook.is_backing {
+            if let Some(entity) = hook.grabed_entity
+                && let Some(target) = targets.iter().find(|target| target.entity == entity)
+            {
+                intent.use_dynamite = inventories.dynamite(*slot) > 0
+                    && !player.is_using_dynamite
+                    && view.should_use_dynamite(target, hook.length);
+            }
+            continue;
+        }
+
+        let free: Vec<Target> = targets
+            .into_iter()
+            .filter(|target| !grabbed.contains(&target.entity))
+            .collect();
+        best_rate = best_rate.max(view.best_rate(&free));
+        if !hook.is_grabing && !hook.is_showing_bonus {
+            intent.fire = view.should_fire(&free);
+        }
+    }
+
+    // 达成目标后没有值得抓的物品时跳过关卡
+    let idle = q_hook
+        .iter()
+        .all(|(hook, _)| !hook.is_grabing && !hook.is_backing && !hook.is_showing_bonus);
+    intents.skip = stats.reach_goal() && idle && best_rate <= 0.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(index: u32, pos: Vec2, value: f32, mass: f32) -> Target {
+        Target {
+            entity: Entity::from_raw_u32(index).unwrap(),
+            pos,
+            radius: 6.0,
+            value,
+            mass,
+            is_rock: false,
+        }
+    }
+
+    fn view(angle: f32) -> HookView {
+        HookView {
+            origin: Vec2::ZERO,
+            angle,
+            max_length: 230.0,
+            claw_radius: 6.0,
+            descent_speed: HOOK_GRAB_SPEED,
+            strength: 1.0,
+            has_strength_drink: false,
+        }
+    }
+
+    #[test]
+    fn fires_only_when_aimed_at_the_best_rate() {
+        // 正下方的轻金块与右下方同价值的重石块
+        let gold = target(1, Vec2::new(0.0, -100.0), 250.0, 2.0);
+        let heavy = target(2, Vec2::new(100.0, -100.0), 250.0, 7.0);
+        let targets = [gold.clone(), heavy.clone()];
+
+        let straight_down = view(0.0);
+        assert_eq!(straight_down.first_hit(0.0, &targets).unwrap().0, &gold);
+        assert!(straight_down.should_fire(&targets));
+
+        let toward_heavy = view(straight_down.aim_angle(&heavy));
+        assert_eq!(toward_heavy.aim_angle(&heavy).round(), 45.0);
+        assert!(!toward_heavy.should_fire(&targets));
+        // 空射线不发射
+        assert!(!view(-60.0).should_fire(&targets));
+    }
+
+    #[test]
+    fn dynamites_rocks_and_buys_useful_props() {
+        let mut rock = target(1, Vec2::new(0.0, -150.0), 20.0, 7.0);
+        rock.is_rock = true;
+        let hook = view(0.0);
+        assert!(hook.should_use_dynamite(&rock, 150.0));
+        assert!(!hook.should_use_dynamite(&rock, 5.0));
+
+        let player = PlayerResource::default();
+        let items = [
+            (PropType::RockCollectorsBook, 10),
+            (PropType::Stopwatch, 60),
+            (PropType::StrengthDrink, 400),
+        ];
+        // 力量药水太贵 (超过 1/3)，买秒表
+        assert_eq!(shop_choice(&items, 600, &player), Some(1));
+        assert_eq!(shop_choice(&items, 1200, &player), Some(2));
+        assert_eq!(shop_choice(&items, 30, &player), None);
+    }
+}
@@ -0,0 +1,138 @@
+//! 连击倍率
+//!
+//! 连续抓取金块、宝石等贵重物品时累积连击，结算金额乘以连击倍率：
+//! - 抓到石头、杂物或空钩回收时连击中断
+//! - 规则与上限由 gameplay.yaml 的 `combo` 配置
+//! - 本局最高连击记录在 `LevelStats` 中
+
+use bevy::prelude::*;
+
+use crate::config::{ComboConfig, GameplayConfig};
+use crate::demo::level::GameplayHandle;
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ComboState>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_combo);
+    app.add_systems(Update, update_combo_text.run_if(in_state(Screen::Gameplay)));
+}
+
+/// HUD 中显示在金钱后的连击倍率
+#[derive(Component)]
+pub struct ComboText;
+
+/// 当前关卡的连击状态
+#[derive(Resource, Debug, Default)]
+pub struct ComboState {
+    /// 连击规则，进入关卡时从 gameplay.yaml 读取
+    pub config: ComboConfig,
+    /// 连续抓取贵重物品的次数
+    pub count: u32,
+    /// 当前倍率，用于 HUD 显示
+    pub multiplier: f32,
+}
+
+impl ComboState {
+    /// 记录一次抓取，返回本次结算应使用的倍率
+    pub fn register_grab(&mut self, has_tag: impl Fn(&str) -> bool) -> f32 {
+        if !self.config.enabled {
+            return 1.0;
+        }
+
+        if self.config.break_tags.iter().any(|tag| has_tag(tag)) {
+            self.break_combo();
+            return 1.0;
+        }
+
+        // 不属于连击也不打断连击的物品 (如福袋) 保持当前连击
+        if !self.config.tags.iter().any(|tag| has_tag(tag)) {
+            return 1.0;
+        }
+
+        self.count += 1;
+        self.multiplier = Self::multiplier_for(&self.config, self.count);
+        self.multiplier
+    }
+
+    /// 中断连击 (抓到石头或空钩回收)
+    pub fn break_combo(&mut self) {
+        self.count = 0;
+        self.multiplier = 1.0;
+    }
+
+    /// 第 count 次连击的倍率：首次为 1，之后逐次递增直到上限
+    pub fn multiplier_for(config: &ComboConfig, count: u32) -> f32 {
+        let bonus = config.step * count.saturating_sub(1) as f32;
+        (1.0 + bonus).min(config.max_multiplier.max(1.0))
+    }
+}
+
+fn reset_combo(
+    mut combo: ResMut<ComboState>,
+    gameplay_handle: Res<GameplayHandle>,
+    gameplay: Res<Assets<GameplayConfig>>,
+) {
+    combo.config = gameplay_handle.get(&gameplay).combo;
+    combo.break_combo();
+}
+
+fn update_combo_text(combo: Res<ComboState>, mut q_text: Query<&mut TextSpan, With<ComboText>>) {
+    let new_text = if combo.multiplier > 1.0 {
+        format!(" x{:.2}", combo.multiplier)
+    } else {
+        String::new()
+    };
+
+    for mut span in &mut q_text {
+        if span.0 != new_text {
+            span.0 = new_text.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled_combo() -> ComboState {
+        ComboState {
+            config: ComboConfig {
+                enabled: true,
+                ..default()
+            },
+            ..default()
+        }
+    }
+
+    #[test]
+    fn consecutive_gold_grabs_raise_multiplier_up_to_cap() {
+        let mut combo = enabled_combo();
+
+        let multipliers: Vec<f32> = (0..6)
+            .map(|_| combo.register_grab(|tag| tag == "gold"))
+            .collect();
+
+        assert_eq!(multipliers, vec![1.0, 1.25, 1.5, 1.75, 2.0, 2.0]);
+        assert_eq!(combo.count, 6);
+    }
+
+    #[test]
+    fn rocks_break_the_combo_and_neutral_items_keep_it() {
+        let mut combo = enabled_combo();
+
+        combo.register_grab(|tag| tag == "gold");
+        combo.register_grab(|tag| tag == "bag");
+        assert_eq!(combo.register_grab(|tag| tag == "gem"), 1.25);
+
+        assert_eq!(combo.register_grab(|tag| tag == "rock"), 1.0);
+        assert_eq!(combo.count, 0);
+    }
+
+    #[test]
+    fn disabled_combo_never_multiplies() {
+        let mut combo = ComboState::default();
+
+        combo.register_grab(|tag| tag == "gold");
+        assert_eq!(combo.register_grab(|tag| tag == "gold"), 1.0);
+    }
+}
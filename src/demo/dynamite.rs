@@ -0,0 +1,153 @@
+//! 抛掷炸药模块
+//!
+//! 钩子空闲摆动时，玩家可以沿当前钩子角度扔出炸药：
+//! - 炸药碰到第一个实体时引爆，超过最大距离或飞出矿区则在空中引爆
+//! - 爆炸复用普通爆炸特效，并按配置半径造成范围伤害
+//! - 范围内的 TNT 会通过 `ExplosiveState` 连锁引爆
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+use crate::AppSystems;
+use crate::audio::{AudioAssets, sound_effect};
+use crate::config::{
+    EntityDescriptor, ImageAssets, LevelEntity, MINE_BOTTOM, ThrownDynamiteConfig,
+};
+use crate::demo::entity::Burrowed;
+use crate::demo::explosive::{ExplosionFX, spawn_standard_explosion_fx};
+use crate::screens::Screen;
+use crate::utils::{VIRTUAL_WIDTH, bevy_to_love_coords};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        thrown_dynamite_system
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// 飞行中的炸药
+#[derive(Component)]
+pub struct ThrownDynamite {
+    /// 起点
+    pub origin: Vec2,
+    /// 飞行方向 (单位向量)
+    pub direction: Vec2,
+    /// 飞行参数
+    pub config: ThrownDynamiteConfig,
+}
+
+impl ThrownDynamite {
+    /// 是否已超过最大飞行距离，或飞出矿区 (斜向扔出时会先到达屏幕边缘)
+    pub fn out_of_range(&self, pos: Vec2) -> bool {
+        let love_pos = bevy_to_love_coords(pos);
+        self.origin.distance(pos) >= self.config.range
+            || !(0.0..=VIRTUAL_WIDTH).contains(&love_pos.x)
+            || love_pos.y >= MINE_BOTTOM
+    }
+}
+
+/// 沿钩子角度扔出一根炸药
+pub fn spawn_thrown_dynamite(
+    commands: &mut Commands,
+    image_assets: &ImageAssets,
+    origin: Vec2,
+    angle: f32,
+    config: ThrownDynamiteConfig,
+) {
+    let angle_rad = angle.to_radians();
+    let direction = Vec2::new(angle_rad.sin(), -angle_rad.cos());
+
+    let mut sprite = Sprite::default();
+    if let Some(image) = image_assets.get_image("DynamiteUI") {
+        sprite.image = image;
+    }
+
+    commands.spawn((
+        Name::new("ThrownDynamite"),
+        ThrownDynamite {
+            origin,
+            direction,
+            config,
+        },
+        sprite,
+        Transform::from_translation(origin.extend(9.0))
+            .with_rotation(Quat::from_rotation_z(angle_rad)),
+        Anchor::CENTER,
+        DespawnOnExit(Screen::Gameplay),
+    ));
+}
+
+/// 移动飞行中的炸药，碰到实体或超出距离时引爆
+fn thrown_dynamite_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    audio_assets: Res<AudioAssets>,
+    image_assets: Res<ImageAssets>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut q_dynamite: Query<(Entity, &ThrownDynamite, &mut Transform)>,
+    q_entities: Query<
+        (&GlobalTransform, &EntityDescriptor),
+        (With<LevelEntity>, Without<Burrowed>),
+    >,
+) {
+    for (entity, dynamite, mut transform) in q_dynamite.iter_mut() {
+        let step = dynamite.direction * dynamite.config.speed * time.delta_secs();
+        transform.translation += step.extend(0.0);
+        let pos = transform.translation.truncate();
+
+        let hit = q_entities.iter().any(|(entity_transform, descriptor)| {
+            entity_transform.translation().truncate().distance(pos)
+                < descriptor.radius() + dynamite.config.collision_radius
+        });
+
+        if !hit && !dynamite.out_of_range(pos) {
+            continue;
+        }
+
+        commands.entity(entity).despawn();
+
+        if let Some(audio) = audio_assets.get_audio("Explosive") {
+            commands.spawn(sound_effect(audio));
+        }
+
+        // 复用普通爆炸特效，附加范围伤害
+        if let Some(fx) = spawn_standard_explosion_fx(
+            &mut commands,
+            &image_assets,
+            texture_atlas_layouts.as_mut(),
+            pos,
+        ) {
+            commands
+                .entity(fx)
+                .insert(ExplosionFX::new(pos).with_radius(dynamite.config.blast_radius));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::love_to_bevy_coords;
+
+    #[test]
+    fn detonates_at_range_or_when_leaving_the_mine() {
+        let dynamite = ThrownDynamite {
+            origin: love_to_bevy_coords(160.0, 40.0),
+            direction: Vec2::NEG_Y,
+            config: ThrownDynamiteConfig {
+                speed: 200.0,
+                range: 230.0,
+                blast_radius: 30.0,
+                collision_radius: 3.0,
+            },
+        };
+
+        assert!(!dynamite.out_of_range(love_to_bevy_coords(160.0, 200.0)));
+        assert!(dynamite.out_of_range(love_to_bevy_coords(160.0, 240.0)));
+        // 斜向扔出：距离未超过射程，但已离开屏幕左右边缘
+        assert!(dynamite.out_of_range(love_to_bevy_coords(-1.0, 120.0)));
+        assert!(dynamite.out_of_range(love_to_bevy_coords(321.0, 120.0)));
+    }
+}
@@ -1,7 +1,10 @@
-use crate::config::{EntityDescriptor, EntityType, LevelEntity};
+use crate::config::{Direction, EntityDescriptor, EntityType, LevelEntity, PathMode, Position};
 use crate::demo::hook::Hook;
 use crate::screens::Screen;
+use crate::screens::stats::LevelStats;
+use crate::utils::{bevy_to_love_coords, love_to_bevy_coords};
 use bevy::prelude::*;
+use std::time::Duration;
 
 /// 注册实体巡逻系统插件
 pub(super) fn plugin(app: &mut App) {
@@ -10,12 +13,201 @@ pub(super) fn plugin(app: &mut App) {
         (
             init_patrol_system,
             patrol_movement_system,
+            init_burrow_system,
+            burrow_system,
             entity_animation_system,
+            sync_carried_sprite_system,
         )
             .run_if(in_state(Screen::Gameplay)),
     );
 }
 
+/// 携带物品相对移动实体中心的偏移 (图片默认朝左，物品在身前)
+const CARRIED_OFFSET: Vec2 = Vec2::new(-7.0, -2.0);
+
+/// 携带物品组件：移动实体携带的另一个实体 (例如抱着钻石的地鼠)
+#[derive(Component, Clone)]
+pub struct Carrying {
+    /// 被携带实体的 ID
+    pub entity_id: String,
+    /// 被携带实体的描述符，结算时单独计算
+    pub descriptor: EntityDescriptor,
+}
+
+impl Carrying {
+    /// 携带者被摧毁时，在原地掉落被携带的实体
+    pub fn drop_bundle(&self, pos: Vec2) -> impl Bundle {
+        spawned_entity_bundle(&self.entity_id, self.descriptor.clone(), pos)
+    }
+}
+
+/// 关卡运行中动态生成的静止实体 (掉落物、碎片等)，精灵由 spawn_entity_sprite 补充
+pub fn spawned_entity_bundle(
+    entity_id: &str,
+    descriptor: EntityDescriptor,
+    pos: Vec2,
+) -> impl Bundle {
+    let love_pos = bevy_to_love_coords(pos);
+    (
+        Name::new(entity_id.to_string()),
+        Transform::from_translation(pos.extend(1.0)),
+        descriptor,
+        LevelEntity {
+            entity_id: entity_id.to_string(),
+            pos: Position {
+                x: love_pos.x,
+                y: love_pos.y,
+            },
+            dir: None,
+            carries: None,
+            path: None,
+        },
+        DespawnOnExit(Screen::Gameplay),
+    )
+}
+
+/// 携带物品的精灵，作为移动实体的子实体渲染
+#[derive(Component)]
+pub struct CarriedSprite;
+
+impl CarriedSprite {
+    /// 根据携带者朝向计算精灵偏移
+    pub fn offset(flip_x: bool) -> Vec2 {
+        if flip_x {
+            Vec2::new(-CARRIED_OFFSET.x, CARRIED_OFFSET.y)
+        } else {
+            CARRIED_OFFSET
+        }
+    }
+}
+
+/// 携带物品跟随携带者的朝向
+fn sync_carried_sprite_system(
+    q_carriers: Query<&Sprite, (With<Carrying>, Without<CarriedSprite>)>,
+    mut q_carried: Query<(&ChildOf, &mut Transform, &mut Sprite), With<CarriedSprite>>,
+) {
+    for (child_of, mut transform, mut sprite) in q_carried.iter_mut() {
+        let Ok(carrier_sprite) = q_carriers.get(child_of.parent()) else {
+            continue;
+        };
+
+        let z = transform.translation.z;
+        transform.translation = CarriedSprite::offset(carrier_sprite.flip_x).extend(z);
+        sprite.flip_x = carrier_sprite.flip_x;
+    }
+}
+
+/// 默认在地下停留的秒数
+const DEFAULT_BURROW_DURATION: f32 = 2.0;
+/// 土堆颜色与尺寸
+const MOUND_COLOR: Color = Color::srgb_u8(121, 85, 72);
+const MOUND_SIZE: Vec2 = Vec2::new(14.0, 4.0);
+/// 土堆相对实体中心的偏移 (贴近地鼠脚下)
+const MOUND_OFFSET: Vec2 = Vec2::new(0.0, -4.5);
+
+/// 钻地状态组件：周期性钻入地下，期间无法被抓取或炸毁
+#[derive(Component)]
+pub struct BurrowState {
+    /// 当前阶段 (地面或地下) 的计时器
+    pub timer: Timer,
+    /// 在地面停留的秒数
+    pub interval: f32,
+    /// 在地下停留的秒数
+    pub duration: f32,
+    /// 钻地时显示的土堆实体
+    pub mound: Option<Entity>,
+}
+
+impl BurrowState {
+    fn new(interval: f32, duration: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(interval, TimerMode::Once),
+            interval,
+            duration,
+            mound: None,
+        }
+    }
+
+    /// 推进当前阶段的计时，阶段结束时切换并返回是否进入地下；被抓住时计时冻结
+    fn advance(&mut self, delta: Duration, is_burrowed: bool, is_grabbed: bool) -> Option<bool> {
+        if is_grabbed {
+            return None;
+        }
+
+        self.timer.tick(delta);
+        if !self.timer.is_finished() {
+            return None;
+        }
+
+        let burrow = !is_burrowed;
+        let seconds = if burrow { self.duration } else { self.interval };
+        self.timer = Timer::from_seconds(seconds, TimerMode::Once);
+        Some(burrow)
+    }
+}
+
+/// 标记实体正处于地下：钩子和爆炸都会忽略该实体
+#[derive(Component)]
+pub struct Burrowed;
+
+/// 为配置了钻地间隔的实体添加钻地状态
+fn init_burrow_system(
+    mut commands: Commands,
+    q_added_entities: Query<(Entity, &EntityDescriptor), Added<LevelEntity>>,
+) {
+    for (entity, descriptor) in q_added_entities.iter() {
+        if let Some(interval) = descriptor.burrow_interval {
+            let duration = descriptor
+                .burrow_duration
+                .unwrap_or(DEFAULT_BURROW_DURATION);
+            commands
+                .entity(entity)
+                .insert(BurrowState::new(interval, duration));
+        }
+    }
+}
+
+/// 切换钻地状态：地下时隐藏实体并显示土堆，巡逻照常进行，因此会在别处钻出
+fn burrow_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_burrowers: Query<(Entity, &mut BurrowState, &mut Visibility, Has<Burrowed>)>,
+    q_hooks: Query<&Hook>,
+) {
+    for (entity, mut state, mut visibility, is_burrowed) in q_burrowers.iter_mut() {
+        // 被钩子抓住时不再钻地
+        let is_grabbed = q_hooks
+            .iter()
+            .any(|hook| hook.grabed_entity == Some(entity));
+        let Some(burrow) = state.advance(time.delta(), is_burrowed, is_grabbed) else {
+            continue;
+        };
+
+        if !burrow {
+            // 钻出地面
+            commands.entity(entity).remove::<Burrowed>();
+            *visibility = Visibility::Inherited;
+            if let Some(mound) = state.mound.take() {
+                commands.entity(mound).despawn();
+            }
+        } else {
+            // 钻入地下：土堆作为子实体跟随移动，且不受父实体隐藏影响
+            commands.entity(entity).insert(Burrowed);
+            *visibility = Visibility::Hidden;
+            let mound = commands
+                .spawn((
+                    Name::new("Burrow Mound"),
+                    Sprite::from_color(MOUND_COLOR, MOUND_SIZE),
+                    Transform::from_translation(MOUND_OFFSET.extend(-0.1)),
+                    Visibility::Visible,
+                    ChildOf(entity),
+                ))
+                .id();
+            state.mound = Some(mound);
+        }
+    }
+}
+
 /// 实体动画状态枚举
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum EntityAnimationState {
@@ -81,19 +273,165 @@ fn entity_animation_system(
     }
 }
 
+/// 未配置路径时的默认巡逻范围
+const DEFAULT_MOVE_RANGE: f32 = 135.0;
+/// 未配置路径时在两端的停留秒数
+const DEFAULT_PAUSE: f32 = 1.0;
+
+/// 巡逻路线中的一个点 (Bevy 坐标)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutePoint {
+    pub pos: Vec2,
+    /// 通往该点的路段速度 (每帧像素数)，None 表示使用实体速度
+    pub speed: Option<f32>,
+    /// 到达该点后的停留秒数
+    pub pause: f32,
+}
+
+/// 巡逻路线：起点为实体生成坐标
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatrolRoute {
+    pub points: Vec<RoutePoint>,
+    pub mode: PathMode,
+}
+
+impl PatrolRoute {
+    /// 根据关卡配置构建路线，未配置路径时沿 dir 左右巡逻
+    pub fn from_level_entity(level_entity: &LevelEntity, descriptor: &EntityDescriptor) -> Self {
+        let start = love_to_bevy_coords(level_entity.pos.x, level_entity.pos.y);
+
+        let Some(path) = &level_entity.path else {
+            let direction = match level_entity.dir {
+                Some(Direction::Left) => -1.0,
+                _ => 1.0,
+            };
+            let move_range = descriptor.move_range.unwrap_or(DEFAULT_MOVE_RANGE);
+            return Self {
+                points: vec![
+                    RoutePoint {
+                        pos: start,
+                        speed: None,
+                        pause: DEFAULT_PAUSE,
+                    },
+                    RoutePoint {
+                        pos: start + Vec2::X * direction * move_range,
+                        speed: None,
+                        pause: DEFAULT_PAUSE,
+                    },
+                ],
+                mode: PathMode::PingPong,
+            };
+        };
+
+        let mut points = vec![RoutePoint {
+            pos: start,
+            speed: None,
+            pause: 0.0,
+        }];
+        points.extend(path.points.iter().map(|p| RoutePoint {
+            pos: love_to_bevy_coords(p.x, p.y),
+            speed: p.speed,
+            pause: p.pause.unwrap_or(0.0),
+        }));
+
+        Self {
+            points,
+            mode: path.mode,
+        }
+    }
+
+    /// 路线是否可以移动 (至少需要两个点)
+    pub fn is_movable(&self) -> bool {
+        self.points.len() >= 2
+    }
+
+    /// 到达 current 后的下一个目标点与折返方向
+    pub fn next_target(&self, current: usize, forward: bool) -> (usize, bool) {
+        let last = self.points.len() - 1;
+        match self.mode {
+            PathMode::Loop => ((current + 1) % self.points.len(), true),
+            PathMode::PingPong => {
+                if forward && current == last {
+                    (current - 1, false)
+                } else if !forward && current == 0 {
+                    (1, true)
+                } else if forward {
+                    (current + 1, true)
+                } else {
+                    (current - 1, false)
+                }
+            }
+        }
+    }
+
+    /// 通往 target 路段的速度配置
+    /// 路段速度由正向终点的路点决定，折返时沿用同一路段的速度
+    pub fn segment_speed(&self, target: usize, forward: bool) -> Option<f32> {
+        let index = if forward { target } else { target + 1 };
+        self.points[index].speed
+    }
+}
+
+/// 向目标点移动指定距离，返回新位置与是否到达
+pub fn step_towards(pos: Vec2, target: Vec2, distance: f32) -> (Vec2, bool) {
+    let remaining = target - pos;
+    if remaining.length() <= distance {
+        (target, true)
+    } else {
+        (pos + remaining.normalize() * distance, false)
+    }
+}
+
 /// 巡逻状态组件，用于管理可移动实体的巡逻行为
 #[derive(Component)]
 pub struct PatrolState {
     /// 是否正在移动
     pub is_moving: bool,
-    /// 移动方向，1.0 表示向右，-1.0 表示向左
-    pub direction: f32,
-    /// 目标位置的 x 坐标
-    pub destination_x: f32,
+    /// 巡逻路线
+    pub route: PatrolRoute,
+    /// 当前目标点索引
+    pub target: usize,
+    /// 是否沿路线正向移动 (仅 PingPong 模式会折返)
+    pub forward: bool,
     /// 闲置计时器
     pub idle_timer: Timer,
-    /// 移动范围
-    pub move_range: f32,
+}
+
+impl PatrolState {
+    pub fn new(route: PatrolRoute) -> Self {
+        Self {
+            is_moving: route.is_movable(),
+            target: if route.is_movable() { 1 } else { 0 },
+            route,
+            forward: true,
+            idle_timer: Timer::from_seconds(0.0, TimerMode::Once),
+        }
+    }
+
+    /// 当前目标点坐标
+    pub fn target_pos(&self) -> Vec2 {
+        self.route.points[self.target].pos
+    }
+
+    /// 当前路段的移动速度 (每帧像素数)
+    pub fn segment_speed(&self, default_speed: f32) -> f32 {
+        self.route
+            .segment_speed(self.target, self.forward)
+            .unwrap_or(default_speed)
+    }
+
+    /// 到达当前目标点：按路点配置停留，并切换到下一个目标点
+    pub fn arrive(&mut self) {
+        let pause = self.route.points[self.target].pause;
+        let (next, forward) = self.route.next_target(self.target, self.forward);
+        self.target = next;
+        self.forward = forward;
+
+        if pause > 0.0 {
+            self.is_moving = false;
+            self.idle_timer = Timer::from_seconds(pause, TimerMode::Once);
+        }
+    }
 }
 
 /// 初始化巡逻系统，为可移动实体添加巡逻状态组件
@@ -107,38 +445,48 @@ fn init_patrol_system(
     for (entity, level_entity, descriptor, mut sprite) in q_added_entities.iter_mut() {
         // 只为需要移动的实体添加巡逻状态
         if descriptor.entity_type == EntityType::MoveAround {
-            // 获取移动范围，默认 135 像素
-            let move_range = descriptor.move_range.unwrap_or(135.0);
-
-            // 确定初始移动方向，从配置读取或默认向右 (1.0)
-            let direction = match level_entity.dir {
-                Some(crate::config::Direction::Left) => -1.0,
-                _ => 1.0,
-            };
+            let state = PatrolState::new(PatrolRoute::from_level_entity(level_entity, descriptor));
 
             // 设置初始朝向（图片默认朝左）
             if let Some(ref mut s) = sprite {
-                s.flip_x = direction > 0.0;
+                let dx = state.target_pos().x - state.route.points[0].pos.x;
+                s.flip_x = match level_entity.dir {
+                    Some(Direction::Left) => false,
+                    Some(Direction::Right) => true,
+                    None => dx > 0.0,
+                };
             }
 
-            // 将 LÖVE 坐标转换为 Bevy 坐标，获取当前 x 位置
-            let current_x =
-                crate::utils::love_to_bevy_coords(level_entity.pos.x, level_entity.pos.y).x;
-            // 计算目标位置
-            let destination_x = current_x + (direction * move_range);
-
             // 为实体添加巡逻状态组件
-            commands.entity(entity).insert(PatrolState {
-                is_moving: true,
-                direction,
-                destination_x,
-                idle_timer: Timer::from_seconds(1.0, TimerMode::Once),
-                move_range,
-            });
+            commands.entity(entity).insert(state);
         }
     }
 }
 
+/// 切换实体动画状态，并立即更新图集索引
+fn set_animation_state(
+    anim: &mut EntityAnimation,
+    sprite: &mut Sprite,
+    state: EntityAnimationState,
+) {
+    if anim.state == state {
+        return;
+    }
+
+    anim.state = state;
+    anim.current_frame = 0;
+
+    let frames = match state {
+        EntityAnimationState::Idle => &anim.idle_frames,
+        EntityAnimationState::Move => &anim.move_frames,
+    };
+    if let Some(&first) = frames.first()
+        && let Some(atlas) = &mut sprite.texture_atlas
+    {
+        atlas.index = first;
+    }
+}
+
 /// 处理实体的巡逻移动逻辑
 fn patrol_movement_system(
     time: Res<Time>,
@@ -151,103 +499,145 @@ fn patrol_movement_system(
         Option<&mut EntityAnimation>,
     )>,
     q_hooks: Query<&Hook>,
+    stats: Res<LevelStats>,
 ) {
     for (entity, mut transform, mut state, descriptor, mut sprite, mut animation) in
         q_patrol.iter_mut()
     {
         // 如果实体被钩子抓取，跳过巡逻更新
-        let mut is_grabbed = false;
-        for hook in q_hooks.iter() {
-            if hook.grabed_entity == Some(entity) {
-                is_grabbed = true;
-                break;
-            }
-        }
+        let is_grabbed = q_hooks
+            .iter()
+            .any(|hook| hook.grabed_entity == Some(entity));
         if is_grabbed {
             state.is_moving = false;
             state.idle_timer.reset();
 
-            if let Some(anim) = &mut animation
-                && anim.state != EntityAnimationState::Move
-            {
-                anim.state = EntityAnimationState::Move;
-                anim.current_frame = 0;
-
-                if !anim.move_frames.is_empty()
-                    && let Some(atlas) = &mut sprite.texture_atlas
-                {
-                    atlas.index = anim.move_frames[0];
-                }
+            if let Some(anim) = &mut animation {
+                set_animation_state(anim, &mut sprite, EntityAnimationState::Move);
             }
-
             continue;
         }
 
         if state.is_moving {
             // 确保动画状态为移动
-            if let Some(anim) = &mut animation
-                && anim.state != EntityAnimationState::Move
-            {
-                anim.state = EntityAnimationState::Move;
-                anim.current_frame = 0;
-                // 立即更新图集索引
-                if !anim.move_frames.is_empty()
-                    && let Some(atlas) = &mut sprite.texture_atlas
-                {
-                    atlas.index = anim.move_frames[0];
-                }
+            if let Some(anim) = &mut animation {
+                set_animation_state(anim, &mut sprite, EntityAnimationState::Move);
             }
 
             // 计算移动速度：配置中的速度通常是每帧像素数，需要乘以 60 转换为每秒像素数
-            let speed = descriptor.speed.unwrap_or(1.0) * 60.0;
-
-            // 计算移动增量并更新位置
-            let delta = speed * state.direction * time.delta_secs();
-            transform.translation.x += delta;
-
-            // 检查是否到达目标位置
-            // 向右移动时检查是否超过目标 x，向左移动时检查是否小于目标 x
-            let reached = if state.direction > 0.0 {
-                transform.translation.x >= state.destination_x
-            } else {
-                transform.translation.x <= state.destination_x
-            };
+            // 无尽模式的“更快地鼠”修饰会提高速度
+            let speed = state.segment_speed(descriptor.speed.unwrap_or(1.0))
+                * 60.0
+                * stats.modifiers.mole_speed_scale();
 
-            if reached {
-                // 到达目标位置，固定到精确位置
-                transform.translation.x = state.destination_x;
-                state.is_moving = false;
-                state.idle_timer.reset();
-                // 反转方向
-                state.direction *= -1.0;
-                // 计算下一个目标位置
-                state.destination_x =
-                    transform.translation.x + (state.direction * state.move_range);
+            let pos = transform.translation.truncate();
+            let target = state.target_pos();
+            let (new_pos, reached) = step_towards(pos, target, speed * time.delta_secs());
+            transform.translation.x = new_pos.x;
+            transform.translation.y = new_pos.y;
+
+            // 更新精灵朝向（图片默认朝左），纯垂直移动时保持原朝向
+            let dx = target.x - pos.x;
+            if dx.abs() > f32::EPSILON {
+                sprite.flip_x = dx > 0.0;
             }
 
-            // 更新精灵朝向（图片默认朝左）
-            sprite.flip_x = state.direction > 0.0;
+            if reached {
+                state.arrive();
+            }
         } else {
             // 确保动画状态为待机
-            if let Some(anim) = &mut animation
-                && anim.state != EntityAnimationState::Idle
-            {
-                anim.state = EntityAnimationState::Idle;
-                anim.current_frame = 0;
-                // 立即更新图集索引
-                if !anim.idle_frames.is_empty()
-                    && let Some(atlas) = &mut sprite.texture_atlas
-                {
-                    atlas.index = anim.idle_frames[0];
-                }
+            if let Some(anim) = &mut animation {
+                set_animation_state(anim, &mut sprite, EntityAnimationState::Idle);
             }
 
             // 闲置状态，更新计时器
             state.idle_timer.tick(time.delta());
             // 闲置结束后开始移动
-            if state.idle_timer.is_finished() {
+            if state.idle_timer.is_finished() && state.route.is_movable() {
                 state.is_moving = true;
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(mode: PathMode, count: usize) -> PatrolRoute {
+        PatrolRoute {
+            points: (0..count)
+                .map(|i| RoutePoint {
+                    pos: Vec2::new(i as f32 * 10.0, 0.0),
+                    speed: Some(i as f32),
+                    pause: 0.0,
+                })
+                .collect(),
+            mode,
+        }
+    }
+
+    #[test]
+    fn step_towards_moves_diagonally_and_snaps_to_target() {
+        let (pos, reached) = step_towards(Vec2::ZERO, Vec2::new(30.0, 40.0), 10.0);
+        assert!(!reached);
+        assert!((pos - Vec2::new(6.0, 8.0)).length() < 1e-4);
+
+        let (pos, reached) = step_towards(pos, Vec2::new(30.0, 40.0), 100.0);
+        assert!(reached);
+        assert_eq!(pos, Vec2::new(30.0, 40.0));
+    }
+
+    #[test]
+    fn ping_pong_reverses_at_both_ends() {
+        let route = route(PathMode::PingPong, 3);
+        let mut visited = vec![];
+        let (mut target, mut forward) = (1, true);
+        for _ in 0..5 {
+            (target, forward) = route.next_target(target, forward);
+            visited.push(target);
+        }
+        assert_eq!(visited, vec![2, 1, 0, 1, 2]);
+        // 折返时沿用同一路段的速度
+        assert_eq!(route.segment_speed(1, false), route.segment_speed(2, true));
+    }
+
+    #[test]
+    fn loop_wraps_back_to_start() {
+        let route = route(PathMode::Loop, 3);
+        assert_eq!(route.next_target(2, true), (0, true));
+        assert_eq!(route.segment_speed(0, true), Some(0.0));
+    }
+
+    #[test]
+    fn arrive_pauses_only_on_waypoints_with_pause() {
+        let mut route = route(PathMode::Loop, 3);
+        route.points[2].pause = 0.5;
+        let mut state = PatrolState::new(route);
+
+        state.arrive();
+        assert!(state.is_moving);
+        assert_eq!(state.target, 2);
+
+        state.arrive();
+        assert!(!state.is_moving);
+        assert_eq!(state.target, 0);
+    }
+
+    #[test]
+    fn burrow_phases_alternate_and_freeze_while_grabbed() {
+        let mut state = BurrowState::new(3.0, 1.0);
+        let secs = Duration::from_secs_f32;
+
+        assert_eq!(state.advance(secs(2.0), false, false), None);
+        // 被抓住时计时不推进
+        assert_eq!(state.advance(secs(5.0), false, true), None);
+        assert_eq!(state.advance(secs(1.0), false, false), Some(true));
+        // 地下停留 duration 秒后钻出，再等待 interval 秒
+        assert_eq!(state.advance(secs(0.5), true, false), None);
+        assert_eq!(state.advance(secs(0.5), true, false), Some(false));
+        assert_eq!(state.advance(secs(2.9), false, false), None);
+        assert_eq!(state.advance(secs(0.1), false, false), Some(true));
+    }
+}
@@ -3,18 +3,23 @@
 //! 实现地图 TNT 实体的爆炸效果：
 //! - 钩子碰撞 TNT 时触发爆炸
 //! - 爆炸产生范围伤害，销毁周围实体
-//! - TNT 可引爆其他 TNT (连锁反应)
+//! - TNT 可引爆其他 TNT (连锁反应)，按各自的引信延迟依次爆炸
+//! - 爆炸半径、引信延迟与免疫标签由 entities.yaml 配置
+//! - 被炸毁的实体可按配置散落低价值碎片
 
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 
 use crate::AppSystems;
 use crate::audio::{AudioAssets, sound_effect};
-use crate::config::{EntityDescriptor, ImageAssets};
+use crate::config::{EntitiesConfig, EntityDescriptor, ImageAssets};
+use crate::demo::entity::{Burrowed, Carrying, spawned_entity_bundle};
 use crate::demo::fx::{FXAnimation, FXPlacement, FXPlayback};
+use crate::demo::level::EntityHandle;
 use crate::screens::Screen;
+use crate::utils::{bevy_to_love_coords, love_to_bevy_coords};
 
-/// 爆炸半径 (与 Lua 版 biggerExplosiveFX 对齐)
+/// 默认爆炸半径 (与 Lua 版 biggerExplosiveFX 对齐)
 const EXPLOSION_RADIUS: f32 = 35.0 / 2.0;
 
 const BIGGER_EXPLOSION_FRAME_DURATION: f32 = 0.06;
@@ -29,13 +34,28 @@ const STANDARD_EXPLOSION_FRAME_SIZE: UVec2 = UVec2::new(16, 16);
 
 const BIGGER_EXPLOSION_FRAME_SIZE: UVec2 = UVec2::new(35, 35);
 
+/// 碎片散落距离
+const DEBRIS_SCATTER_DISTANCE: f32 = 12.0;
+
+/// 碎片落定动画时长 (秒)
+const DEBRIS_SETTLE_DURATION: f32 = 0.35;
+
+/// 碎片落定时弹起的高度
+const DEBRIS_HOP_HEIGHT: f32 = 6.0;
+
+/// 碎片可散落的区域 (LÖVE 坐标，地面以下)
+const DEBRIS_BOUNDS_MIN: Vec2 = Vec2::new(8.0, 50.0);
+const DEBRIS_BOUNDS_MAX: Vec2 = Vec2::new(312.0, 232.0);
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
+            explosion_fuse_system,
             explosion_trigger_system,
             explosion_damage_system,
             explosion_cleanup_system,
+            debris_settle_system,
         )
             .chain()
             .in_set(AppSystems::Update)
@@ -50,20 +70,49 @@ pub struct ExplosiveState {
     pub is_exploding: bool,
     /// 是否已触发过范围伤害 (防止重复触发)
     pub damage_dealt: bool,
+    /// 被其他爆炸波及后的引信计时器；计时结束后才真正爆炸
+    pub fuse_timer: Option<Timer>,
     /// 爆炸后的销毁计时器；被钩子抓住时延迟真正清理。
     pub cleanup_timer: Option<Timer>,
 }
 
 /// 爆炸特效组件
-#[derive(Component)]
+#[derive(Component, Clone)]
 pub struct ExplosionFX {
     /// 爆炸中心位置 (用于范围伤害检测)
     pub center: Vec2,
+    /// 爆炸范围半径
+    pub radius: f32,
+    /// 带有这些标签的实体不受伤害
+    pub immune_tags: Vec<String>,
+    /// 是否已结算范围伤害，每次爆炸只结算一次
+    pub damage_dealt: bool,
 }
 
 impl ExplosionFX {
     pub fn new(center: Vec2) -> Self {
-        Self { center }
+        Self {
+            center,
+            radius: EXPLOSION_RADIUS,
+            immune_tags: Vec::new(),
+            damage_dealt: false,
+        }
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    pub fn with_immune_tags(mut self, immune_tags: Vec<String>) -> Self {
+        self.immune_tags = immune_tags;
+        self
+    }
+
+    /// 位于 pos 的实体是否会被这次爆炸摧毁
+    pub fn destroys(&self, pos: Vec2, descriptor: &EntityDescriptor) -> bool {
+        self.center.distance(pos) < self.radius + descriptor.radius()
+            && !self.immune_tags.iter().any(|tag| descriptor.has_tag(tag))
     }
 
     pub fn bigger_animation(center: Vec2) -> FXAnimation {
@@ -87,30 +136,48 @@ pub fn standard_explosion_animation(center: Vec2) -> FXAnimation {
     .with_z_layer(10.0)
 }
 
+/// 生成普通爆炸特效，返回特效实体 (可附加 [`ExplosionFX`] 造成范围伤害)
 pub fn spawn_standard_explosion_fx(
     commands: &mut Commands,
     image_assets: &ImageAssets,
     texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
     center: Vec2,
-) {
-    if let Some(fx_image) = image_assets.get_image("ExplosiveFX") {
+) -> Option<Entity> {
+    image_assets.get_image("ExplosiveFX").map(|fx_image| {
         let layout = TextureAtlasLayout::from_grid(STANDARD_EXPLOSION_FRAME_SIZE, 4, 3, None, None);
         let atlas_layout = texture_atlas_layouts.add(layout);
 
-        commands.spawn((
-            Name::new("StandardExplosionFX"),
-            standard_explosion_animation(center),
-            Sprite::from_atlas_image(
-                fx_image,
-                TextureAtlas {
-                    layout: atlas_layout,
-                    index: 0,
-                },
-            ),
-            Transform::from_translation(center.extend(10.0)),
-            Anchor::CENTER,
-            DespawnOnExit(Screen::Gameplay),
-        ));
+        commands
+            .spawn((
+                Name::new("StandardExplosionFX"),
+                standard_explosion_animation(center),
+                Sprite::from_atlas_image(
+                    fx_image,
+                    TextureAtlas {
+                        layout: atlas_layout,
+                        index: 0,
+                    },
+                ),
+                Transform::from_translation(center.extend(10.0)),
+                Anchor::CENTER,
+                DespawnOnExit(Screen::Gameplay),
+            ))
+            .id()
+    })
+}
+
+/// 引信系统：连锁波及的 TNT 在延迟结束后才爆炸，使大片 TNT 依次引爆
+fn explosion_fuse_system(time: Res<Time>, mut q_explosives: Query<&mut ExplosiveState>) {
+    for mut state in q_explosives.iter_mut() {
+        let Some(timer) = &mut state.fuse_timer else {
+            continue;
+        };
+
+        timer.tick(time.delta());
+        if timer.is_finished() {
+            state.fuse_timer = None;
+            state.is_exploding = true;
+        }
     }
 }
 
@@ -149,7 +216,9 @@ fn explosion_trigger_system(
 
                 commands.spawn((
                     Name::new("ExplosionFX"),
-                    ExplosionFX::new(center),
+                    ExplosionFX::new(center)
+                        .with_radius(descriptor.blast_radius.unwrap_or(EXPLOSION_RADIUS))
+                        .with_immune_tags(descriptor.immune_tags.clone()),
                     ExplosionFX::bigger_animation(center),
                     Sprite::from_atlas_image(
                         fx_image,
@@ -183,57 +252,70 @@ fn explosion_trigger_system(
 /// 爆炸范围伤害系统：对范围内实体造成伤害
 fn explosion_damage_system(
     mut commands: Commands,
-    q_fx: Query<&ExplosionFX>,
-    mut q_explosives: Query<(Entity, &mut ExplosiveState, &GlobalTransform)>,
+    mut q_fx: Query<&mut ExplosionFX>,
+    mut q_explosives: Query<(&mut ExplosiveState, &GlobalTransform, &EntityDescriptor)>,
+    entity_handle: Res<EntityHandle>,
+    entities: Res<Assets<EntitiesConfig>>,
     q_entities: Query<
-        (Entity, &GlobalTransform, &EntityDescriptor),
-        (With<crate::config::LevelEntity>, Without<ExplosiveState>),
+        (
+            Entity,
+            &GlobalTransform,
+            &EntityDescriptor,
+            Option<&Carrying>,
+        ),
+        (
+            With<crate::config::LevelEntity>,
+            Without<ExplosiveState>,
+            Without<Burrowed>,
+        ),
     >,
 ) {
-    // 收集所有活跃的爆炸中心
-    let explosion_centers: Vec<Vec2> = q_fx.iter().map(|fx| fx.center).collect();
+    // 收集本帧新产生的爆炸
+    let mut explosions: Vec<ExplosionFX> = Vec::new();
+    for mut fx in q_fx.iter_mut() {
+        if !fx.damage_dealt {
+            fx.damage_dealt = true;
+            explosions.push(fx.clone());
+        }
+    }
 
-    if explosion_centers.is_empty() {
+    if explosions.is_empty() {
         return;
     }
 
     // 检测范围内的普通实体
-    for (entity, transform, descriptor) in q_entities.iter() {
+    for (entity, transform, descriptor, carrying) in q_entities.iter() {
         let entity_pos = transform.translation().truncate();
-        let entity_radius = descriptor.collision_radius.unwrap_or(6.0);
 
-        for center in &explosion_centers {
-            if center.distance(entity_pos) < (EXPLOSION_RADIUS + entity_radius) {
-                // 销毁普通实体
-                commands.entity(entity).despawn();
-                break;
+        if explosions
+            .iter()
+            .any(|fx| fx.destroys(entity_pos, descriptor))
+        {
+            // 销毁普通实体，携带的物品掉落在原地
+            commands.entity(entity).despawn();
+            if let Some(carrying) = carrying {
+                commands.spawn(carrying.drop_bundle(entity_pos));
+            }
+
+            if let Some(entities_config) = entity_handle.get(&entities) {
+                spawn_debris(&mut commands, entities_config, descriptor, entity_pos);
             }
         }
     }
 
-    // 检测范围内的其他 TNT (连锁反应)
-    let explosive_positions: Vec<(Entity, Vec2)> = q_explosives
-        .iter()
-        .map(|(e, _, t)| (e, t.translation().truncate()))
-        .collect();
-
-    for (entity, mut state, _) in q_explosives.iter_mut() {
-        if state.is_exploding {
-            continue; // 已在爆炸中的 TNT 跳过
+    // 检测范围内的其他 TNT (连锁反应)，点燃引信而不是立即爆炸
+    for (mut state, transform, descriptor) in q_explosives.iter_mut() {
+        if state.is_exploding || state.fuse_timer.is_some() {
+            continue; // 已在爆炸或引信已点燃的 TNT 跳过
         }
 
-        let entity_pos = explosive_positions
+        let entity_pos = transform.translation().truncate();
+        if explosions
             .iter()
-            .find(|(e, _)| *e == entity)
-            .map(|(_, pos)| *pos)
-            .unwrap_or(Vec2::ZERO);
-
-        for center in &explosion_centers {
-            if center.distance(entity_pos) < (EXPLOSION_RADIUS + 6.0) {
-                // 触发连锁爆炸
-                state.is_exploding = true;
-                break;
-            }
+            .any(|fx| fx.destroys(entity_pos, descriptor))
+        {
+            let delay = descriptor.chain_delay.unwrap_or(0.0);
+            state.fuse_timer = Some(Timer::from_seconds(delay, TimerMode::Once));
         }
     }
 }
@@ -262,3 +344,132 @@ fn explosion_cleanup_system(
         }
     }
 }
+
+/// 碎片落定动画：从爆炸位置弹跳到散落位置
+#[derive(Component)]
+pub struct DebrisSettling {
+    pub from: Vec2,
+    pub to: Vec2,
+    pub timer: Timer,
+}
+
+/// 围绕爆炸点均匀分布的碎片偏移，phase 为起始角度
+pub fn debris_offsets(count: usize, distance: f32, phase: f32) -> Vec<Vec2> {
+    (0..count)
+        .map(|i| {
+            let angle = phase + i as f32 * std::f32::consts::TAU / count as f32;
+            Vec2::from_angle(angle) * distance
+        })
+        .collect()
+}
+
+/// 按描述符中的 debris 配置生成碎片实体
+fn spawn_debris(
+    commands: &mut Commands,
+    entities_config: &EntitiesConfig,
+    descriptor: &EntityDescriptor,
+    origin: Vec2,
+) {
+    let mut fragments = Vec::new();
+    for debris in &descriptor.debris {
+        let Some(fragment) = entities_config.entities.get(&debris.entity_id) else {
+            warn!("Unknown debris entity {}", debris.entity_id);
+            continue;
+        };
+
+        let mut fragment = fragment.clone();
+        let ratio = debris.bonus_ratio.unwrap_or(1.0);
+        fragment.bonus = fragment
+            .bonus
+            .map(|bonus| (bonus as f32 * ratio).round() as i32);
+
+        for _ in 0..debris.count.unwrap_or(1) {
+            fragments.push((debris.entity_id.as_str(), fragment.clone()));
+        }
+    }
+
+    let offsets = debris_offsets(
+        fragments.len(),
+        DEBRIS_SCATTER_DISTANCE,
+        rand::random::<f32>() * std::f32::consts::TAU,
+    );
+
+    for ((entity_id, fragment), offset) in fragments.into_iter().zip(offsets) {
+        let love_target =
+            bevy_to_love_coords(origin + offset).clamp(DEBRIS_BOUNDS_MIN, DEBRIS_BOUNDS_MAX);
+        let target = love_to_bevy_coords(love_target.x, love_target.y);
+
+        commands
+            .spawn(spawned_entity_bundle(entity_id, fragment, target))
+            .insert((
+                Transform::from_translation(origin.extend(1.0)),
+                DebrisSettling {
+                    from: origin,
+                    to: target,
+                    timer: Timer::from_seconds(DEBRIS_SETTLE_DURATION, TimerMode::Once),
+                },
+            ));
+    }
+}
+
+/// 播放碎片落定动画
+fn debris_settle_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_debris: Query<(Entity, &mut DebrisSettling, &mut Transform)>,
+) {
+    for (entity, mut settling, mut transform) in q_debris.iter_mut() {
+        settling.timer.tick(time.delta());
+        let t = settling.timer.fraction();
+
+        // 先快后慢地滑向目标，同时弹起一次
+        let eased = 1.0 - (1.0 - t) * (1.0 - t);
+        let hop = (t * std::f32::consts::PI).sin() * DEBRIS_HOP_HEIGHT;
+        let pos = settling.from.lerp(settling.to, eased) + Vec2::Y * hop;
+        transform.translation.x = pos.x;
+        transform.translation.y = pos.y;
+
+        if settling.timer.is_finished() {
+            commands.entity(entity).remove::<DebrisSettling>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptor(tags: &[&str]) -> EntityDescriptor {
+        EntityDescriptor {
+            collision_radius: Some(5.0),
+            ..EntityDescriptor::with_tags(tags)
+        }
+    }
+
+    #[test]
+    fn destroys_entities_within_blast_and_collision_radius() {
+        let fx = ExplosionFX::new(Vec2::ZERO).with_radius(20.0);
+        let rock = descriptor(&["rock"]);
+
+        assert!(fx.destroys(Vec2::new(24.0, 0.0), &rock));
+        assert!(!fx.destroys(Vec2::new(26.0, 0.0), &rock));
+    }
+
+    #[test]
+    fn immune_tags_survive_the_blast() {
+        let fx = ExplosionFX::new(Vec2::ZERO).with_immune_tags(vec!["gem".to_string()]);
+
+        assert!(!fx.destroys(Vec2::ZERO, &descriptor(&["gem"])));
+        assert!(fx.destroys(Vec2::ZERO, &descriptor(&["gold"])));
+    }
+
+    #[test]
+    fn debris_offsets_spread_evenly_around_the_blast() {
+        let offsets = debris_offsets(4, 10.0, 0.0);
+
+        assert_eq!(offsets.len(), 4);
+        assert!((offsets[0] - Vec2::new(10.0, 0.0)).length() < 1e-4);
+        assert!((offsets[2] - Vec2::new(-10.0, 0.0)).length() < 1e-4);
+        assert!(offsets.iter().sum::<Vec2>().length() < 1e-4);
+    }
+}
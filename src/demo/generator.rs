@@ -0,0 +1,392 @@
+//! 程序化关卡生成
+//!
+//! 超出手工关卡表后，按种子生成关卡布局：
+//! - 场上总价值以本关目标增幅为预算，按深度层的权重抽取实体
+//! - 实体之间至少间隔碰撞半径之和再加最小间距
+//! - 贵重物品附近有概率布置 TNT
+//! - 生成结果使用与手工关卡相同的 [`LevelDescriptor::validate`] 检查，
+//!   并可以导出为 levels.yaml 格式
+
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::config::{
+    Direction, EntitiesConfig, EntityType, GeneratorConfig, LevelDescriptor, LevelEntity,
+    MINE_BOTTOM, Position,
+};
+
+/// 生成区域 (LÖVE 坐标)，比矿区略小，避免实体贴边
+const SPAWN_LEFT: f32 = 20.0;
+const SPAWN_RIGHT: f32 = 300.0;
+const SPAWN_TOP: f32 = 70.0;
+const SPAWN_BOTTOM: f32 = MINE_BOTTOM - 15.0;
+
+/// 每个实体的最大放置尝试次数
+const PLACEMENT_ATTEMPTS: usize = 30;
+
+/// 生成结果未通过检查时，换用后续种子重试的次数
+const GENERATION_RETRIES: u64 = 8;
+
+/// 按种子生成关卡，未通过检查时依次尝试后续种子
+pub fn generate_valid_level(
+    config: &GeneratorConfig,
+    entities: &EntitiesConfig,
+    budget: i32,
+    seed: u64,
+) -> LevelDescriptor {
+    let mut level = generate_level(config, entities, budget, seed);
+    for retry in 1..=GENERATION_RETRIES {
+        let issues = level.validate(entities);
+        if issues.is_empty() {
+            return level;
+        }
+        warn!("Generated level {seed} failed validation: {issues:?}");
+        level = generate_level(config, entities, budget, seed.wrapping_add(retry));
+    }
+    level
+}
+
+/// 按种子生成关卡：相同的种子、预算与配置总是得到相同的布局
+pub fn generate_level(
+    config: &GeneratorConfig,
+    entities: &EntitiesConfig,
+    budget: i32,
+    seed: u64,
+) -> LevelDescriptor {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut placer = Placer {
+        config,
+        entities,
+        placed: Vec::new(),
+    };
+    let mut value = 0;
+
+    while value < budget && placer.placed.len() < config.max_entities {
+        let Some((entity_id, pos)) = placer.pick_and_place(&mut rng) else {
+            break;
+        };
+        let Some(descriptor) = entities.entities.get(&entity_id) else {
+            continue;
+        };
+        let bonus = descriptor.expected_bonus();
+        value += bonus;
+
+        // 贵重物品旁布置 TNT，炸药会炸毁附近的金块
+        if bonus >= config.valuable_bonus && rng.random::<f32>() < config.tnt_cluster_chance {
+            let count = rng.random_range(1..=config.tnt_per_cluster.max(1));
+            for _ in 0..count {
+                placer.place_near(&mut rng, "TNT", pos);
+            }
+        }
+    }
+
+    let background = if config.backgrounds.is_empty() {
+        "LevelE".to_string()
+    } else {
+        config.backgrounds[rng.random_range(0..config.backgrounds.len())].clone()
+    };
+
+    LevelDescriptor {
+        level_type: background,
+        entities: placer
+            .placed
+            .into_iter()
+            .map(|placed| placed.level_entity)
+            .collect(),
+    }
+}
+
+/// 在已有关卡中补充实体 (例如无尽模式的“更多石头”)，保持与已有实体的间距
+pub fn add_extra_entities(
+    level: &mut LevelDescriptor,
+    config: &GeneratorConfig,
+    entities: &EntitiesConfig,
+    entity_ids: &[&str],
+    count: usize,
+    seed: u64,
+) {
+    if entity_ids.is_empty() {
+        return;
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut placer = Placer {
+        config,
+        entities,
+        placed: level
+            .entities
+            .iter()
+            .map(|level_entity| PlacedEntity {
+                level_entity: level_entity.clone(),
+                pos: Vec2::new(level_entity.pos.x, level_entity.pos.y),
+                radius: entities
+                    .entities
+                    .get(&level_entity.entity_id)
+                    .map(|descriptor| descriptor.radius())
+                    .unwrap_or_default(),
+            })
+            .collect(),
+    };
+
+    for _ in 0..count {
+        let entity_id = entity_ids[rng.random_range(0..entity_ids.len())];
+        for _ in 0..PLACEMENT_ATTEMPTS {
+            let pos = Vec2::new(
+                rng.random_range(SPAWN_LEFT..SPAWN_RIGHT),
+                rng.random_range(SPAWN_TOP..SPAWN_BOTTOM),
+            );
+            if placer.try_place(&mut rng, entity_id, pos) {
+                break;
+            }
+        }
+    }
+
+    level.entities = placer
+        .placed
+        .into_iter()
+        .map(|placed| placed.level_entity)
+        .collect();
+}
+
+/// 将关卡导出为 levels.yaml 中的一项
+#[cfg(any(feature = "dev", test))]
+pub fn export_yaml(id: &str, level: &LevelDescriptor) -> Result<String, serde_yaml::Error> {
+    let mut levels = HashMap::new();
+    levels.insert(id.to_string(), level.clone());
+    serde_yaml::to_string(&levels)
+}
+
+struct PlacedEntity {
+    level_entity: LevelEntity,
+    pos: Vec2,
+    radius: f32,
+}
+
+/// 记录已放置的实体，保证最小间距
+struct Placer<'a> {
+    config: &'a GeneratorConfig,
+    entities: &'a EntitiesConfig,
+    placed: Vec<PlacedEntity>,
+}
+
+impl Placer<'_> {
+    /// 随机选择深度与该深度的实体，放置成功时返回实体与坐标
+    fn pick_and_place(&mut self, rng: &mut StdRng) -> Option<(String, Vec2)> {
+        for _ in 0..PLACEMENT_ATTEMPTS {
+            let y = rng.random_range(SPAWN_TOP..SPAWN_BOTTOM);
+            let entity_id = self.pick_entity(rng, y)?;
+            let x = rng.random_range(SPAWN_LEFT..SPAWN_RIGHT);
+            let pos = Vec2::new(x, y);
+            if self.try_place(rng, &entity_id, pos) {
+                return Some((entity_id, pos));
+            }
+        }
+        None
+    }
+
+    /// 在 center 附近放置实体 (TNT 聚集)
+    fn place_near(&mut self, rng: &mut StdRng, entity_id: &str, center: Vec2) -> bool {
+        let Some(radius) = self.radius_of(entity_id) else {
+            return false;
+        };
+        for _ in 0..PLACEMENT_ATTEMPTS {
+            let angle = rng.random_range(0.0..std::f32::consts::TAU);
+            let distance = radius * 2.0 + self.config.min_spacing + rng.random_range(0.0..12.0);
+            let pos = center + Vec2::from_angle(angle) * distance;
+            if (SPAWN_LEFT..=SPAWN_RIGHT).contains(&pos.x)
+                && (SPAWN_TOP..=SPAWN_BOTTOM).contains(&pos.y)
+                && self.try_place(rng, entity_id, pos)
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn pick_entity(&self, rng: &mut StdRng, y: f32) -> Option<String> {
+        let layer = self
+            .config
+            .layers
+            .iter()
+            .find(|layer| y <= layer.until_y)
+            .or(self.config.layers.last())?;
+
+        let total: u32 = layer.weights.values().sum();
+        if total == 0 {
+            return None;
+        }
+        let mut roll = rng.random_range(0..total);
+        for (entity_id, weight) in &layer.weights {
+            if roll < *weight {
+                return Some(entity_id.clone());
+            }
+            roll -= weight;
+        }
+        None
+    }
+
+    fn radius_of(&self, entity_id: &str) -> Option<f32> {
+        self.entities
+            .entities
+            .get(entity_id)
+            .map(|descriptor| descriptor.radius())
+    }
+
+    fn try_place(&mut self, rng: &mut StdRng, entity_id: &str, pos: Vec2) -> bool {
+        let Some(descriptor) = self.entities.entities.get(entity_id) else {
+            return false;
+        };
+        let radius = descriptor.radius();
+        let blocked = self.placed.iter().any(|placed| {
+            placed.pos.distance(pos) < placed.radius + radius + self.config.min_spacing
+        });
+        if blocked {
+            return false;
+        }
+
+        // 巡逻实体随机朝向，沿默认范围左右巡逻
+        let dir = (descriptor.entity_type == EntityType::MoveAround).then(|| {
+            if rng.random::<bool>() {
+                Direction::Left
+            } else {
+                Direction::Right
+            }
+        });
+
+        self.placed.push(PlacedEntity {
+            level_entity: LevelEntity {
+                entity_id: entity_id.to_string(),
+                pos: Position {
+                    x: pos.x.round(),
+                    y: pos.y.round(),
+                },
+                dir,
+                carries: None,
+                path: None,
+            },
+            pos,
+            radius,
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LevelsConfig;
+
+    fn entities() -> EntitiesConfig {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/config/entities.yaml");
+        serde_yaml::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn total_value(level: &LevelDescriptor, entities: &EntitiesConfig) -> i32 {
+        level
+            .entities
+            .iter()
+            .map(|e| entities.entities[&e.entity_id].expected_bonus())
+            .sum()
+    }
+
+    #[test]
+    fn authored_levels_pass_validation() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/config/levels.yaml");
+        let levels: LevelsConfig =
+            serde_yaml::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let entities = entities();
+
+        for (id, level) in &levels.levels {
+            assert_eq!(level.validate(&entities), Vec::<String>::new(), "{id}");
+        }
+    }
+
+    #[test]
+    fn same_seed_generates_same_valid_layout() {
+        let config = GeneratorConfig::default();
+        let entities = entities();
+
+        for seed in 0..20 {
+            let level = generate_valid_level(&config, &entities, 3000, seed);
+            let again = generate_level(&config, &entities, 3000, seed);
+
+            assert!(level.validate(&entities).is_empty(), "seed {seed}");
+            assert_eq!(
+                export_yaml("GEN", &level).unwrap(),
+                export_yaml("GEN", &again).unwrap()
+            );
+            assert!(total_value(&level, &entities) >= 3000 || level.entities.len() >= 24);
+        }
+    }
+
+    #[test]
+    fn generated_entities_keep_spacing_and_cluster_tnt_near_valuables() {
+        let config = GeneratorConfig {
+            tnt_cluster_chance: 1.0,
+            ..default()
+        };
+        let entities = entities();
+        let distance =
+            |a: &LevelEntity, b: &LevelEntity| (a.pos.x - b.pos.x).hypot(a.pos.y - b.pos.y);
+        let mut tnt_count = 0;
+
+        for seed in 0..10 {
+            let level = generate_level(&config, &entities, 5000, seed);
+
+            for (index, a) in level.entities.iter().enumerate() {
+                for b in level.entities.iter().skip(index + 1) {
+                    // 坐标取整后允许 1 像素误差
+                    assert!(distance(a, b) + 1.0 >= config.min_spacing + 12.0);
+                }
+            }
+
+            for tnt in level.entities.iter().filter(|e| e.entity_id == "TNT") {
+                tnt_count += 1;
+                assert!(level.entities.iter().any(|e| {
+                    entities.entities[&e.entity_id].expected_bonus() >= config.valuable_bonus
+                        && distance(e, tnt) <= 36.0
+                }));
+            }
+        }
+
+        assert!(tnt_count > 0);
+    }
+
+    #[test]
+    fn exported_yaml_loads_as_levels_config() {
+        let entities = entities();
+        let level = generate_level(&GeneratorConfig::default(), &entities, 2000, 7);
+
+        let yaml = export_yaml("GEN_7", &level).unwrap();
+        let loaded: LevelsConfig = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(loaded.levels["GEN_7"].entities.len(), level.entities.len());
+    }
+
+    #[test]
+    fn extra_entities_keep_existing_layout() {
+        let config = GeneratorConfig::default();
+        let entities = entities();
+        let mut level = generate_level(&config, &entities, 1000, 3);
+        let original = level.entities.len();
+
+        add_extra_entities(
+            &mut level,
+            &config,
+            &entities,
+            &["NormalRock", "BigRock"],
+            4,
+            9,
+        );
+
+        assert!(level.entities.len() > original);
+        assert!(
+            level.entities[original..]
+                .iter()
+                .all(|e| e.entity_id.ends_with("Rock"))
+        );
+        assert!(level.validate(&entities).is_empty());
+    }
+}
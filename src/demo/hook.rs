@@ -1,18 +1,29 @@
 use crate::AppSystems;
 use crate::asset_tracking::LoadResource;
 use crate::audio::{AudioAssets, sound_effect};
-use crate::config::{EntityDescriptor, EntityType, ImageAssets};
-use crate::constants::COLOR_GREEN;
+use crate::config::{EntityDescriptor, EntityType, GameplayConfig, ImageAssets};
+use crate::constants::{COLOR_GREEN, COLOR_YELLOW};
+use crate::demo::bot::{BotIntents, Controllers};
+use crate::demo::combo::ComboState;
+use crate::demo::dynamite::spawn_thrown_dynamite;
+use crate::demo::entity::{Burrowed, Carrying};
 use crate::demo::explosive::{ExplosiveState, spawn_standard_explosion_fx};
 use crate::demo::fx::{FXAnimation, FXPlacement, FXPlayback};
-use crate::demo::player::{PlayerAnimation, PlayerAnimationState, PlayerResource};
+use crate::demo::level::GameplayHandle;
+use crate::demo::payout::{PayoutContext, PayoutEffect, PayoutPipeline};
+use crate::demo::player::{
+    Inventories, PlayerAnimation, PlayerAnimationState, PlayerSlot, set_animation,
+};
 use crate::screens::Screen;
+use crate::telemetry::TelemetryEvent;
 use crate::utils::love_to_bevy_coords;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 
 pub(super) fn plugin(app: &mut App) {
     app.load_resource::<HookAssets>();
+    app.init_resource::<HookStats>();
+    app.add_message::<EntityCollected>();
     app.add_systems(OnEnter(Screen::Gameplay), spawn_hook);
     app.add_systems(
         Update,
@@ -24,14 +35,14 @@ pub(super) fn plugin(app: &mut App) {
 }
 
 // --- 配置常量 ---
-const HOOK_MIN_ANGLE: f32 = -75.0; // 最小角度 (-75度)
-const HOOK_MAX_ANGLE: f32 = 75.0; // 最大角度 (75度)
-const HOOK_ROTATE_SPEED: f32 = 65.0; // 旋转速度 (度/秒)
-const HOOK_MAX_LENGTH: f32 = 230.0; // 最大伸出长度 (对齐 Lua)
-const HOOK_GRAB_SPEED: f32 = 100.0; // 抓取速度 (像素/秒)
-const HOOK_COLLISION_RADIUS: f32 = 6.0; // 钩子碰撞半径 (匹配 Lua)
-const HOOK_COLLISION_OFFSET: f32 = 13.0; // 碰撞圆心偏移 (匹配 Lua)
-const BONUS_DISPLAY_DURATION: f32 = 1.0; // 奖励显示时长 (秒)
+pub const HOOK_MIN_ANGLE: f32 = -75.0; // 最小角度 (-75度)
+pub const HOOK_MAX_ANGLE: f32 = 75.0; // 最大角度 (75度)
+const HOOK_ROTATE_SPEED: f32 = 65.0; // 基础旋转速度 (度/秒)
+const HOOK_MAX_LENGTH: f32 = 230.0; // 基础最大伸出长度 (对齐 Lua)
+pub const HOOK_GRAB_SPEED: f32 = 100.0; // 基础抓取速度 (像素/秒)
+const HOOK_COLLISION_RADIUS: f32 = 6.0; // 基础钩子碰撞半径 (匹配 Lua)
+pub const HOOK_COLLISION_OFFSET: f32 = 13.0; // 碰撞圆心偏移 (匹配 Lua)
+pub const BONUS_DISPLAY_DURATION: f32 = 1.0; // 奖励显示时长 (秒)
 const STRENGTH_DISPLAY_DURATION: f32 = 1.0;
 
 fn carried_entity_anchor(entity_type: &EntityType) -> Anchor {
@@ -42,6 +53,116 @@ fn carried_entity_anchor(entity_type: &EntityType) -> Anchor {
     }
 }
 
+// --- 钩子升级 ---
+pub const HOOK_UPGRADE_MAX_TIER: u32 = 3; // 每项升级的最高等级
+const ROPE_LENGTH_PER_TIER: f32 = 30.0; // 每级增加的绳长
+const DESCENT_SPEED_PER_TIER: f32 = 25.0; // 每级增加的下放速度
+const CLAW_RADIUS_PER_TIER: f32 = 1.5; // 每级增加的钩爪碰撞半径
+const SWING_SPEED_PER_TIER: f32 = 10.0; // 每级降低的摆动速度
+
+/// 钩子升级项 (商店中购买，整局有效)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HookUpgrade {
+    /// 更长的绳子
+    LongerRope,
+    /// 更快的下放速度
+    FasterDescent,
+    /// 更宽的钩爪
+    WiderClaw,
+    /// 更慢的摆动
+    SlowerSwing,
+}
+
+impl HookUpgrade {
+    pub const ALL: [HookUpgrade; 4] = [
+        HookUpgrade::LongerRope,
+        HookUpgrade::FasterDescent,
+        HookUpgrade::WiderClaw,
+        HookUpgrade::SlowerSwing,
+    ];
+
+    /// 购买下一级 (next_tier 从 1 开始) 的基础价格，逐级递增
+    pub fn base_price(&self, next_tier: u32) -> u32 {
+        let base = match self {
+            HookUpgrade::LongerRope => 150,
+            HookUpgrade::FasterDescent => 200,
+            HookUpgrade::WiderClaw => 250,
+            HookUpgrade::SlowerSwing => 120,
+        };
+        base * next_tier
+    }
+}
+
+/// 单局内的钩子参数：由商店升级逐级提升，开始新游戏时重置
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct HookStats {
+    pub rope_tier: u32,
+    pub descent_tier: u32,
+    pub claw_tier: u32,
+    pub swing_tier: u32,
+}
+
+impl HookStats {
+    /// 最大伸出长度
+    pub fn max_length(&self) -> f32 {
+        HOOK_MAX_LENGTH + self.rope_tier as f32 * ROPE_LENGTH_PER_TIER
+    }
+
+    /// 下放速度 (回缩速度仍由力量与质量决定)
+    pub fn grab_speed(&self) -> f32 {
+        HOOK_GRAB_SPEED + self.descent_tier as f32 * DESCENT_SPEED_PER_TIER
+    }
+
+    /// 钩爪碰撞半径
+    pub fn collision_radius(&self) -> f32 {
+        HOOK_COLLISION_RADIUS + self.claw_tier as f32 * CLAW_RADIUS_PER_TIER
+    }
+
+    /// 摆动速度
+    pub fn rotate_speed(&self) -> f32 {
+        HOOK_ROTATE_SPEED - self.swing_tier as f32 * SWING_SPEED_PER_TIER
+    }
+
+    pub fn tier(&self, upgrade: HookUpgrade) -> u32 {
+        match upgrade {
+            HookUpgrade::LongerRope => self.rope_tier,
+            HookUpgrade::FasterDescent => self.descent_tier,
+            HookUpgrade::WiderClaw => self.claw_tier,
+            HookUpgrade::SlowerSwing => self.swing_tier,
+        }
+    }
+
+    pub fn can_upgrade(&self, upgrade: HookUpgrade) -> bool {
+        self.tier(upgrade) < HOOK_UPGRADE_MAX_TIER
+    }
+
+    /// 提升一级，已满级时不变
+    pub fn upgrade(&mut self, upgrade: HookUpgrade) {
+        if !self.can_upgrade(upgrade) {
+            return;
+        }
+        match upgrade {
+            HookUpgrade::LongerRope => self.rope_tier += 1,
+            HookUpgrade::FasterDescent => self.descent_tier += 1,
+            HookUpgrade::WiderClaw => self.claw_tier += 1,
+            HookUpgrade::SlowerSwing => self.swing_tier += 1,
+        }
+    }
+
+    /// 是否购买过任何升级
+    pub fn is_upgraded(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// HUD 显示的升级等级
+    pub fn hud_label(&self) -> String {
+        format!(
+            "Hook R{} D{} C{} S{}",
+            self.rope_tier, self.descent_tier, self.claw_tier, self.swing_tier
+        )
+    }
+}
+
 // --- 动画帧索引 ---
 const HOOK_ANIM_IDLE: usize = 0;
 const HOOK_ANIM_GRAB_NORMAL: usize = 1;
@@ -62,13 +183,23 @@ pub struct Hook {
     pub strength_timer: f32,
     pub current_bonus: i32,  // 当前奖励金额
     pub show_strength: bool, // 是否显示力量增强图标
+    pub origin: Vec2,        // 绳索起点 (矿工手中)
+    pub reel_time: f32,      // 本次回收已用的秒数
+}
+
+/// 抓到的物品结算为金钱时发送
+#[derive(Message, Debug, Clone)]
+pub struct EntityCollected {
+    pub entity_id: String,
+    pub bonus: u32,
 }
 
+/// 抓取结算的浮动文字，记录所属玩家
 #[derive(Component)]
-struct BonusText;
+struct BonusText(PlayerSlot);
 
 #[derive(Component)]
-struct StrengthIcon;
+struct StrengthIcon(PlayerSlot);
 
 impl Default for Hook {
     fn default() -> Self {
@@ -84,6 +215,8 @@ impl Default for Hook {
             strength_timer: 0.0,
             current_bonus: 0,
             show_strength: false,
+            origin: love_to_bevy_coords(158.0, 30.0),
+            reel_time: 0.0,
         }
     }
 }
@@ -108,69 +241,127 @@ fn spawn_hook(
     mut commands: Commands,
     hook_assets: Res<HookAssets>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    inventories: Inventories,
 ) {
     let layout = TextureAtlasLayout::from_grid(UVec2::new(13, 15), 3, 1, None, None);
     let texture_atlas_layout = texture_atlas_layouts.add(layout);
 
-    let base_pos = love_to_bevy_coords(158.0, 30.0);
+    for slot in inventories.slots() {
+        let base_pos = love_to_bevy_coords(158.0 + slot.offset(inventories.is_two_player()), 30.0);
 
-    commands.spawn((
-        Name::new("hook"),
-        Hook::default(),
-        Sprite::from_atlas_image(
-            hook_assets.hook.clone(),
-            TextureAtlas {
-                layout: texture_atlas_layout,
-                index: HOOK_ANIM_IDLE,
+        commands.spawn((
+            Name::new(format!("hook {}", slot.label())),
+            Hook {
+                origin: base_pos,
+                ..default()
             },
-        ),
-        ZIndex(10),
-        Transform::from_translation(base_pos.extend(0.0)),
-        Anchor::TOP_CENTER,
-        DespawnOnExit(Screen::Gameplay),
-    ));
+            slot,
+            Sprite::from_atlas_image(
+                hook_assets.hook.clone(),
+                TextureAtlas {
+                    layout: texture_atlas_layout.clone(),
+                    index: HOOK_ANIM_IDLE,
+                },
+            ),
+            ZIndex(10),
+            Transform::from_translation(base_pos.extend(0.0)),
+            Anchor::TOP_CENTER,
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
+}
+
+/// 钩子的按键绑定
+struct HookBindings {
+    fire: &'static [KeyCode],
+    dynamite: &'static [KeyCode],
+    /// 使用第几个手柄 (按连接顺序)，None 表示任意手柄
+    gamepad: Option<usize>,
+}
+
+/// 单人模式兼容所有按键与手柄；双人模式 1P 使用 S/W 与第一个手柄，2P 使用方向键与第二个手柄
+fn bindings(slot: PlayerSlot, two_player: bool) -> HookBindings {
+    match (two_player, slot.0) {
+        (false, _) => HookBindings {
+            fire: &[KeyCode::ArrowDown, KeyCode::KeyJ, KeyCode::KeyK],
+            dynamite: &[KeyCode::ArrowUp, KeyCode::KeyU, KeyCode::KeyI],
+            gamepad: None,
+        },
+        (true, 0) => HookBindings {
+            fire: &[KeyCode::KeyS],
+            dynamite: &[KeyCode::KeyW],
+            gamepad: Some(0),
+        },
+        (true, _) => HookBindings {
+            fire: &[KeyCode::ArrowDown],
+            dynamite: &[KeyCode::ArrowUp],
+            gamepad: Some(1),
+        },
+    }
 }
 
 fn handle_hook_input(
     input: Res<ButtonInput<KeyCode>>,
-    gamepads: Query<&Gamepad>,
+    gamepads: Query<(Entity, &Gamepad)>,
     mut commands: Commands,
     audio_assets: Res<AudioAssets>,
-    mut q_hook: Query<(&mut Hook, &Transform, &mut Sprite)>,
-    mut player: ResMut<PlayerResource>,
+    mut q_hook: Query<(&mut Hook, &Transform, &mut Sprite, &PlayerSlot)>,
+    mut inventories: Inventories,
     stats: Res<crate::screens::stats::LevelStats>,
     mut next_screen: ResMut<NextState<Screen>>,
-    mut q_player_anim: Query<&mut PlayerAnimation>,
+    mut q_player_anim: Query<(&mut PlayerAnimation, &PlayerSlot)>,
     image_assets: Res<ImageAssets>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    gameplay_handle: Res<GameplayHandle>,
+    gameplay: Res<Assets<GameplayConfig>>,
+    controllers: Res<Controllers>,
+    intents: Res<BotIntents>,
+    q_level_entities: Query<&crate::config::LevelEntity>,
 ) {
-    let mut fire = input.just_pressed(KeyCode::ArrowDown)
-        || input.just_pressed(KeyCode::KeyJ)
-        || input.just_pressed(KeyCode::KeyK);
-    let mut use_dynamite = input.just_pressed(KeyCode::ArrowUp)
-        || input.just_pressed(KeyCode::KeyU)
-        || input.just_pressed(KeyCode::KeyI);
-    let mut skip = input.just_pressed(KeyCode::Space);
-
-    for gamepad in &gamepads {
-        if gamepad.just_pressed(GamepadButton::DPadDown)
-            || gamepad.just_pressed(GamepadButton::South)
-            || gamepad.just_pressed(GamepadButton::East)
-        {
-            fire = true;
-        }
-        if gamepad.just_pressed(GamepadButton::DPadUp)
-            || gamepad.just_pressed(GamepadButton::West)
-            || gamepad.just_pressed(GamepadButton::North)
-        {
-            use_dynamite = true;
-        }
+    // 手柄按连接顺序分配给玩家
+    let mut gamepads: Vec<_> = gamepads.iter().collect();
+    gamepads.sort_by_key(|(entity, _)| *entity);
+
+    let mut skip = input.just_pressed(KeyCode::Space) || intents.skip;
+    for (_, gamepad) in &gamepads {
         if gamepad.just_pressed(GamepadButton::Select) {
             skip = true;
         }
     }
 
-    if let Some((mut hook, transform, mut sprite)) = q_hook.iter_mut().next() {
+    let two_player = inventories.is_two_player();
+    for (mut hook, transform, mut sprite, slot) in &mut q_hook {
+        let bindings = bindings(*slot, two_player);
+        let mut fire = input.any_just_pressed(bindings.fire.iter().copied());
+        let mut use_dynamite = input.any_just_pressed(bindings.dynamite.iter().copied());
+
+        // 机器人操作的槽位只接受机器人的决定
+        if controllers.is_bot(*slot) {
+            let intent = intents.hooks[slot.0];
+            fire = intent.fire;
+            use_dynamite = intent.use_dynamite;
+        }
+
+        for (index, (_, gamepad)) in gamepads.iter().enumerate() {
+            if controllers.is_bot(*slot)
+                || bindings.gamepad.is_some_and(|assigned| assigned != index)
+            {
+                continue;
+            }
+            if gamepad.just_pressed(GamepadButton::DPadDown)
+                || gamepad.just_pressed(GamepadButton::South)
+                || gamepad.just_pressed(GamepadButton::East)
+            {
+                fire = true;
+            }
+            if gamepad.just_pressed(GamepadButton::DPadUp)
+                || gamepad.just_pressed(GamepadButton::West)
+                || gamepad.just_pressed(GamepadButton::North)
+            {
+                use_dynamite = true;
+            }
+        }
+
         // 1. 发射钩子
         if fire && !hook.is_grabing && !hook.is_backing && !hook.is_showing_bonus {
             hook.is_grabing = true;
@@ -183,17 +374,10 @@ fn handle_hook_input(
         if use_dynamite
             && hook.is_backing
             && hook.grabed_entity.is_some()
-            && player.dynamite_count > 0
-            && !player.is_using_dynamite
+            && inventories.use_dynamite(*slot)
         {
-            player.dynamite_count -= 1;
-            player.is_using_dynamite = true;
-            player.using_dynamite_timer = 0.39;
-
             // 切换玩家动画
-            for mut anim in &mut q_player_anim {
-                anim.update_state(PlayerAnimationState::UseDynamite);
-            }
+            set_animation(&mut q_player_anim, *slot, PlayerAnimationState::UseDynamite);
 
             // 播放炸药生效音效 (Lua 版使用 Dynamite 音效)
             if let Some(audio) = audio_assets.get_audio("Explosive") {
@@ -211,6 +395,13 @@ fn handle_hook_input(
 
             // 销毁被抓取的物品
             if let Some(entity) = hook.grabed_entity {
+                commands.write_message(TelemetryEvent::Dynamite {
+                    player: slot.0,
+                    entity_id: q_level_entities
+                        .get(entity)
+                        .ok()
+                        .map(|le| le.entity_id.clone()),
+                });
                 commands.entity(entity).despawn();
                 hook.grabed_entity = None;
             }
@@ -222,40 +413,148 @@ fn handle_hook_input(
             // 钩子回缩速度变回正常 (因为物品没了)
         }
 
-        // 3. 跳过关卡
-        if skip && stats.reach_goal() {
-            next_screen.set(Screen::MadeGoal);
+        // 3. 钩子空闲时沿当前角度扔出炸药
+        if use_dynamite
+            && !hook.is_grabing
+            && !hook.is_backing
+            && !hook.is_showing_bonus
+            && inventories.use_dynamite(*slot)
+        {
+            set_animation(&mut q_player_anim, *slot, PlayerAnimationState::UseDynamite);
+            commands.write_message(TelemetryEvent::Dynamite {
+                player: slot.0,
+                entity_id: None,
+            });
+
+            spawn_thrown_dynamite(
+                &mut commands,
+                &image_assets,
+                transform.translation.truncate(),
+                hook.angle,
+                gameplay_handle.get(&gameplay).thrown_dynamite,
+            );
+        }
+    }
+
+    // 4. 跳过关卡 (练习模式直接结束)
+    if skip && stats.can_skip() {
+        next_screen.set(if stats.mode == crate::screens::stats::GameMode::Practice {
+            Screen::GameOver
+        } else {
+            Screen::MadeGoal
+        });
+    }
+}
+
+/// 钩子在本帧碰到的物品
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GrabClaim {
+    slot: PlayerSlot,
+    entity: Entity,
+    /// 碰撞圆心与物品的距离
+    distance: f32,
+}
+
+/// 双人模式下两个钩子同一帧碰到同一物品时，距离更近的钩子抓到，距离相同时 1P 优先；
+/// 落空的钩子继续下探，与查询顺序无关
+fn resolve_grab_claims(mut claims: Vec<GrabClaim>) -> Vec<GrabClaim> {
+    claims.sort_by(|a, b| {
+        a.distance
+            .total_cmp(&b.distance)
+            .then(a.slot.0.cmp(&b.slot.0))
+    });
+    let mut resolved: Vec<GrabClaim> = Vec::with_capacity(claims.len());
+    for claim in claims {
+        if !resolved.iter().any(|won| won.entity == claim.entity) {
+            resolved.push(claim);
         }
     }
+    resolved
 }
 
 fn update_hook(
     time: Res<Time>,
     mut commands: Commands,
     mut gizmos: Gizmos,
-    player: Res<PlayerResource>,
+    inventories: Inventories,
+    hook_stats: Res<HookStats>,
+    stats: Res<crate::screens::stats::LevelStats>,
+    mut combo: ResMut<ComboState>,
     audio_assets: Res<AudioAssets>,
-    mut query: Query<(&mut Hook, &mut Transform, &mut Sprite)>,
+    mut query: Query<(&mut Hook, &mut Transform, &mut Sprite, &PlayerSlot)>,
     q_entities: Query<
         (Entity, &GlobalTransform),
-        (With<crate::config::LevelEntity>, Without<Hook>),
+        (
+            With<crate::config::LevelEntity>,
+            Without<Hook>,
+            Without<Burrowed>,
+        ),
     >,
     q_descriptors: Query<&EntityDescriptor>,
     mut q_explosives: Query<&mut ExplosiveState>,
-    mut q_player_anim: Query<&mut PlayerAnimation>,
+    mut q_player_anim: Query<(&mut PlayerAnimation, &PlayerSlot)>,
     q_level_entities: Query<&crate::config::LevelEntity>,
     image_assets: Res<ImageAssets>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
     let rope_color = Color::srgb(66.0 / 255.0, 66.0 / 255.0, 66.0 / 255.0);
-    let base_pos = love_to_bevy_coords(158.0, 30.0);
 
-    for (mut hook, mut transform, mut sprite) in &mut query {
+    // 已被钩住的物品，另一个钩子不能再抓
+    let grabbed: Vec<Entity> = query
+        .iter()
+        .filter_map(|(hook, ..)| hook.grabed_entity)
+        .collect();
+
+    // 本帧各钩子碰到的最近物品，多个钩子碰到同一物品时按距离与槽位裁决
+    let grab_length = time.delta_secs() * hook_stats.grab_speed();
+    let claims = query
+        .iter()
+        .filter(|(hook, ..)| hook.is_grabing && !hook.is_showing_bonus)
+        .filter_map(|(hook, _, _, slot)| {
+            let angle_rad = hook.angle.to_radians();
+            let dir = Vec2::new(angle_rad.sin(), -angle_rad.cos());
+            let collision_pos =
+                hook.origin + dir * (hook.length + grab_length + HOOK_COLLISION_OFFSET);
+
+            q_entities
+                .iter()
+                .filter(|(entity, _)| !grabbed.contains(entity))
+                .filter_map(|(entity, entity_transform)| {
+                    // 获取实体的碰撞半径，默认为 HOOK_COLLISION_RADIUS
+                    let entity_radius = q_descriptors
+                        .get(entity)
+                        .ok()
+                        .and_then(|descriptor| descriptor.collision_radius)
+                        .unwrap_or(HOOK_COLLISION_RADIUS);
+                    let distance =
+                        collision_pos.distance(entity_transform.translation().truncate());
+                    // 碰撞判定：当两圆心距离小于半径之和时发生碰撞 (对齐 Lua)
+                    (distance < hook_stats.collision_radius() + entity_radius).then_some(
+                        GrabClaim {
+                            slot: *slot,
+                            entity,
+                            distance,
+                        },
+                    )
+                })
+                .min_by(|a, b| {
+                    a.distance
+                        .total_cmp(&b.distance)
+                        .then(a.entity.cmp(&b.entity))
+                })
+        })
+        .collect();
+    let claims = resolve_grab_claims(claims);
+
+    for (mut hook, mut transform, mut sprite, slot) in &mut query {
         // 奖励显示状态下跳过其他更新
         if hook.is_showing_bonus {
             continue;
         }
 
+        let base_pos = hook.origin;
+        let player = inventories.get(*slot);
+
         // 绘制绳索
         if hook.is_grabing || hook.is_backing {
             let angle_rad = hook.angle.to_radians();
@@ -271,12 +570,10 @@ fn update_hook(
 
         if hook.is_grabing {
             // 切换玩家动画到 Grab 状态
-            for mut player_anim in &mut q_player_anim {
-                player_anim.update_state(PlayerAnimationState::Grab);
-            }
+            set_animation(&mut q_player_anim, *slot, PlayerAnimationState::Grab);
 
             // 抓取逻辑：长度递增
-            hook.length += time.delta_secs() * HOOK_GRAB_SPEED;
+            hook.length += time.delta_secs() * hook_stats.grab_speed();
 
             let angle_rad = hook.angle.to_radians();
             let dir = Vec2::new(angle_rad.sin(), -angle_rad.cos());
@@ -289,89 +586,76 @@ fn update_hook(
             // 碰撞检测圆心位置 (末端 + 偏移)
             let collision_pos = base_pos + dir * (hook.length + HOOK_COLLISION_OFFSET);
 
-            // 碰撞检测
+            // 碰撞检测 (本帧的裁决结果)
             let mut collided = false;
-            for (entity, entity_transform) in q_entities.iter() {
-                let entity_pos = entity_transform.translation().truncate();
-
-                // 获取实体的碰撞半径，默认为 HOOK_COLLISION_RADIUS
-                let mut entity_radius = HOOK_COLLISION_RADIUS;
-                if let Ok(descriptor) = q_descriptors.get(entity)
-                    && let Some(radius) = descriptor.collision_radius
+            if let Some(claim) = claims.iter().find(|claim| claim.slot == *slot) {
+                let entity = claim.entity;
+                hook.grabed_entity = Some(entity);
+                collided = true;
+
+                // BigGold 闪光特效：被抓取时触发
+                let entity_id = q_level_entities
+                    .get(entity)
+                    .map(|le| le.entity_id.as_str())
+                    .unwrap_or("");
+                if entity_id == "BigGold"
+                    && let Some(fx_image) = image_assets.get_image("BigGoldFX")
                 {
-                    entity_radius = radius;
+                    let layout =
+                        TextureAtlasLayout::from_grid(UVec2::new(16, 16), 3, 3, None, None);
+                    let atlas_layout = texture_atlas_layouts.add(layout);
+
+                    commands.spawn((
+                        Name::new("BigGoldSparkle"),
+                        FXAnimation::new(
+                            9,
+                            0.2,
+                            FXPlayback::Loop,
+                            FXPlacement::Follow {
+                                entity,
+                                offset: Vec2::ZERO,
+                            },
+                        ),
+                        Sprite::from_atlas_image(
+                            fx_image,
+                            TextureAtlas {
+                                layout: atlas_layout,
+                                index: 0,
+                            },
+                        ),
+                        Anchor::CENTER,
+                        DespawnOnExit(Screen::Gameplay),
+                    ));
                 }
 
-                // 碰撞判定：当两圆心距离小于半径之和时发生碰撞 (对齐 Lua)
-                if collision_pos.distance(entity_pos) < (HOOK_COLLISION_RADIUS + entity_radius) {
-                    hook.grabed_entity = Some(entity);
-                    collided = true;
-
-                    // BigGold 闪光特效：被抓取时触发
-                    let entity_id = q_level_entities
-                        .get(entity)
-                        .map(|le| le.entity_id.as_str())
-                        .unwrap_or("");
-                    if entity_id == "BigGold"
-                        && let Some(fx_image) = image_assets.get_image("BigGoldFX")
-                    {
-                        let layout =
-                            TextureAtlasLayout::from_grid(UVec2::new(16, 16), 3, 3, None, None);
-                        let atlas_layout = texture_atlas_layouts.add(layout);
-
-                        commands.spawn((
-                            Name::new("BigGoldSparkle"),
-                            FXAnimation::new(
-                                9,
-                                0.2,
-                                FXPlayback::Loop,
-                                FXPlacement::Follow {
-                                    entity,
-                                    offset: Vec2::ZERO,
-                                },
-                            ),
-                            Sprite::from_atlas_image(
-                                fx_image,
-                                TextureAtlas {
-                                    layout: atlas_layout,
-                                    index: 0,
-                                },
-                            ),
-                            Anchor::CENTER,
-                            DespawnOnExit(Screen::Gameplay),
-                        ));
-                    }
-
-                    // 根据实体大小切换动画帧
-                    if let Ok(descriptor) = q_descriptors.get(entity) {
-                        // TNT 爆炸处理：碰撞时触发爆炸，使用 is_destroyed_tiny 判定动画
-                        if descriptor.entity_type == EntityType::Explosive {
-                            // 触发爆炸
-                            if let Ok(mut explosive_state) = q_explosives.get_mut(entity) {
-                                explosive_state.is_exploding = true;
-                            }
-                            // 使用 is_destroyed_tiny 配置判断动画帧
-                            let is_tiny = descriptor.is_destroyed_tiny.unwrap_or(true);
-                            if let Some(atlas) = &mut sprite.texture_atlas {
-                                atlas.index = if is_tiny {
-                                    HOOK_ANIM_GRAB_MINI
-                                } else {
-                                    HOOK_ANIM_GRAB_NORMAL
-                                };
-                            }
-                        } else {
-                            // 普通实体：简化判断 mass < 2.0 视为小物体
-                            let is_tiny = descriptor.mass.unwrap_or(1.0) < 2.0;
-                            if let Some(atlas) = &mut sprite.texture_atlas {
-                                atlas.index = if is_tiny {
-                                    HOOK_ANIM_GRAB_MINI
-                                } else {
-                                    HOOK_ANIM_GRAB_NORMAL
-                                };
-                            }
+                // 根据实体大小切换动画帧
+                if let Ok(descriptor) = q_descriptors.get(entity) {
+                    // TNT 爆炸处理：碰撞时触发爆炸，使用 is_destroyed_tiny 判定动画
+                    if descriptor.entity_type == EntityType::Explosive {
+                        // 触发爆炸
+                        if let Ok(mut explosive_state) = q_explosives.get_mut(entity) {
+                            explosive_state.is_exploding = true;
+                        }
+                        // 使用 is_destroyed_tiny 配置判断动画帧
+                        let is_tiny = descriptor.is_destroyed_tiny.unwrap_or(true);
+                        if let Some(atlas) = &mut sprite.texture_atlas {
+                            atlas.index = if is_tiny {
+                                HOOK_ANIM_GRAB_MINI
+                            } else {
+                                HOOK_ANIM_GRAB_NORMAL
+                            };
+                        }
+                    } else {
+                        // 普通实体：简化判断 mass < 2.0 视为小物体
+                        let is_tiny = descriptor.mass.unwrap_or(1.0) < 2.0;
+                        if let Some(atlas) = &mut sprite.texture_atlas {
+                            atlas.index = if is_tiny {
+                                HOOK_ANIM_GRAB_MINI
+                            } else {
+                                HOOK_ANIM_GRAB_NORMAL
+                            };
                         }
                     }
-                    break;
                 }
             }
 
@@ -383,9 +667,10 @@ fn update_hook(
                 || collision_pos.y < -half_height
                 || collision_pos.y > half_height;
 
-            if collided || hook.length >= HOOK_MAX_LENGTH || out_of_bounds {
+            if collided || hook.length >= hook_stats.max_length() || out_of_bounds {
                 hook.is_grabing = false;
                 hook.is_backing = true;
+                hook.reel_time = 0.0;
                 // 播放回缩音效
                 if let Some(audio) = audio_assets.get_audio("GrabBack") {
                     commands.spawn(sound_effect(audio));
@@ -393,9 +678,7 @@ fn update_hook(
             }
         } else if hook.is_backing {
             if !player.is_using_dynamite && hook.grabed_entity.is_some() {
-                for mut player_anim in &mut q_player_anim {
-                    player_anim.update_state(PlayerAnimationState::GrabBack);
-                }
+                set_animation(&mut q_player_anim, *slot, PlayerAnimationState::GrabBack);
             }
 
             // 回缩逻辑
@@ -412,6 +695,7 @@ fn update_hook(
             }
 
             hook.length -= time.delta_secs() * speed;
+            hook.reel_time += time.delta_secs();
 
             if hook.length <= 0.0 {
                 hook.length = 0.0;
@@ -422,14 +706,13 @@ fn update_hook(
                     hook.is_showing_bonus = true;
                     hook.bonus_timer = BONUS_DISPLAY_DURATION;
                 } else {
-                    // 无物体，重置动画
+                    // 无物体：空钩回收中断连击，重置动画
+                    combo.break_combo();
                     if let Some(atlas) = &mut sprite.texture_atlas {
                         atlas.index = HOOK_ANIM_IDLE;
                     }
                     // 切换玩家动画回 Idle 状态
-                    for mut player_anim in &mut q_player_anim {
-                        player_anim.update_state(PlayerAnimationState::Idle);
-                    }
+                    set_animation(&mut q_player_anim, *slot, PlayerAnimationState::Idle);
                     // 播放重置音效
                     if let Some(audio) = audio_assets.get_audio("HookReset") {
                         commands.spawn(sound_effect(audio));
@@ -443,15 +726,16 @@ fn update_hook(
             transform.translation = tip_pos.extend(0.0);
             transform.rotation = Quat::from_rotation_z(angle_rad);
         } else {
-            // 待机旋转逻辑
+            // 待机旋转逻辑，摆动速度随难度缩放
+            let swing_speed = hook_stats.rotate_speed() * stats.scaling.swing_scale;
             if hook.rotate_right {
-                hook.angle += HOOK_ROTATE_SPEED * time.delta_secs();
+                hook.angle += swing_speed * time.delta_secs();
                 if hook.angle >= HOOK_MAX_ANGLE {
                     hook.angle = HOOK_MAX_ANGLE;
                     hook.rotate_right = false;
                 }
             } else {
-                hook.angle -= HOOK_ROTATE_SPEED * time.delta_secs();
+                hook.angle -= swing_speed * time.delta_secs();
                 if hook.angle <= HOOK_MIN_ANGLE {
                     hook.angle = HOOK_MIN_ANGLE;
                     hook.rotate_right = true;
@@ -464,6 +748,34 @@ fn update_hook(
     }
 }
 
+/// 结算明细相对金额文字的字号比例
+const BREAKDOWN_FONT_SCALE: f32 = 10.0 / 32.0;
+
+/// 奖励文字的字号：网页版与本地版使用不同的基准，金额与明细按同一基准缩放
+fn bonus_font_size(scale: f32) -> f32 {
+    #[cfg(target_arch = "wasm32")]
+    let base = 8.0;
+    #[cfg(not(target_arch = "wasm32"))]
+    let base = 32.0;
+    base * scale
+}
+
+/// 抓取结算后显示的浮动文字 (金额或增加的时间)
+fn bonus_text(asset_server: &AssetServer, slot: PlayerSlot, x: f32, text: String) -> impl Bundle {
+    (
+        BonusText(slot),
+        Text2d::new(text),
+        TextFont {
+            font: asset_server.load("fonts/Kurland.ttf"),
+            font_size: bonus_font_size(1.0),
+            ..default()
+        },
+        TextColor(COLOR_GREEN),
+        Transform::from_translation(love_to_bevy_coords(x, 18.0).extend(10.0)),
+        Anchor::TOP_LEFT,
+    )
+}
+
 /// 处理奖励显示状态的系统
 fn update_bonus_state(
     time: Res<Time>,
@@ -472,18 +784,24 @@ fn update_bonus_state(
     audio_assets: Res<AudioAssets>,
     image_assets: Res<ImageAssets>,
     mut stats: ResMut<crate::screens::stats::LevelStats>,
-    mut query: Query<(&mut Hook, &mut Sprite)>,
+    mut query: Query<(&mut Hook, &mut Sprite, &PlayerSlot)>,
     q_descriptors: Query<&EntityDescriptor>,
-    q_level_entities: Query<&crate::config::LevelEntity>,
-    mut q_player_anim: Query<&mut PlayerAnimation>,
+    q_level_entities: Query<(&crate::config::LevelEntity, Option<&Carrying>)>,
+    mut combo: ResMut<ComboState>,
+    mut q_player_anim: Query<(&mut PlayerAnimation, &PlayerSlot)>,
     mut q_transforms: Query<(&mut Transform, Option<&mut Anchor>), Without<Hook>>,
-    mut player: ResMut<PlayerResource>,
-    q_bonus_text: Query<Entity, With<BonusText>>,
-    q_strength_icon: Query<Entity, With<StrengthIcon>>,
+    mut inventories: Inventories,
+    q_bonus_text: Query<(Entity, &BonusText)>,
+    pipeline: Res<PayoutPipeline>,
+    q_strength_icon: Query<(Entity, &StrengthIcon)>,
 ) {
-    let base_pos = love_to_bevy_coords(158.0, 30.0);
+    let is_versus = inventories.is_versus();
+    let two_player = inventories.is_two_player();
 
-    for (mut hook, mut sprite) in &mut query {
+    for (mut hook, mut sprite, slot) in &mut query {
+        let base_pos = hook.origin;
+        // 浮动文字随玩家位置偏移
+        let text_offset = slot.offset(two_player);
         // 如果正在抓取物体，同步物体位置和旋转 (对齐 Lua)
         if let Some(entity) = hook.grabed_entity
             && let Ok((mut transform, anchor)) = q_transforms.get_mut(entity)
@@ -507,111 +825,197 @@ fn update_bonus_state(
             continue;
         }
 
-        // 首帧进入奖励状态: 结算并显示 UI
+        // 首帧进入奖励状态: 计时物品增加剩余时间而非金钱
         if hook.bonus_timer == BONUS_DISPLAY_DURATION
             && let Some(entity) = hook.grabed_entity
             && let Ok(descriptor) = q_descriptors.get(entity)
+            && descriptor.entity_type == EntityType::TimeBonus
         {
-            let mut bonus = descriptor.bonus.unwrap_or(0);
+            let seconds = descriptor.time_bonus.unwrap_or(0.0);
+            stats.add_time(seconds);
+            commands.write_message(TelemetryEvent::Grab {
+                player: slot.0,
+                entity_id: q_level_entities
+                    .get(entity)
+                    .map(|(le, _)| le.entity_id.clone())
+                    .unwrap_or_default(),
+                angle: hook.angle,
+                reel_time: hook.reel_time,
+                payout: 0,
+                effects: vec![format!("+{seconds:.0}s")],
+            });
+            commands.spawn(bonus_text(
+                &asset_server,
+                *slot,
+                90.0 + text_offset,
+                format!("+{seconds:.0}s"),
+            ));
+
             let sound_id = descriptor.bonus_type.as_deref().unwrap_or("Normal");
+            if let Some(audio) = audio_assets.get_audio(sound_id) {
+                commands.spawn(sound_effect(audio));
+            }
 
-            // 获取实体 ID 以判断类型
-            let entity_id = q_level_entities
-                .get(entity)
-                .map(|le| le.entity_id.as_str())
-                .unwrap_or("");
+            commands.entity(entity).despawn();
+        }
 
-            // 石头收藏书效果：岩石价值 ×3
-            if player.has_rock_collectors_book
-                && matches!(entity_id, "MiniRock" | "NormalRock" | "BigRock")
-            {
-                bonus *= 3;
-            }
+        // 首帧进入奖励状态: 结算并显示 UI
+        if hook.bonus_timer == BONUS_DISPLAY_DURATION
+            && let Some(entity) = hook.grabed_entity
+            && let Ok(descriptor) = q_descriptors.get(entity)
+            && descriptor.entity_type != EntityType::TimeBonus
+        {
+            let mut sound_id = descriptor.bonus_type.as_deref().unwrap_or("Normal");
+
+            // 获取实体 ID 以判断类型，以及携带的物品
+            let (entity_id, carrying) = q_level_entities
+                .get(entity)
+                .map(|(le, carrying)| (le.entity_id.as_str(), carrying))
+                .unwrap_or(("", None));
+
+            // 依次执行结算修饰器 (道具加成、额外效果判定等)
+            let player = inventories.get_mut(*slot);
+            let mut payout = pipeline.evaluate(
+                &PayoutContext {
+                    entity_id,
+                    descriptor,
+                    player,
+                    scaling: &stats.scaling,
+                },
+                &mut rand::rng(),
+            );
 
-            // 宝石抛光剂效果：钻石价值 ×1.5
-            if player.has_gem_polish {
-                if entity_id == "Diamond" {
-                    bonus = (bonus as f32 * 1.5) as i32;
-                } else if entity_id == "MoleWithDiamond" {
-                    let mole_bonus = 2;
-                    bonus = (((bonus - mole_bonus) as f32) * 1.5 + mole_bonus as f32) as i32;
+            // 携带物品单独结算后累加，音效取价值更高的部分
+            if let Some(carrying) = carrying {
+                let carried = pipeline.evaluate(
+                    &PayoutContext {
+                        entity_id: &carrying.entity_id,
+                        descriptor: &carrying.descriptor,
+                        player,
+                        scaling: &stats.scaling,
+                    },
+                    &mut rand::rng(),
+                );
+                if carried.bonus > payout.bonus {
+                    sound_id = carrying
+                        .descriptor
+                        .bonus_type
+                        .as_deref()
+                        .unwrap_or("Normal");
                 }
+                payout.merge(carried);
             }
 
-            // 幸运草效果：翻倍 extra_effect_chances
-            let mut chances = descriptor.extra_effect_chances.unwrap_or(0.0);
-            if player.has_lucky_clover {
-                chances *= 2.0;
+            // 连击倍率：携带的物品同样计入，只作用于金钱奖励 (对战中关闭)
+            let multiplier = if is_versus {
+                1.0
+            } else {
+                combo.register_grab(|tag| {
+                    descriptor.has_tag(tag) || carrying.is_some_and(|c| c.descriptor.has_tag(tag))
+                })
+            };
+            stats.best_combo = stats.best_combo.max(combo.count);
+            if multiplier > 1.0 && !payout.has_effects() {
+                let bonus = (payout.bonus as f32 * multiplier) as i32;
+                payout.apply(format!("Combo x{multiplier:.2}"), bonus);
             }
 
-            // 处理 extra_effect_chances 特殊效果 (对齐 Lua)
-            if chances > 0.0 {
-                let rand_val = rand::random::<f32>();
-                if rand_val < chances {
-                    // 20% 概率增加炸药，80% 概率增加玩家力量
-                    if rand::random::<f32>() < 0.2 {
-                        player.dynamite_count = (player.dynamite_count + 1).min(12);
-                    } else {
-                        // Lua: strength = min(6, strength * 1.5 + 1)
-                        player.strength = (player.strength * 1.5 + 1.0).min(6.0);
-                        hook.show_strength = true;
-                        hook.strength_timer = STRENGTH_DISPLAY_DURATION;
-
-                        if !player.is_using_dynamite {
-                            for mut player_anim in &mut q_player_anim {
-                                player_anim.update_state(PlayerAnimationState::Strengthen);
+            if payout.has_effects() {
+                for effect in &payout.effects {
+                    match effect {
+                        PayoutEffect::AddDynamite => inventories.add_dynamite(*slot),
+                        PayoutEffect::Strengthen => {
+                            let player = inventories.get_mut(*slot);
+                            player.strengthen();
+                            hook.show_strength = true;
+                            hook.strength_timer = STRENGTH_DISPLAY_DURATION;
+
+                            if !player.is_using_dynamite {
+                                set_animation(
+                                    &mut q_player_anim,
+                                    *slot,
+                                    PlayerAnimationState::Strengthen,
+                                );
                             }
-                        }
 
-                        // Spawn Strength! 文字 - 位置 (80, 10) → Bevy 换算
-                        if let Some(strength_image) = image_assets.get_image("Strength!") {
-                            commands.spawn((
-                                StrengthIcon,
-                                Sprite::from_image(strength_image),
-                                Transform::from_translation(
-                                    love_to_bevy_coords(80.0, 10.0).extend(10.0),
-                                ),
-                                Anchor::TOP_LEFT,
-                            ));
+                            // Spawn Strength! 文字 - 位置 (80, 10) → Bevy 换算
+                            if let Some(strength_image) = image_assets.get_image("Strength!") {
+                                commands.spawn((
+                                    StrengthIcon(*slot),
+                                    Sprite::from_image(strength_image),
+                                    Transform::from_translation(
+                                        love_to_bevy_coords(80.0 + text_offset, 10.0).extend(10.0),
+                                    ),
+                                    Anchor::TOP_LEFT,
+                                ));
+                            }
                         }
                     }
-                    if let Some(audio) = audio_assets.get_audio("High") {
-                        commands.spawn(sound_effect(audio));
-                    }
-                } else {
-                    // 正常奖励
-                    hook.current_bonus = bonus;
-                    stats.money += bonus as u32;
-                    if let Some(audio) = audio_assets.get_audio(sound_id) {
-                        commands.spawn(sound_effect(audio));
-                    }
+                }
+                if let Some(audio) = audio_assets.get_audio("High") {
+                    commands.spawn(sound_effect(audio));
                 }
             } else {
-                // 无特殊效果概率，正常奖励
-                hook.current_bonus = bonus;
-                stats.money += bonus as u32;
+                // 正常奖励
+                hook.current_bonus = payout.bonus;
+                inventories.award(*slot, &mut stats, payout.bonus as u32);
+                commands.write_message(EntityCollected {
+                    entity_id: entity_id.to_string(),
+                    bonus: payout.bonus as u32,
+                });
                 if let Some(audio) = audio_assets.get_audio(sound_id) {
                     commands.spawn(sound_effect(audio));
                 }
             }
 
+            commands.write_message(TelemetryEvent::Grab {
+                player: slot.0,
+                entity_id: entity_id.to_string(),
+                angle: hook.angle,
+                reel_time: hook.reel_time,
+                payout: if payout.has_effects() {
+                    0
+                } else {
+                    payout.bonus
+                },
+                effects: payout
+                    .effects
+                    .iter()
+                    .map(|effect| format!("{effect:?}"))
+                    .collect(),
+            });
+
             // 如果有奖励金额，spawn 显示文本
             if hook.current_bonus > 0 {
-                commands.spawn((
-                    BonusText,
-                    Text2d::new(format!("${}", hook.current_bonus)),
-                    TextFont {
-                        font: asset_server.load("fonts/Kurland.ttf"),
-                        #[cfg(target_arch = "wasm32")]
-                        font_size: 8.0,
-                        #[cfg(not(target_arch = "wasm32"))]
-                        font_size: 32.0,
-                        ..default()
-                    },
-                    TextColor(COLOR_GREEN),
-                    Transform::from_translation(love_to_bevy_coords(90.0, 18.0).extend(10.0)),
-                    Anchor::TOP_LEFT,
+                commands.spawn(bonus_text(
+                    &asset_server,
+                    *slot,
+                    90.0 + text_offset,
+                    format!("${}", hook.current_bonus),
                 ));
+
+                // 结算明细：列出生效的修饰器
+                if !payout.breakdown.is_empty() {
+                    let lines: Vec<&str> = payout
+                        .breakdown
+                        .iter()
+                        .map(|step| step.label.as_str())
+                        .collect();
+                    commands.spawn((
+                        BonusText(*slot),
+                        Text2d::new(lines.join("\n")),
+                        TextFont {
+                            font: asset_server.load("fonts/visitor1.ttf"),
+                            font_size: bonus_font_size(BREAKDOWN_FONT_SCALE),
+                            ..default()
+                        },
+                        TextColor(COLOR_YELLOW),
+                        Transform::from_translation(
+                            love_to_bevy_coords(150.0 + text_offset, 5.0).extend(10.0),
+                        ),
+                        Anchor::TOP_LEFT,
+                    ));
+                }
             }
 
             // 销毁被抓取的实体
@@ -620,11 +1024,10 @@ fn update_bonus_state(
 
         hook.bonus_timer -= time.delta_secs();
 
+        let is_using_dynamite = inventories.get(*slot).is_using_dynamite;
         if hook.show_strength {
-            if !player.is_using_dynamite {
-                for mut player_anim in &mut q_player_anim {
-                    player_anim.update_state(PlayerAnimationState::Strengthen);
-                }
+            if !is_using_dynamite {
+                set_animation(&mut q_player_anim, *slot, PlayerAnimationState::Strengthen);
             }
 
             hook.strength_timer -= time.delta_secs();
@@ -632,18 +1035,18 @@ fn update_bonus_state(
                 hook.strength_timer = 0.0;
                 hook.show_strength = false;
 
-                for entity in &q_strength_icon {
-                    commands.entity(entity).despawn();
+                for (entity, icon) in &q_strength_icon {
+                    if icon.0 == *slot {
+                        commands.entity(entity).despawn();
+                    }
                 }
 
-                if !player.is_using_dynamite
+                if !is_using_dynamite
                     && !hook.is_grabing
                     && !hook.is_backing
                     && !hook.is_showing_bonus
                 {
-                    for mut player_anim in &mut q_player_anim {
-                        player_anim.update_state(PlayerAnimationState::Idle);
-                    }
+                    set_animation(&mut q_player_anim, *slot, PlayerAnimationState::Idle);
                 }
             }
         }
@@ -651,8 +1054,10 @@ fn update_bonus_state(
         // 奖励计时器结束
         if hook.bonus_timer <= 0.0 {
             // 清理显示元素
-            for entity in &q_bonus_text {
-                commands.entity(entity).despawn();
+            for (entity, text) in &q_bonus_text {
+                if text.0 == *slot {
+                    commands.entity(entity).despawn();
+                }
             }
 
             // 重置钩子状态
@@ -667,10 +1072,8 @@ fn update_bonus_state(
             }
 
             // 切换玩家动画回 Idle 状态
-            if !hook.show_strength && !player.is_using_dynamite {
-                for mut player_anim in &mut q_player_anim {
-                    player_anim.update_state(PlayerAnimationState::Idle);
-                }
+            if !hook.show_strength && !is_using_dynamite {
+                set_animation(&mut q_player_anim, *slot, PlayerAnimationState::Idle);
             }
 
             // 播放重置音效
@@ -680,3 +1083,75 @@ fn update_bonus_state(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upgrades_stop_at_max_tier() {
+        let mut stats = HookStats::default();
+        for _ in 0..5 {
+            stats.upgrade(HookUpgrade::LongerRope);
+        }
+
+        assert_eq!(stats.rope_tier, HOOK_UPGRADE_MAX_TIER);
+        assert!(!stats.can_upgrade(HookUpgrade::LongerRope));
+        assert!(stats.can_upgrade(HookUpgrade::WiderClaw));
+        assert_eq!(
+            stats.max_length(),
+            HOOK_MAX_LENGTH + HOOK_UPGRADE_MAX_TIER as f32 * ROPE_LENGTH_PER_TIER
+        );
+    }
+
+    #[test]
+    fn slower_swing_keeps_hook_rotating() {
+        let mut stats = HookStats::default();
+        for _ in 0..HOOK_UPGRADE_MAX_TIER {
+            stats.upgrade(HookUpgrade::SlowerSwing);
+        }
+
+        assert!(stats.rotate_speed() > 0.0);
+        assert!(stats.rotate_speed() < HOOK_ROTATE_SPEED);
+    }
+
+    #[test]
+    fn tier_prices_increase() {
+        for upgrade in HookUpgrade::ALL {
+            assert!(upgrade.base_price(2) > upgrade.base_price(1));
+        }
+    }
+
+    #[test]
+    fn versus_bindings_do_not_overlap() {
+        let first = bindings(PlayerSlot(0), true);
+        let second = bindings(PlayerSlot(1), true);
+
+        for key in first.fire.iter().chain(first.dynamite) {
+            assert!(!second.fire.contains(key) && !second.dynamite.contains(key));
+        }
+        assert_ne!(first.gamepad, second.gamepad);
+        assert_eq!(bindings(PlayerSlot(0), false).gamepad, None);
+    }
+
+    #[test]
+    fn contested_grab_goes_to_nearest_hook() {
+        let gold = Entity::from_raw_u32(1).unwrap();
+        let rock = Entity::from_raw_u32(2).unwrap();
+        let claim = |slot, entity, distance| GrabClaim {
+            slot: PlayerSlot(slot),
+            entity,
+            distance,
+        };
+
+        let resolved = resolve_grab_claims(vec![claim(0, gold, 6.0), claim(1, gold, 4.0)]);
+        assert_eq!(resolved, vec![claim(1, gold, 4.0)]);
+
+        // 距离相同时 1P 优先，与输入顺序无关
+        let resolved = resolve_grab_claims(vec![claim(1, gold, 5.0), claim(0, gold, 5.0)]);
+        assert_eq!(resolved, vec![claim(0, gold, 5.0)]);
+
+        let resolved = resolve_grab_claims(vec![claim(0, gold, 5.0), claim(1, rock, 9.0)]);
+        assert_eq!(resolved.len(), 2);
+    }
+}
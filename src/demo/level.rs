@@ -1,20 +1,29 @@
 //! Spawn the main level.
 
-use crate::config::{EntitiesConfig, LevelEntity, LevelsConfig};
+use crate::config::{
+    DEFAULT_BACKGROUND, EntitiesConfig, GENERATED_LEVEL_PREFIX, GameplayConfig, LevelDescriptor,
+    LevelEntity, LevelsConfig, ProgressionConfig,
+};
 use crate::config::{EntityDescriptor, EntityType, ImageAssets};
 use crate::constants::{COLOR_DEEP_ORANGE, COLOR_GREEN, COLOR_ORANGE};
-use crate::demo::player::PlayerResource;
+use crate::demo::combo::ComboText;
+use crate::demo::entity::{CarriedSprite, Carrying};
+use crate::demo::generator::{add_extra_entities, generate_valid_level};
+use crate::demo::hook::HookStats;
+use crate::demo::player::{PlayerResource, PlayerSlot};
 use crate::screens::Screen;
 use crate::screens::stats::LevelStats;
+use crate::screens::versus::VersusState;
 use crate::utils::love_to_bevy_coords;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CurrentLevel>();
     app.add_systems(Startup, setup_level_assets);
     app.add_systems(
         OnEnter(Screen::Gameplay),
-        (setup_ui, spawn_background, spawn_level),
+        (prepare_level, (setup_ui, spawn_background, spawn_level)).chain(),
     );
 
     app.add_systems(
@@ -42,7 +51,15 @@ fn setup_ui(
     stats: Res<crate::screens::stats::LevelStats>,
     image_assets: Res<ImageAssets>,
     player: Res<PlayerResource>,
+    hook_stats: Res<HookStats>,
+    versus: Option<Res<VersusState>>,
 ) {
+    // 对战模式中金钱与目标两行分别显示 1P 与 2P 的金钱
+    let (money_label, goal_label) = if versus.is_some() {
+        ("1P", "2P")
+    } else {
+        ("Money", "Goal")
+    };
     let game_font = asset_server.load("fonts/visitor1.ttf");
     let game_style = TextFont {
         font: game_font.clone(),
@@ -53,7 +70,7 @@ fn setup_ui(
     commands
         .spawn((
             DespawnOnExit(Screen::Gameplay),
-            Text2d::new("Money"),
+            Text2d::new(money_label),
             game_style.clone(),
             TextColor(COLOR_DEEP_ORANGE),
             Transform::from_translation(love_to_bevy_coords(5.0, 5.0).extend(10.0)),
@@ -66,13 +83,19 @@ fn setup_ui(
                 TextColor(COLOR_GREEN),
                 MoneyText,
             ));
+            parent.spawn((
+                TextSpan::default(),
+                game_style.clone(),
+                TextColor(COLOR_ORANGE),
+                ComboText,
+            ));
         });
 
     // Goal HUD
     commands
         .spawn((
             DespawnOnExit(Screen::Gameplay),
-            Text2d::new("Goal"),
+            Text2d::new(goal_label),
             game_style.clone(),
             TextColor(COLOR_DEEP_ORANGE),
             Transform::from_translation(love_to_bevy_coords(11.0, 15.0).extend(10.0)),
@@ -125,6 +148,18 @@ fn setup_ui(
             ));
         });
 
+    // 钩子升级 HUD：仅在购买过升级后显示
+    if hook_stats.is_upgraded() {
+        commands.spawn((
+            DespawnOnExit(Screen::Gameplay),
+            Text2d::new(hook_stats.hud_label()),
+            game_style.clone(),
+            TextColor(COLOR_ORANGE),
+            Transform::from_translation(love_to_bevy_coords(5.0, 25.0).extend(10.0)),
+            Anchor::TOP_LEFT,
+        ));
+    }
+
     // 达成目标提示 "Press Select to Skip"
     commands.spawn((
         DespawnOnExit(Screen::Gameplay),
@@ -197,29 +232,48 @@ fn update_ui(
     mut q_timer: Query<&mut TextSpan, (With<TimerText>, Without<MoneyText>, Without<GoalText>)>,
     mut q_reach_goal: Query<&mut Visibility, With<ReachGoalTip>>,
     mut q_dynamite: Query<(&DynamiteIcon, &mut Visibility), Without<ReachGoalTip>>,
+    versus: Option<Res<VersusState>>,
 ) {
+    // 对战模式：两行分别显示 1P、2P 的金钱与炸药数量
+    let (money, goal) = match &versus {
+        Some(versus) => {
+            let line = |slot| {
+                let player = versus.player(slot);
+                format!(" ${} TNT {}", player.money, player.inventory.dynamite_count)
+            };
+            (line(PlayerSlot(0)), line(PlayerSlot(1)))
+        }
+        None => (
+            format!(" ${}", stats.money_view),
+            format!(" ${}", stats.goal),
+        ),
+    };
     for mut span in &mut q_money {
-        let new_text = format!(" ${}", stats.money_view);
+        let new_text = money.clone();
         if span.0 != new_text {
             span.0 = new_text;
         }
     }
     for mut span in &mut q_goal {
-        let new_text = format!(" ${}", stats.goal);
+        let new_text = goal.clone();
         if span.0 != new_text {
             span.0 = new_text;
         }
     }
     for mut span in &mut q_timer {
-        let new_text = format!("{:.0}", stats.timer);
+        let new_text = if stats.timed {
+            format!("{:.0}", stats.timer)
+        } else {
+            "--".to_string()
+        };
         if span.0 != new_text {
             span.0 = new_text;
         }
     }
 
-    // 更新达成目标提示
+    // 更新达成目标提示 (练习模式随时可以跳过)
     for mut visibility in &mut q_reach_goal {
-        *visibility = if stats.reach_goal() {
+        *visibility = if stats.can_skip() {
             Visibility::Visible
         } else {
             Visibility::Hidden
@@ -236,30 +290,126 @@ fn update_ui(
     }
 }
 
+/// 当前关卡：进入关卡时从 levels.yaml 读取，或按种子生成
+#[derive(Resource, Debug, Default)]
+pub struct CurrentLevel {
+    /// 关卡 ID (例如 "L4_2" 或 "GEN_12345")
+    pub id: String,
+    /// 关卡布局，配置尚未加载时为空
+    pub descriptor: Option<LevelDescriptor>,
+    /// 背景图片 ID
+    pub background: String,
+}
+
+/// 关卡 ID 对应的布局：生成关卡以本关目标增幅为价值预算，手工关卡找不到时使用 L1_1
+pub fn level_layout(
+    id: &str,
+    stats: &LevelStats,
+    progression: &ProgressionConfig,
+    levels: Option<&LevelsConfig>,
+    entities: &EntitiesConfig,
+) -> Option<LevelDescriptor> {
+    if let Some(seed) = id
+        .strip_prefix(GENERATED_LEVEL_PREFIX)
+        .and_then(|seed| seed.parse::<u64>().ok())
+    {
+        let budget =
+            stats.goal_addon as f32 * stats.scaling.goal_scale * progression.generator.value_ratio;
+        return Some(generate_valid_level(
+            &progression.generator,
+            entities,
+            budget as i32,
+            seed,
+        ));
+    }
+
+    levels.and_then(|level| {
+        level
+            .levels
+            .get(id)
+            .or_else(|| level.levels.get("L1_1"))
+            .cloned()
+    })
+}
+
+/// 解析 real_level_str 得到当前关卡布局与背景
+fn prepare_level(
+    mut current: ResMut<CurrentLevel>,
+    level_handle: Res<LevelHandle>,
+    entity_handle: Res<EntityHandle>,
+    progression_handle: Res<ProgressionHandle>,
+    levels: Res<Assets<LevelsConfig>>,
+    entities: Res<Assets<EntitiesConfig>>,
+    progression: Res<Assets<ProgressionConfig>>,
+    stats: Res<LevelStats>,
+) {
+    let progression = progression_handle.get(&progression);
+    let id = stats.real_level_str.clone();
+    let Some(entities_config) = entity_handle.get(&entities) else {
+        *current = CurrentLevel {
+            background: progression
+                .background_for(stats.layout_number())
+                .to_string(),
+            id,
+            descriptor: None,
+        };
+        return;
+    };
+
+    let descriptor = level_layout(
+        &id,
+        &stats,
+        &progression,
+        level_handle.get(&levels),
+        entities_config,
+    );
+    *current = if id.starts_with(GENERATED_LEVEL_PREFIX) {
+        CurrentLevel {
+            background: descriptor
+                .as_ref()
+                .map(|descriptor| descriptor.level_type.clone())
+                .unwrap_or_default(),
+            id,
+            descriptor,
+        }
+    } else {
+        // 手工关卡：由 progression.yaml 的 backgrounds 按关卡编号映射背景
+        if let Some(descriptor) = &descriptor {
+            for issue in descriptor.validate(entities_config) {
+                warn!("Level {id}: {issue}");
+            }
+        }
+        CurrentLevel {
+            background: progression
+                .background_for(stats.layout_number())
+                .to_string(),
+            id,
+            descriptor,
+        }
+    };
+
+    // 无尽模式的“更多石头”修饰
+    let extra_rocks = stats.modifiers.extra_rocks();
+    if extra_rocks > 0
+        && let Some(descriptor) = &mut current.descriptor
+    {
+        add_extra_entities(
+            descriptor,
+            &progression.generator,
+            entities_config,
+            &["NormalRock", "BigRock"],
+            extra_rocks,
+            rand::random(),
+        );
+    }
+}
+
 pub fn spawn_background(
     mut commands: Commands,
     image_assets: Res<ImageAssets>,
-    stats: Res<crate::screens::stats::LevelStats>,
+    current: Res<CurrentLevel>,
 ) {
-    // 根据 real_level_str 解析关卡编号，映射到背景类型
-    // real_level_str 格式: "L1_1", "L2_2", "L3_1" 等
-    // 关卡 1-2 使用 LevelA, 3-4 使用 LevelB, 5-6 使用 LevelC, 7-8 使用 LevelD, 9+ 使用 LevelE
-    let level_num: u32 = stats
-        .real_level_str
-        .chars()
-        .skip(1) // 跳过 'L'
-        .take_while(|c| c.is_ascii_digit())
-        .collect::<String>()
-        .parse()
-        .unwrap_or(1);
-
-    let bg_type = match level_num {
-        1..=2 => "LevelA",
-        3..=4 => "LevelB",
-        5..=6 => "LevelC",
-        7..=8 => "LevelD",
-        _ => "LevelE",
-    };
+    let bg_type = current.background.as_str();
 
     commands.spawn((
         Name::new("LevelBackground"),
@@ -280,51 +430,86 @@ fn bg_top(image_assets: &Res<ImageAssets>) -> impl Bundle {
 }
 
 fn bg_level(image_assets: &Res<ImageAssets>, bg_type: &str) -> impl Bundle {
+    // 背景名来自 progression.yaml，拼写错误时使用默认背景而不是崩溃
+    let image = image_assets.get_image(bg_type).unwrap_or_else(|| {
+        warn!("Unknown level background {bg_type:?}, using {DEFAULT_BACKGROUND}");
+        image_assets.get_image(DEFAULT_BACKGROUND).unwrap()
+    });
     (
         Name::new(format!("{bg_type} Background")),
         Transform::from_translation(love_to_bevy_coords(0.0, 40.0).extend(-1.0)),
         Anchor::TOP_LEFT,
-        Sprite::from_image(image_assets.get_image(bg_type).unwrap()),
+        Sprite::from_image(image),
     )
 }
 
 #[derive(Resource)]
 pub struct LevelHandle(Handle<LevelsConfig>);
 
+impl LevelHandle {
+    /// 读取关卡配置，尚未加载完成时返回 None
+    pub fn get<'a>(&self, assets: &'a Assets<LevelsConfig>) -> Option<&'a LevelsConfig> {
+        assets.get(self.0.id())
+    }
+}
+
 #[derive(Resource)]
 pub struct EntityHandle(Handle<EntitiesConfig>);
 
+impl EntityHandle {
+    /// 读取实体配置，尚未加载完成时返回 None
+    pub fn get<'a>(&self, assets: &'a Assets<EntitiesConfig>) -> Option<&'a EntitiesConfig> {
+        assets.get(self.0.id())
+    }
+}
+
+#[derive(Resource)]
+pub struct GameplayHandle(Handle<GameplayConfig>);
+
+impl GameplayHandle {
+    /// 读取玩法参数，尚未加载完成时使用默认值
+    pub fn get(&self, assets: &Assets<GameplayConfig>) -> GameplayConfig {
+        assets.get(self.0.id()).cloned().unwrap_or_default()
+    }
+}
+
+#[derive(Resource)]
+pub struct ProgressionHandle(Handle<ProgressionConfig>);
+
+impl ProgressionHandle {
+    /// 读取关卡进度配置，尚未加载完成时使用与原版一致的默认值
+    pub fn get(&self, assets: &Assets<ProgressionConfig>) -> ProgressionConfig {
+        assets.get(self.0.id()).cloned().unwrap_or_default()
+    }
+}
+
 pub fn setup_level_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
     let level = LevelHandle(asset_server.load("config/levels.yaml"));
     commands.insert_resource(level);
 
     let entities = EntityHandle(asset_server.load("config/entities.yaml"));
     commands.insert_resource(entities);
+
+    let gameplay = GameplayHandle(asset_server.load("config/gameplay.yaml"));
+    commands.insert_resource(gameplay);
+
+    let progression = ProgressionHandle(asset_server.load("config/progression.yaml"));
+    commands.insert_resource(progression);
 }
 
 pub fn spawn_level(
     mut commands: Commands,
-    level_handle: Res<LevelHandle>,
     entity_handle: Res<EntityHandle>,
-    levels: Res<Assets<LevelsConfig>>,
     entities: Res<Assets<EntitiesConfig>>,
-    stats: Res<LevelStats>,
+    current: Res<CurrentLevel>,
 ) {
-    if let (Some(level), Some(entities_config)) = (
-        levels.get(level_handle.0.id()),
-        entities.get(entity_handle.0.id()),
-    ) {
-        let selected_level = level
-            .levels
-            .get(&stats.real_level_str)
-            .or_else(|| level.levels.get("L1_1"));
-
-        let Some(config) = selected_level else {
-            warn!("No level config found for {}", stats.real_level_str);
+    if let Some(entities_config) = entity_handle.get(&entities) {
+        let Some(config) = &current.descriptor else {
+            warn!("No level config found for {}", current.id);
             return;
         };
 
-        info!("Loading gameplay level {}", stats.real_level_str);
+        info!("Loading gameplay level {}", current.id);
 
         commands
             .spawn((
@@ -335,7 +520,19 @@ pub fn spawn_level(
             ))
             .with_children(|parent| {
                 for level_entity in config.entities.clone() {
-                    parent.spawn((
+                    // 携带物品 (例如地鼠抱着的钻石)
+                    let carrying = level_entity.carries.as_ref().and_then(|carried_id| {
+                        let descriptor = entities_config.entities.get(carried_id);
+                        if descriptor.is_none() {
+                            warn!("Unknown carried entity {carried_id}");
+                        }
+                        descriptor.map(|descriptor| Carrying {
+                            entity_id: carried_id.clone(),
+                            descriptor: descriptor.clone(),
+                        })
+                    });
+
+                    let mut entity = parent.spawn((
                         Name::new(level_entity.entity_id.clone()),
                         Transform::from_translation(
                             love_to_bevy_coords(level_entity.pos.x, level_entity.pos.y).extend(1.0),
@@ -347,6 +544,9 @@ pub fn spawn_level(
                             .clone(),
                         level_entity,
                     ));
+                    if let Some(carrying) = carrying {
+                        entity.insert(carrying);
+                    }
                 }
             });
     }
@@ -355,11 +555,14 @@ pub fn spawn_level(
 pub fn spawn_entity_sprite(
     mut commands: Commands,
     _entity_handle: Res<EntityHandle>,
-    q_entities: Query<(Entity, &LevelEntity, &EntityDescriptor), Added<LevelEntity>>,
+    q_entities: Query<
+        (Entity, &LevelEntity, &EntityDescriptor, Option<&Carrying>),
+        Added<LevelEntity>,
+    >,
     entities_assets: Res<ImageAssets>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
-    for (entity, level_entity, entity_desc) in q_entities.iter() {
+    for (entity, level_entity, entity_desc, carrying) in q_entities.iter() {
         if let Some(img_handle) = entities_assets.get_image(&level_entity.entity_id) {
             let anchor = if entity_desc.entity_type == EntityType::MoveAround {
                 Anchor::CENTER
@@ -393,6 +596,19 @@ pub fn spawn_entity_sprite(
                         vec![0, 1, 2, 3, 4, 5, 6],
                     ),
                 ));
+
+                // 携带物品作为子实体渲染，随携带者移动和翻转
+                if let Some(carrying) = carrying
+                    && let Some(carried_image) = entities_assets.get_image(&carrying.entity_id)
+                {
+                    commands.entity(entity).with_child((
+                        Name::new(format!("Carried {}", carrying.entity_id)),
+                        CarriedSprite,
+                        Sprite::from_image(carried_image),
+                        Transform::from_translation(CarriedSprite::offset(false).extend(0.1)),
+                        Anchor::CENTER,
+                    ));
+                }
             } else if entity_desc.entity_type == EntityType::Explosive {
                 // TNT (Explosive) logic: 添加 ExplosiveState 组件
                 commands.entity(entity).insert((
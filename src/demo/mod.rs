@@ -5,11 +5,16 @@
 
 use bevy::prelude::*;
 
+pub mod bot;
+pub mod combo;
+pub mod dynamite;
 pub mod entity;
 pub mod explosive;
 pub mod fx;
+pub mod generator;
 pub mod hook;
 pub mod level;
+pub mod payout;
 pub mod player;
 
 pub(super) fn plugin(app: &mut App) {
@@ -20,5 +25,9 @@ pub(super) fn plugin(app: &mut App) {
         entity::plugin,
         fx::plugin,
         explosive::plugin,
+        payout::plugin,
+        dynamite::plugin,
+        combo::plugin,
+        bot::plugin,
     ));
 }
@@ -0,0 +1,452 @@
+//! 抓取结算管线
+//!
+//! 将抓取物品的奖励计算拆成一组有序的结算修饰器：
+//! - 每个修饰器读取实体描述、标签与玩家状态，变换奖励金额
+//! - 修饰器可以附带副作用 (如获得炸药、增强力量)
+//! - 每一步变换都会记录到明细中，用于向玩家展示
+//!
+//! 新的商店道具、关卡修饰或难度规则通过 [`AddPayoutModifier`] 注册即可加入结算。
+
+use bevy::prelude::*;
+use rand::{Rng, RngCore};
+
+use crate::config::{DifficultyScaling, EntityDescriptor};
+use crate::demo::player::PlayerResource;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PayoutPipeline>();
+
+    // 内置规则，按注册顺序依次生效
+    app.add_payout_modifier(RockCollectorsBook)
+        .add_payout_modifier(GemPolish)
+        .add_payout_modifier(LuckyClover)
+        .add_payout_modifier(DifficultyLuck)
+        .add_payout_modifier(ExtraEffectRoll);
+}
+
+/// 结算上下文：修饰器可以读取的信息
+pub struct PayoutContext<'a> {
+    /// 实体 ID (例如 "MiniGold")
+    pub entity_id: &'a str,
+    /// 实体描述符
+    pub descriptor: &'a EntityDescriptor,
+    /// 玩家状态
+    pub player: &'a PlayerResource,
+    /// 本局难度的缩放系数
+    pub scaling: &'a DifficultyScaling,
+}
+
+impl PayoutContext<'_> {
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.descriptor.has_tag(tag)
+    }
+}
+
+/// 结算副作用：由结算结果统一应用
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayoutEffect {
+    /// 增加炸药
+    AddDynamite,
+    /// 增强玩家力量
+    Strengthen,
+}
+
+/// 结算明细中的一步
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoutStep {
+    /// 展示给玩家的说明 (例如 "Rock Book x3")
+    pub label: String,
+    /// 该步之后的奖励金额
+    pub bonus: i32,
+}
+
+/// 结算结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Payout {
+    /// 最终奖励金额
+    pub bonus: i32,
+    /// 触发额外效果的概率，修饰器可以调整
+    pub effect_chance: f32,
+    /// 需要应用的副作用
+    pub effects: Vec<PayoutEffect>,
+    /// 每一步变换的明细
+    pub breakdown: Vec<PayoutStep>,
+}
+
+impl Payout {
+    /// 以描述符中的基础数值创建结算
+    pub fn new(descriptor: &EntityDescriptor) -> Self {
+        Self {
+            bonus: descriptor.bonus.unwrap_or(0),
+            effect_chance: descriptor.extra_effect_chances.unwrap_or(0.0),
+            effects: Vec::new(),
+            breakdown: Vec::new(),
+        }
+    }
+
+    /// 设置新的奖励金额并记录到明细
+    pub fn apply(&mut self, label: impl Into<String>, bonus: i32) {
+        self.bonus = bonus;
+        self.breakdown.push(PayoutStep {
+            label: label.into(),
+            bonus,
+        });
+    }
+
+    /// 合并另一部分的结算 (例如移动实体携带的物品)
+    pub fn merge(&mut self, other: Payout) {
+        self.bonus += other.bonus;
+        self.effects.extend(other.effects);
+        self.breakdown.extend(other.breakdown);
+    }
+
+    /// 是否触发了副作用 (触发时不发放金钱)
+    pub fn has_effects(&self) -> bool {
+        !self.effects.is_empty()
+    }
+}
+
+/// 结算修饰器
+pub trait PayoutModifier: Send + Sync + 'static {
+    /// 修饰器名称，用于日志与调试
+    fn name(&self) -> &'static str;
+
+    /// 根据上下文变换结算结果，随机判定使用传入的随机数
+    fn apply(&self, ctx: &PayoutContext, payout: &mut Payout, rng: &mut dyn RngCore);
+}
+
+/// 结算管线：按注册顺序保存所有修饰器
+#[derive(Resource, Default)]
+pub struct PayoutPipeline {
+    modifiers: Vec<Box<dyn PayoutModifier>>,
+}
+
+impl PayoutPipeline {
+    /// 只包含内置规则的管线，供不启动游戏的模拟使用
+    pub fn builtin() -> Self {
+        let mut app = App::new();
+        plugin(&mut app);
+        app.world_mut()
+            .remove_resource::<PayoutPipeline>()
+            .unwrap_or_default()
+    }
+
+    pub fn push(&mut self, modifier: impl PayoutModifier) {
+        self.modifiers.push(Box::new(modifier));
+    }
+
+    /// 依次执行所有修饰器，得到最终结算
+    pub fn evaluate(&self, ctx: &PayoutContext, rng: &mut dyn RngCore) -> Payout {
+        let mut payout = Payout::new(ctx.descriptor);
+        for modifier in &self.modifiers {
+            modifier.apply(ctx, &mut payout, rng);
+        }
+        debug!("Payout for {}: {}", ctx.entity_id, payout.bonus);
+        payout
+    }
+
+    /// 预期结算金额，供机器人与模拟器估算收益：
+    /// 随机物品取预期分值，额外效果不触发，再按触发概率折算 (触发时不发放金钱)
+    pub fn expected_payout(&self, ctx: &PayoutContext) -> f32 {
+        let mut payout = Payout {
+            bonus: ctx.descriptor.expected_bonus(),
+            ..Payout::new(ctx.descriptor)
+        };
+        for modifier in &self.modifiers {
+            modifier.apply(ctx, &mut payout, &mut NeverRoll);
+        }
+        payout.bonus as f32 * (1.0 - payout.effect_chance.clamp(0.0, 1.0))
+    }
+}
+
+/// 随机判定总是取最大值的随机数，概率小于 1 的判定都不会触发
+struct NeverRoll;
+
+impl RngCore for NeverRoll {
+    fn next_u32(&mut self) -> u32 {
+        u32::MAX
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        u64::MAX
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        dst.fill(u8::MAX);
+    }
+}
+
+pub trait AddPayoutModifier {
+    /// 在结算管线末尾注册一个修饰器
+    fn add_payout_modifier(&mut self, modifier: impl PayoutModifier) -> &mut Self;
+}
+
+impl AddPayoutModifier for App {
+    fn add_payout_modifier(&mut self, modifier: impl PayoutModifier) -> &mut Self {
+        self.init_resource::<PayoutPipeline>();
+        debug!("Registering payout modifier {}", modifier.name());
+        self.world_mut()
+            .resource_mut::<PayoutPipeline>()
+            .push(modifier);
+        self
+    }
+}
+
+// --- 内置修饰器 ---
+
+/// 石头收藏书：岩石价值 ×3
+struct RockCollectorsBook;
+
+impl PayoutModifier for RockCollectorsBook {
+    fn name(&self) -> &'static str {
+        "RockCollectorsBook"
+    }
+
+    fn apply(&self, ctx: &PayoutContext, payout: &mut Payout, _rng: &mut dyn RngCore) {
+        if ctx.player.has_rock_collectors_book && ctx.has_tag("rock") {
+            payout.apply("Rock Book x3", payout.bonus * 3);
+        }
+    }
+}
+
+/// 宝石抛光剂：钻石价值 ×1.5
+struct GemPolish;
+
+impl PayoutModifier for GemPolish {
+    fn name(&self) -> &'static str {
+        "GemPolish"
+    }
+
+    fn apply(&self, ctx: &PayoutContext, payout: &mut Payout, _rng: &mut dyn RngCore) {
+        if !ctx.player.has_gem_polish {
+            return;
+        }
+
+        if ctx.has_tag("gem") {
+            payout.apply("Gem Polish x1.5", (payout.bonus as f32 * 1.5) as i32);
+        }
+    }
+}
+
+/// 幸运草：翻倍额外效果概率
+struct LuckyClover;
+
+impl PayoutModifier for LuckyClover {
+    fn name(&self) -> &'static str {
+        "LuckyClover"
+    }
+
+    fn apply(&self, ctx: &PayoutContext, payout: &mut Payout, _rng: &mut dyn RngCore) {
+        if ctx.player.has_lucky_clover {
+            payout.effect_chance *= 2.0;
+        }
+    }
+}
+
+/// 难度运气：按难度缩放福袋等物品的额外效果概率
+struct DifficultyLuck;
+
+impl PayoutModifier for DifficultyLuck {
+    fn name(&self) -> &'static str {
+        "DifficultyLuck"
+    }
+
+    fn apply(&self, ctx: &PayoutContext, payout: &mut Payout, _rng: &mut dyn RngCore) {
+        payout.effect_chance = (payout.effect_chance * ctx.scaling.bag_luck).min(1.0);
+    }
+}
+
+/// 额外效果判定 (对齐 Lua)：触发时不发放金钱，
+/// 20% 概率增加炸药，80% 概率增加玩家力量
+struct ExtraEffectRoll;
+
+impl PayoutModifier for ExtraEffectRoll {
+    fn name(&self) -> &'static str {
+        "ExtraEffectRoll"
+    }
+
+    fn apply(&self, _ctx: &PayoutContext, payout: &mut Payout, rng: &mut dyn RngCore) {
+        if payout.effect_chance <= 0.0 || rng.random::<f32>() >= payout.effect_chance {
+            return;
+        }
+
+        if rng.random::<f32>() < 0.2 {
+            payout.effects.push(PayoutEffect::AddDynamite);
+        } else {
+            payout.effects.push(PayoutEffect::Strengthen);
+        }
+        payout.bonus = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::EntityType;
+
+    fn descriptor(bonus: i32, tags: &[&str]) -> EntityDescriptor {
+        EntityDescriptor {
+            bonus: Some(bonus),
+            ..EntityDescriptor::with_tags(tags)
+        }
+    }
+
+    fn pipeline() -> PayoutPipeline {
+        let mut pipeline = PayoutPipeline::default();
+        pipeline.push(RockCollectorsBook);
+        pipeline.push(GemPolish);
+        pipeline
+    }
+
+    #[test]
+    fn non_matching_tags_keep_base_bonus() {
+        let descriptor = descriptor(100, &["gold"]);
+        let player = PlayerResource {
+            has_rock_collectors_book: true,
+            has_gem_polish: true,
+            ..default()
+        };
+        let ctx = PayoutContext {
+            entity_id: "NormalGold",
+            descriptor: &descriptor,
+            player: &player,
+            scaling: &DifficultyScaling::default(),
+        };
+
+        let payout = pipeline().evaluate(&ctx, &mut rand::rng());
+
+        assert_eq!(payout.bonus, 100);
+        assert!(payout.breakdown.is_empty());
+    }
+
+    #[test]
+    fn modifiers_match_tags_and_record_each_step() {
+        let descriptor = descriptor(20, &["rock"]);
+        let player = PlayerResource {
+            has_rock_collectors_book: true,
+            ..default()
+        };
+        let ctx = PayoutContext {
+            entity_id: "NormalRock",
+            descriptor: &descriptor,
+            player: &player,
+            scaling: &DifficultyScaling::default(),
+        };
+
+        let payout = pipeline().evaluate(&ctx, &mut rand::rng());
+
+        assert_eq!(payout.bonus, 60);
+        assert_eq!(
+            payout.breakdown,
+            vec![PayoutStep {
+                label: "Rock Book x3".to_string(),
+                bonus: 60,
+            }]
+        );
+    }
+
+    #[test]
+    fn carried_parts_apply_modifiers_separately() {
+        let mole = descriptor(2, &["creature"]);
+        let diamond = descriptor(600, &["gem"]);
+        let player = PlayerResource {
+            has_gem_polish: true,
+            ..default()
+        };
+        let pipeline = pipeline();
+
+        let mut payout = pipeline.evaluate(
+            &PayoutContext {
+                entity_id: "Mole",
+                descriptor: &mole,
+                player: &player,
+                scaling: &DifficultyScaling::default(),
+            },
+            &mut rand::rng(),
+        );
+        payout.merge(pipeline.evaluate(
+            &PayoutContext {
+                entity_id: "Diamond",
+                descriptor: &diamond,
+                player: &player,
+                scaling: &DifficultyScaling::default(),
+            },
+            &mut rand::rng(),
+        ));
+
+        assert_eq!(payout.bonus, 902);
+        assert_eq!(payout.breakdown.len(), 1);
+    }
+
+    #[test]
+    fn gem_polish_only_applies_when_owned() {
+        let descriptor = descriptor(600, &["gem"]);
+        let mut player = PlayerResource::default();
+        let pipeline = pipeline();
+
+        let plain = pipeline.evaluate(
+            &PayoutContext {
+                entity_id: "Diamond",
+                descriptor: &descriptor,
+                player: &player,
+                scaling: &DifficultyScaling::default(),
+            },
+            &mut rand::rng(),
+        );
+        assert_eq!(plain.bonus, 600);
+
+        player.has_gem_polish = true;
+        let polished = pipeline.evaluate(
+            &PayoutContext {
+                entity_id: "Diamond",
+                descriptor: &descriptor,
+                player: &player,
+                scaling: &DifficultyScaling::default(),
+            },
+            &mut rand::rng(),
+        );
+        assert_eq!(polished.bonus, 900);
+    }
+
+    #[test]
+    fn expected_payout_discounts_extra_effect_chance() {
+        let mut descriptor = descriptor(0, &["bag"]);
+        descriptor.entity_type = EntityType::RandomEffect;
+        descriptor.bonus_base = Some(100);
+        descriptor.extra_effect_chances = Some(0.25);
+        let ctx = PayoutContext {
+            entity_id: "QuestionBag",
+            descriptor: &descriptor,
+            player: &PlayerResource::default(),
+            scaling: &DifficultyScaling::default(),
+        };
+
+        let expected = PayoutPipeline::builtin().expected_payout(&ctx);
+
+        assert!((expected - 75.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn difficulty_luck_scales_effect_chance() {
+        let mut descriptor = descriptor(0, &["bag"]);
+        descriptor.extra_effect_chances = Some(0.2);
+        let player = PlayerResource::default();
+        let mut pipeline = PayoutPipeline::default();
+        pipeline.push(DifficultyLuck);
+
+        let easy = pipeline.evaluate(
+            &PayoutContext {
+                entity_id: "QuestionBag",
+                descriptor: &descriptor,
+                player: &player,
+                scaling: &DifficultyScaling {
+                    bag_luck: 1.5,
+                    ..default()
+                },
+            },
+            &mut rand::rng(),
+        );
+
+        assert!((easy.effect_chance - 0.3).abs() < f32::EPSILON);
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::time::Duration;
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 
@@ -9,6 +10,9 @@ use crate::AppSystems;
 use crate::asset_tracking::LoadResource;
 use crate::demo::hook::Hook;
 use crate::screens::Screen;
+use crate::screens::coop::CoopState;
+use crate::screens::stats::LevelStats;
+use crate::screens::versus::VersusState;
 use crate::utils::love_to_bevy_coords;
 
 pub(super) fn plugin(app: &mut App) {
@@ -32,24 +36,161 @@ pub(super) fn plugin(app: &mut App) {
 /// 更新玩家扔炸药的状态计时器
 fn update_dynamite_status(
     time: Res<Time>,
-    mut player: ResMut<PlayerResource>,
-    q_hook: Query<&Hook>,
-    mut q_player_anim: Query<&mut PlayerAnimation>,
+    mut inventories: Inventories,
+    q_hook: Query<(&Hook, &PlayerSlot)>,
+    mut q_player_anim: Query<(&mut PlayerAnimation, &PlayerSlot)>,
 ) {
-    if player.is_using_dynamite {
+    for slot in inventories.slots() {
+        let player = inventories.get_mut(slot);
+        if !player.is_using_dynamite {
+            continue;
+        }
+
         player.using_dynamite_timer -= time.delta_secs();
         if player.using_dynamite_timer <= 0.0 {
             player.is_using_dynamite = false;
             player.using_dynamite_timer = 0.39;
-            let next_state = if q_hook.iter().any(|hook| hook.show_strength) {
+            let next_state = if q_hook
+                .iter()
+                .any(|(hook, hook_slot)| *hook_slot == slot && hook.show_strength)
+            {
                 PlayerAnimationState::Strengthen
             } else {
                 PlayerAnimationState::Idle
             };
 
-            for mut anim in &mut q_player_anim {
-                anim.update_state(next_state);
-            }
+            set_animation(&mut q_player_anim, slot, next_state);
+        }
+    }
+}
+
+/// 玩家槽位：单人模式只有 1P，双人模式中 1P 在左、2P 在右
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PlayerSlot(pub usize);
+
+impl PlayerSlot {
+    pub fn label(&self) -> &'static str {
+        match self.0 {
+            0 => "1P",
+            _ => "2P",
+        }
+    }
+
+    /// 矿工与钩子相对单人位置的水平偏移
+    pub fn offset(&self, two_player: bool) -> f32 {
+        match (two_player, self.0) {
+            (false, _) => 0.0,
+            (true, 0) => -VERSUS_SPACING,
+            (true, _) => VERSUS_SPACING,
+        }
+    }
+}
+
+/// 双人模式中两名矿工与单人位置的水平距离
+const VERSUS_SPACING: f32 = 50.0;
+
+/// 各玩家的道具栏与金钱：单人模式为全局 `PlayerResource` 与 `LevelStats`，
+/// 对战模式为 `VersusState` 中各自的记录；合作模式道具栏各自独立，
+/// 金钱计入 `LevelStats`，炸药共用全局 `PlayerResource` 中的库存
+#[derive(SystemParam)]
+pub struct Inventories<'w> {
+    player: ResMut<'w, PlayerResource>,
+    versus: Option<ResMut<'w, VersusState>>,
+    coop: Option<ResMut<'w, CoopState>>,
+}
+
+impl Inventories<'_> {
+    pub fn is_versus(&self) -> bool {
+        self.versus.is_some()
+    }
+
+    /// 对战或合作：两名玩家同时在场
+    pub fn is_two_player(&self) -> bool {
+        self.versus.is_some() || self.coop.is_some()
+    }
+
+    /// 当前参与的玩家槽位
+    pub fn slots(&self) -> impl Iterator<Item = PlayerSlot> + use<> {
+        let count = if self.is_two_player() { 2 } else { 1 };
+        (0..count).map(PlayerSlot)
+    }
+
+    pub fn get(&self, slot: PlayerSlot) -> &PlayerResource {
+        if let Some(versus) = &self.versus {
+            return &versus.player(slot).inventory;
+        }
+        if let Some(coop) = &self.coop {
+            return &coop.player(slot).inventory;
+        }
+        &self.player
+    }
+
+    pub fn get_mut(&mut self, slot: PlayerSlot) -> &mut PlayerResource {
+        if let Some(versus) = &mut self.versus {
+            return &mut versus.player_mut(slot).inventory;
+        }
+        if let Some(coop) = &mut self.coop {
+            return &mut coop.player_mut(slot).inventory;
+        }
+        &mut self.player
+    }
+
+    /// 炸药库存，合作模式两人共用
+    pub fn dynamite(&self, slot: PlayerSlot) -> i32 {
+        match &self.coop {
+            Some(_) => self.player.dynamite_count,
+            None => self.get(slot).dynamite_count,
+        }
+    }
+
+    pub fn dynamite_mut(&mut self, slot: PlayerSlot) -> &mut i32 {
+        if self.coop.is_some() {
+            return &mut self.player.dynamite_count;
+        }
+        &mut self.get_mut(slot).dynamite_count
+    }
+
+    /// 增加一根炸药，上限 12
+    pub fn add_dynamite(&mut self, slot: PlayerSlot) {
+        let count = self.dynamite_mut(slot);
+        *count = (*count + 1).min(12);
+    }
+
+    /// 扔出一根炸药并进入扔炸药状态，没有炸药或正在扔炸药时返回 false
+    pub fn use_dynamite(&mut self, slot: PlayerSlot) -> bool {
+        if self.dynamite(slot) <= 0 || self.get(slot).is_using_dynamite {
+            return false;
+        }
+        *self.dynamite_mut(slot) -= 1;
+        let player = self.get_mut(slot);
+        player.is_using_dynamite = true;
+        player.using_dynamite_timer = 0.39;
+        true
+    }
+
+    /// 指定玩家的金钱：对战模式为各自的记录，其他模式为 `LevelStats` 中的金钱
+    pub fn money_mut<'a>(&'a mut self, slot: PlayerSlot, stats: &'a mut LevelStats) -> &'a mut u32 {
+        match &mut self.versus {
+            Some(versus) => &mut versus.player_mut(slot).money,
+            None => &mut stats.money,
+        }
+    }
+
+    /// 结算奖励金额
+    pub fn award(&mut self, slot: PlayerSlot, stats: &mut LevelStats, amount: u32) {
+        *self.money_mut(slot, stats) += amount;
+    }
+}
+
+/// 切换指定玩家矿工的动画
+pub fn set_animation(
+    q_player_anim: &mut Query<(&mut PlayerAnimation, &PlayerSlot)>,
+    slot: PlayerSlot,
+    state: PlayerAnimationState,
+) {
+    for (mut anim, anim_slot) in q_player_anim.iter_mut() {
+        if *anim_slot == slot {
+            anim.update_state(state);
         }
     }
 }
@@ -63,28 +204,38 @@ fn spawn_player(
     mut commands: Commands,
     player_assets: Res<PlayerAssets>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    inventories: Inventories,
 ) {
     commands.init_resource::<PlayerResource>();
     // miner_sheet.png 布局: 8 帧横排，每帧 32x40 像素
     let layout = TextureAtlasLayout::from_grid(UVec2::new(32, 40), 8, 1, None, None);
     let texture_atlas_layout = texture_atlas_layouts.add(layout);
-    let player_animation = PlayerAnimation::new();
 
-    commands.spawn((
-        Name::new("Player"),
-        PlayerMarker,
-        player_animation,
-        Sprite::from_atlas_image(
+    for slot in inventories.slots() {
+        let mut sprite = Sprite::from_atlas_image(
             player_assets.miner.clone(),
             TextureAtlas {
-                layout: texture_atlas_layout,
+                layout: texture_atlas_layout.clone(),
                 index: 0,
             },
-        ),
-        Transform::from_translation(love_to_bevy_coords(165.0, 39.0).extend(0.0)),
-        Anchor::BOTTOM_CENTER,
-        DespawnOnExit(Screen::Gameplay),
-    ));
+        );
+        // 2P 矿工略带蓝色以便区分
+        if slot.0 > 0 {
+            sprite.color = Color::srgb(0.75, 0.85, 1.0);
+        }
+
+        let x = 165.0 + slot.offset(inventories.is_two_player());
+        commands.spawn((
+            Name::new(format!("Player {}", slot.label())),
+            PlayerMarker,
+            slot,
+            PlayerAnimation::new(),
+            sprite,
+            Transform::from_translation(love_to_bevy_coords(x, 39.0).extend(0.0)),
+            Anchor::BOTTOM_CENTER,
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
 }
 
 #[derive(Component)]
@@ -116,6 +267,8 @@ pub struct PlayerResource {
     pub has_rock_collectors_book: bool,
     /// 是否持有宝石抛光剂
     pub has_gem_polish: bool,
+    /// 是否持有秒表 (下一关开始时增加时间)
+    pub has_stopwatch: bool,
 
     /// 标识玩家是否正在扔炸药
     pub is_using_dynamite: bool,
@@ -137,18 +290,34 @@ impl Default for PlayerResource {
             has_lucky_clover: false,
             has_rock_collectors_book: false,
             has_gem_polish: false,
+            has_stopwatch: false,
             is_using_dynamite: false,
             using_dynamite_timer: 0.39,
         }
     }
 }
 
-fn reset_level_effects(mut player: ResMut<PlayerResource>) {
-    player.strength = 1.0;
-    player.has_strength_drink = false;
-    player.has_lucky_clover = false;
-    player.has_rock_collectors_book = false;
-    player.has_gem_polish = false;
+impl PlayerResource {
+    /// 福袋的力量效果 (Lua: strength = min(6, strength * 1.5 + 1))
+    pub fn strengthen(&mut self) {
+        self.strength = (self.strength * 1.5 + 1.0).min(6.0);
+    }
+
+    /// 关卡结束时清除只在一关内有效的力量与道具
+    pub fn reset_level_effects(&mut self) {
+        self.strength = 1.0;
+        self.has_strength_drink = false;
+        self.has_lucky_clover = false;
+        self.has_rock_collectors_book = false;
+        self.has_gem_polish = false;
+        self.has_stopwatch = false;
+    }
+}
+
+fn reset_level_effects(mut inventories: Inventories) {
+    for slot in inventories.slots() {
+        inventories.get_mut(slot).reset_level_effects();
+    }
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
@@ -1,31 +0,0 @@
-//! Development tools for the game. This plugin is only enabled in dev builds.
-
-use bevy::{
-    dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*,
-};
-
-use crate::screens::{Screen, persistent::PersistentData};
-
-pub(super) fn plugin(app: &mut App) {
-    app.add_systems(Update, log_transitions::<Screen>);
-
-    app.add_systems(
-        Update,
-        toggle_debug_ui.run_if(input_just_pressed(TOGGLE_KEY)),
-    );
-
-    app.add_systems(
-        Update,
-        reset_high_score.run_if(input_just_pressed(KeyCode::KeyC)),
-    );
-}
-
-const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
-
-fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
-    options.toggle();
-}
-
-fn reset_high_score(mut persistent: ResMut<PersistentData>) {
-    *persistent = PersistentData::reset();
-}
@@ -0,0 +1,614 @@
+//! 开发者作弊控制台 (仅开发版本)
+//!
+//! 按 F1 打开或关闭，打开时暂停游戏时间，输入不会传给游戏：
+//! - Enter 执行命令，↑/↓ 浏览历史命令，Tab 补全命令名、实体 ID (`EntitiesConfig`)、道具名与关卡 ID
+//! - 命令见 `COMMANDS`，例如 `money +500`、`spawn Diamond 160 120`、`give gem_polish`
+//! - 金钱、炸药、力量与道具命令默认作用于 1P，双人模式中末尾加 `2p` 作用于 2P
+
+use std::mem;
+use std::str::FromStr;
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::{ButtonState, InputSystems};
+use bevy::prelude::*;
+
+use crate::config::{EntitiesConfig, GENERATED_LEVEL_PREFIX, LevelsConfig};
+use crate::constants::COLOR_YELLOW;
+use crate::demo::entity::spawned_entity_bundle;
+use crate::demo::hook::{HookStats, HookUpgrade};
+use crate::demo::level::{EntityHandle, LevelHandle};
+use crate::demo::player::{Inventories, PlayerSlot};
+use crate::screens::Screen;
+use crate::screens::gameplay::level_end_screen;
+use crate::screens::shop::PropType;
+use crate::screens::stats::LevelStats;
+use crate::utils::love_to_bevy_coords;
+
+const CONSOLE_KEY: KeyCode = KeyCode::F1;
+/// 控制台显示的输出行数
+const LOG_LINES: usize = 10;
+
+/// 命令名与用法
+const COMMANDS: [(&str, &str); 10] = [
+    ("money", "money <n|+n|-n> [2p] set or adjust money"),
+    ("dynamite", "dynamite <n> [2p]   set dynamite count"),
+    ("strength", "strength <x> [2p]   set strength"),
+    ("level", "level <id>          restart on a level layout"),
+    ("timer", "timer <seconds>     set remaining time"),
+    ("spawn", "spawn <id> <x> <y>  spawn an entity (LOVE coords)"),
+    ("give", "give <item> [2p]    apply a shop item"),
+    ("win", "win                 reach the goal and win"),
+    ("lose", "lose                fail the level"),
+    ("help", "help                list commands"),
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Console>();
+    app.add_systems(Startup, spawn_console_ui);
+    // 在输入系统之后读取按键，游戏系统看到的是清空后的按键状态
+    app.add_systems(
+        PreUpdate,
+        (toggle_console, read_console_input, run_console_commands)
+            .chain()
+            .after(InputSystems),
+    );
+    app.add_systems(Update, update_console_ui);
+}
+
+#[derive(Resource, Debug, Default)]
+struct Console {
+    open: bool,
+    input: String,
+    log: Vec<String>,
+    history: Vec<String>,
+    /// 正在浏览的历史命令
+    cursor: Option<usize>,
+    /// 本帧提交、等待执行的命令
+    pending: Vec<String>,
+}
+
+impl Console {
+    fn print(&mut self, line: impl Into<String>) {
+        self.log.push(line.into());
+        let excess = self.log.len().saturating_sub(LOG_LINES);
+        self.log.drain(..excess);
+    }
+
+    /// 提交当前输入，记入历史 (与上一条相同时不重复记录)
+    fn submit(&mut self) {
+        let line = mem::take(&mut self.input).trim().to_string();
+        self.cursor = None;
+        if line.is_empty() {
+            return;
+        }
+        if self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+        }
+        self.print(format!("> {line}"));
+        self.pending.push(line);
+    }
+
+    fn history_prev(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let cursor = self
+            .cursor
+            .map_or(self.history.len() - 1, |cursor| cursor.saturating_sub(1));
+        self.cursor = Some(cursor);
+        self.input = self.history[cursor].clone();
+    }
+
+    fn history_next(&mut self) {
+        match self.cursor {
+            Some(cursor) if cursor + 1 < self.history.len() => {
+                self.cursor = Some(cursor + 1);
+                self.input = self.history[cursor + 1].clone();
+            }
+            _ => {
+                self.cursor = None;
+                self.input.clear();
+            }
+        }
+    }
+}
+
+/// `player` 为命令作用的玩家 (`1p` 或 `2p`，默认 1P)
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    /// `relative` 为 true 时在当前金钱上增减
+    Money {
+        amount: i64,
+        relative: bool,
+        player: PlayerSlot,
+    },
+    Dynamite {
+        count: i32,
+        player: PlayerSlot,
+    },
+    Strength {
+        strength: f32,
+        player: PlayerSlot,
+    },
+    Level(String),
+    Timer(f32),
+    Spawn {
+        entity_id: String,
+        pos: Vec2,
+    },
+    Give {
+        prop: PropType,
+        player: PlayerSlot,
+    },
+    Win,
+    Lose,
+    Help,
+}
+
+fn usage(name: &str) -> &'static str {
+    COMMANDS
+        .iter()
+        .find(|(command, _)| *command == name)
+        .map_or("", |(_, usage)| usage)
+}
+
+fn parse_number<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .trim_start_matches('+')
+        .parse()
+        .map_err(|_| format!("invalid number {value}"))
+}
+
+/// `give` 可用的全部道具
+fn items() -> impl Iterator<Item = PropType> {
+    [
+        PropType::Dynamite,
+        PropType::StrengthDrink,
+        PropType::LuckyClover,
+        PropType::RockCollectorsBook,
+        PropType::GemPolish,
+        PropType::Stopwatch,
+    ]
+    .into_iter()
+    .chain(HookUpgrade::ALL.map(PropType::HookUpgrade))
+}
+
+/// 道具名比较时忽略大小写与下划线 (`gem_polish` 与 `GemPolish` 相同)
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_')
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// `GemPolish` → `gem_polish`
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+fn parse_command(line: &str) -> Result<Command, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let Some((name, args)) = words.split_first() else {
+        return Err("empty command".to_string());
+    };
+    let name = name.to_ascii_lowercase();
+    let arg = |index: usize| {
+        args.get(index)
+            .copied()
+            .ok_or_else(|| format!("usage: {}", usage(&name)))
+    };
+    // 可选的玩家参数
+    let player = |index: usize| match args.get(index).map(|word| word.to_ascii_lowercase()) {
+        None => Ok(PlayerSlot(0)),
+        Some(word) if word == "1p" => Ok(PlayerSlot(0)),
+        Some(word) if word == "2p" => Ok(PlayerSlot(1)),
+        Some(word) => Err(format!("unknown player {word} (1p or 2p)")),
+    };
+
+    Ok(match name.as_str() {
+        "money" => {
+            let value = arg(0)?;
+            Command::Money {
+                amount: parse_number(value)?,
+                relative: value.starts_with(['+', '-']),
+                player: player(1)?,
+            }
+        }
+        "dynamite" => Command::Dynamite {
+            count: parse_number(arg(0)?)?,
+            player: player(1)?,
+        },
+        "strength" => Command::Strength {
+            strength: parse_number(arg(0)?)?,
+            player: player(1)?,
+        },
+        "level" => Command::Level(arg(0)?.to_string()),
+        "timer" => Command::Timer(parse_number(arg(0)?)?),
+        "spawn" => Command::Spawn {
+            entity_id: arg(0)?.to_string(),
+            pos: Vec2::new(parse_number(arg(1)?)?, parse_number(arg(2)?)?),
+        },
+        "give" => {
+            let item = arg(0)?;
+            Command::Give {
+                prop: items()
+                    .find(|prop| normalize(prop.name()) == normalize(item))
+                    .ok_or_else(|| format!("unknown item {item}"))?,
+                player: player(1)?,
+            }
+        }
+        "win" => Command::Win,
+        "lose" => Command::Lose,
+        "help" => Command::Help,
+        _ => return Err(format!("unknown command {name} (try help)")),
+    })
+}
+
+/// 补全最后一个词，返回补全后的输入与所有候选
+///
+/// 第一个词补全命令名；`spawn` 之后补全实体 ID，`give` 之后补全道具名，`level` 之后补全关卡 ID。
+/// 多个候选时补全到公共前缀。
+fn complete(line: &str, entity_ids: &[&str], level_ids: &[&str]) -> (String, Vec<String>) {
+    let mut words: Vec<&str> = line.split_whitespace().collect();
+    let word = if line.is_empty() || line.ends_with(' ') {
+        ""
+    } else {
+        words.pop().unwrap_or_default()
+    };
+
+    let candidates: Vec<String> = match words.as_slice() {
+        [] => COMMANDS.iter().map(|(name, _)| name.to_string()).collect(),
+        [command] => match command.to_ascii_lowercase().as_str() {
+            "spawn" => entity_ids.iter().map(|id| id.to_string()).collect(),
+            "give" => items().map(|prop| snake_case(prop.name())).collect(),
+            "level" => level_ids.iter().map(|id| id.to_string()).collect(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+    let lower = word.to_ascii_lowercase();
+    let mut matches: Vec<String> = candidates
+        .into_iter()
+        .filter(|candidate| candidate.to_ascii_lowercase().starts_with(&lower))
+        .collect();
+    matches.sort();
+
+    let completed_word = match matches.as_slice() {
+        [] => return (line.to_string(), matches),
+        [only] => format!("{only} "),
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.clone(), |common, candidate| {
+                common
+                    .chars()
+                    .zip(candidate.chars())
+                    .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+                    .map(|(a, _)| a)
+                    .collect()
+            });
+            if common.len() >= word.len() {
+                common
+            } else {
+                word.to_string()
+            }
+        }
+    };
+    words.push(&completed_word);
+    (words.join(" "), matches)
+}
+
+#[derive(Component)]
+struct ConsoleRoot;
+
+#[derive(Component)]
+struct ConsoleText;
+
+fn spawn_console_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Name::new("Dev Console"),
+        ConsoleRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            left: px(0),
+            right: px(0),
+            bottom: px(0),
+            padding: UiRect::all(px(8)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        GlobalZIndex(100),
+        Visibility::Hidden,
+        children![(
+            ConsoleText,
+            Text::new(""),
+            TextFont {
+                font: asset_server.load("fonts/Pixel-Square-10-1.ttf"),
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor(COLOR_YELLOW),
+        )],
+    ));
+}
+
+fn toggle_console(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut console: ResMut<Console>,
+    mut time: ResMut<Time<Virtual>>,
+    mut q_root: Query<&mut Visibility, With<ConsoleRoot>>,
+) {
+    if !keys.just_pressed(CONSOLE_KEY) {
+        return;
+    }
+    console.open = !console.open;
+    if console.open {
+        time.pause();
+    } else {
+        time.unpause();
+    }
+    for mut visibility in &mut q_root {
+        *visibility = if console.open {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+fn read_console_input(
+    mut console: ResMut<Console>,
+    mut keyboard: MessageReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    entity_handle: Res<EntityHandle>,
+    entities: Res<Assets<EntitiesConfig>>,
+    level_handle: Res<LevelHandle>,
+    levels: Res<Assets<LevelsConfig>>,
+) {
+    if !console.open {
+        keyboard.clear();
+        return;
+    }
+
+    for event in keyboard.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => console.submit(),
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Escape => console.input.clear(),
+            Key::ArrowUp => console.history_prev(),
+            Key::ArrowDown => console.history_next(),
+            Key::Space => console.input.push(' '),
+            Key::Tab => {
+                let entity_ids: Vec<&str> = entity_handle
+                    .get(&entities)
+                    .map(|config| config.entities.keys().map(String::as_str).collect())
+                    .unwrap_or_default();
+                let level_ids: Vec<&str> = level_handle
+                    .get(&levels)
+                    .map(|config| config.levels.keys().map(String::as_str).collect())
+                    .unwrap_or_default();
+                let (line, matches) = complete(&console.input, &entity_ids, &level_ids);
+                if matches.len() > 1 && line == console.input {
+                    console.print(matches.join(" "));
+                }
+                console.input = line;
+            }
+            Key::Character(text) => {
+                let text: String = text.chars().filter(|c| !c.is_control()).collect();
+                console.input.push_str(&text);
+            }
+            _ => {}
+        }
+    }
+
+    // 控制台打开时游戏收不到按键
+    keys.reset_all();
+}
+
+fn run_console_commands(
+    mut commands: Commands,
+    mut console: ResMut<Console>,
+    mut stats: ResMut<LevelStats>,
+    mut inventories: Inventories,
+    mut hook_stats: ResMut<HookStats>,
+    screen: Res<State<Screen>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    entity_handle: Res<EntityHandle>,
+    entities: Res<Assets<EntitiesConfig>>,
+    level_handle: Res<LevelHandle>,
+    levels: Res<Assets<LevelsConfig>>,
+) {
+    let in_gameplay = *screen.get() == Screen::Gameplay;
+    for line in mem::take(&mut console.pending) {
+        let result = parse_command(&line).and_then(|command| {
+            let needs_gameplay = matches!(
+                command,
+                Command::Level(_) | Command::Spawn { .. } | Command::Win | Command::Lose
+            );
+            if needs_gameplay && !in_gameplay {
+                return Err("only available during gameplay".to_string());
+            }
+            let player = match command {
+                Command::Money { player, .. }
+                | Command::Dynamite { player, .. }
+                | Command::Strength { player, .. }
+                | Command::Give { player, .. } => player,
+                _ => PlayerSlot(0),
+            };
+            if !inventories.slots().any(|slot| slot == player) {
+                return Err(format!("{} is not playing", player.label()));
+            }
+
+            Ok(match command {
+                Command::Money {
+                    amount, relative, ..
+                } => {
+                    let money = inventories.money_mut(player, &mut stats);
+                    let base = if relative { *money as i64 } else { 0 };
+                    *money = (base + amount).clamp(0, u32::MAX as i64) as u32;
+                    format!("{} money = {}", player.label(), money)
+                }
+                Command::Dynamite { count, .. } => {
+                    *inventories.dynamite_mut(player) = count.max(0);
+                    format!(
+                        "{} dynamite = {}",
+                        player.label(),
+                        inventories.dynamite(player)
+                    )
+                }
+                Command::Strength { strength, .. } => {
+                    inventories.get_mut(player).strength = strength;
+                    format!("{} strength = {strength}", player.label())
+                }
+                Command::Level(id) => {
+                    let exists = id.starts_with(GENERATED_LEVEL_PREFIX)
+                        || level_handle
+                            .get(&levels)
+                            .is_some_and(|config| config.levels.contains_key(&id));
+                    if !exists {
+                        return Err(format!("unknown level {id}"));
+                    }
+                    stats.real_level_str = id.clone();
+                    next_screen.set(Screen::Gameplay);
+                    format!("loading level {id}")
+                }
+                Command::Timer(seconds) => {
+                    stats.timer = seconds.max(0.0);
+                    format!("timer = {:.1}", stats.timer)
+                }
+                Command::Spawn { entity_id, pos } => {
+                    let descriptor = entity_handle
+                        .get(&entities)
+                        .and_then(|config| config.entities.get(&entity_id))
+                        .ok_or_else(|| format!("unknown entity {entity_id}"))?;
+                    commands.spawn(spawned_entity_bundle(
+                        &entity_id,
+                        descriptor.clone(),
+                        love_to_bevy_coords(pos.x, pos.y),
+                    ));
+                    format!("spawned {entity_id} at ({}, {})", pos.x, pos.y)
+                }
+                Command::Give { prop, .. } => {
+                    // 与商店相同：合作模式中炸药放入共用库存
+                    if prop == PropType::Dynamite {
+                        inventories.add_dynamite(player);
+                    } else {
+                        prop.apply(inventories.get_mut(player), &mut hook_stats);
+                    }
+                    format!("gave {} to {}", prop.name(), player.label())
+                }
+                // 与时间耗尽时相同的界面路由 (练习只进行一关，对战显示回合比分)
+                Command::Win => {
+                    stats.money = stats.money.max(stats.goal);
+                    stats.timer = 0.0;
+                    next_screen.set(level_end_screen(&stats));
+                    "level won".to_string()
+                }
+                Command::Lose => {
+                    stats.timer = 0.0;
+                    next_screen.set(match level_end_screen(&stats) {
+                        Screen::MadeGoal => Screen::GameOver,
+                        screen => screen,
+                    });
+                    "level lost".to_string()
+                }
+                Command::Help => COMMANDS
+                    .iter()
+                    .map(|(_, usage)| *usage)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            })
+        });
+
+        match result {
+            Ok(output) => {
+                for line in output.lines() {
+                    console.print(line);
+                }
+            }
+            Err(err) => console.print(format!("error: {err}")),
+        }
+    }
+}
+
+fn update_console_ui(console: Res<Console>, mut q_text: Query<&mut Text, With<ConsoleText>>) {
+    if !console.is_changed() {
+        return;
+    }
+    for mut text in &mut q_text {
+        let mut content = console.log.join("\n");
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        content.push_str(&format!("> {}_", console.input));
+        text.0 = content;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cheat_commands() {
+        assert_eq!(
+            parse_command("money +500"),
+            Ok(Command::Money {
+                amount: 500,
+                relative: true,
+                player: PlayerSlot(0),
+            })
+        );
+        assert_eq!(
+            parse_command("money 80"),
+            Ok(Command::Money {
+                amount: 80,
+                relative: false,
+                player: PlayerSlot(0),
+            })
+        );
+        assert_eq!(
+            parse_command("spawn Diamond 160 120"),
+            Ok(Command::Spawn {
+                entity_id: "Diamond".to_string(),
+                pos: Vec2::new(160.0, 120.0),
+            })
+        );
+        assert_eq!(
+            parse_command("give gem_polish 2p"),
+            Ok(Command::Give {
+                prop: PropType::GemPolish,
+                player: PlayerSlot(1),
+            })
+        );
+        assert!(parse_command("dynamite").is_err());
+        assert!(parse_command("dynamite 3 3p").is_err());
+        assert!(parse_command("fly").is_err());
+    }
+
+    #[test]
+    fn completes_commands_entities_and_items() {
+        let entities = ["Diamond", "MiniGold", "NormalGold", "BigGold"];
+        assert_eq!(complete("sp", &entities, &[]).0, "spawn ");
+        assert_eq!(complete("spawn di", &entities, &[]).0, "spawn Diamond ");
+        // 多个候选补全到公共前缀
+        let (line, matches) = complete("spawn ", &entities, &[]);
+        assert_eq!((line.as_str(), matches.len()), ("spawn ", 4));
+        assert_eq!(complete("give gem", &entities, &[]).0, "give gem_polish ");
+        assert_eq!(complete("level L8", &[], &["L8_1", "L8_3"]).0, "level L8_");
+    }
+}
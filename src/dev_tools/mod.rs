@@ -0,0 +1,60 @@
+//! Development tools for the game. This plugin is only enabled in dev builds.
+
+mod console;
+
+use bevy::{
+    dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*,
+};
+
+use crate::demo::{generator::export_yaml, level::CurrentLevel};
+use crate::menus::Menu;
+use crate::screens::{Screen, persistent::PersistentData};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(console::plugin);
+    app.add_systems(Update, log_transitions::<Screen>);
+
+    app.add_systems(
+        Update,
+        toggle_debug_ui.run_if(input_just_pressed(TOGGLE_KEY)),
+    );
+
+    // 联网菜单中输入房间码时 C 是普通字母
+    app.add_systems(
+        Update,
+        reset_high_score.run_if(input_just_pressed(KeyCode::KeyC).and(not(in_state(Menu::Online)))),
+    );
+
+    app.add_systems(
+        Update,
+        export_current_level
+            .run_if(input_just_pressed(KeyCode::F2).and(in_state(Screen::Gameplay))),
+    );
+}
+
+const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+
+fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
+    options.toggle();
+}
+
+fn reset_high_score(mut persistent: ResMut<PersistentData>) {
+    *persistent = PersistentData::reset();
+}
+
+const EXPORT_FILE: &str = "exported_level.yaml";
+
+/// 将当前关卡 (包括生成关卡) 导出为 levels.yaml 格式
+fn export_current_level(current: Res<CurrentLevel>) {
+    let Some(descriptor) = &current.descriptor else {
+        return;
+    };
+
+    match export_yaml(&current.id, descriptor) {
+        Ok(content) => match std::fs::write(EXPORT_FILE, content) {
+            Ok(()) => info!("Exported level {} to {EXPORT_FILE}", current.id),
+            Err(e) => error!("Failed to write {EXPORT_FILE}: {e}"),
+        },
+        Err(e) => error!("Failed to export level {}: {e}", current.id),
+    }
+}
@@ -5,13 +5,16 @@
 
 mod asset_tracking;
 mod audio;
+mod balance;
 mod config;
 mod constants;
 mod demo;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod menus;
+mod online;
 mod screens;
+mod telemetry;
 mod theme;
 mod utils;
 
@@ -19,7 +22,22 @@ use crate::config::ConfigPlugin;
 use bevy::{asset::AssetMetaCheck, image::ImagePlugin, prelude::*};
 
 fn main() -> AppExit {
-    App::new().add_plugins(AppPlugin).run()
+    // 命令行工具，不启动窗口：
+    // - `goldminer simulate [选项]` 平衡模拟器
+    // - `goldminer telemetry [日志...]` 汇总遥测日志
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("simulate") => balance::run_cli(&args[1..]),
+        Some("telemetry") => telemetry::analyze::run_cli(&args[1..]),
+        _ => return App::new().add_plugins(AppPlugin).run(),
+    };
+    match result {
+        Ok(()) => AppExit::Success,
+        Err(err) => {
+            eprintln!("{}: {err:#}", args[0]);
+            AppExit::error()
+        }
+    }
 }
 
 pub struct AppPlugin;
@@ -58,7 +76,9 @@ impl Plugin for AppPlugin {
             #[cfg(feature = "dev")]
             dev_tools::plugin,
             menus::plugin,
+            online::plugin,
             screens::plugin,
+            telemetry::plugin,
             theme::plugin,
             ConfigPlugin,
         ));
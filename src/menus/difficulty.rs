@@ -0,0 +1,151 @@
+//! The difficulty menu.
+//!
+//! Shown after "Start Game"; the chosen preset scales the new run.
+
+use crate::constants::{COLOR_GREEN, COLOR_YELLOW};
+use crate::screens::stats::{Difficulty, SelectedDifficulty};
+use crate::utils::love_to_bevy_coords;
+use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+/// 第一项的 y 坐标与项间距 (LÖVE 坐标)
+const FIRST_ITEM_Y: f32 = 120.0;
+const ITEM_SPACING: f32 = 20.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Difficulty), spawn_difficulty_menu)
+        .add_systems(
+            Update,
+            (keyboard_input, update_menu_arrow)
+                .chain()
+                .run_if(in_state(Menu::Difficulty)),
+        );
+}
+
+#[derive(Component)]
+struct MenuArrow;
+
+fn spawn_difficulty_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    selected: Res<SelectedDifficulty>,
+) {
+    let font = asset_server.load("fonts/Kurland.ttf");
+    let style = TextFont {
+        font: font.clone(),
+        font_size: 20.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            widget::ui_root("Difficulty Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Difficulty),
+            Sprite::from_image(asset_server.load("images/bg_start_menu.png")),
+            children![(
+                Name::new("Menu Arrow"),
+                Sprite::from_image(asset_server.load("images/menu_arrow.png")),
+                Transform::from_translation(arrow_position(selected.0)),
+                Anchor::TOP_LEFT,
+                MenuArrow,
+            )],
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2d::new("Difficulty"),
+                style.clone(),
+                Transform::from_translation(love_to_bevy_coords(30.0, 95.0).extend(1.0)),
+                Anchor::TOP_LEFT,
+                TextColor(COLOR_GREEN),
+            ));
+
+            for (index, difficulty) in Difficulty::ALL.iter().enumerate() {
+                parent.spawn((
+                    Text2d::new(difficulty.label()),
+                    style.clone(),
+                    Transform::from_translation(
+                        love_to_bevy_coords(30.0, item_y(index)).extend(1.0),
+                    ),
+                    Anchor::TOP_LEFT,
+                    TextColor(COLOR_YELLOW),
+                ));
+            }
+        });
+}
+
+fn item_y(index: usize) -> f32 {
+    FIRST_ITEM_Y + index as f32 * ITEM_SPACING
+}
+
+fn arrow_position(difficulty: Difficulty) -> Vec3 {
+    let index = Difficulty::ALL
+        .iter()
+        .position(|d| *d == difficulty)
+        .unwrap_or(0);
+    love_to_bevy_coords(5.0, item_y(index) + 2.0).extend(1.0)
+}
+
+fn keyboard_input(
+    input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut selected: ResMut<SelectedDifficulty>,
+    mut next_menu: ResMut<NextState<Menu>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    resource_handles: Res<ResourceHandles>,
+) {
+    let mut up = input.just_pressed(KeyCode::ArrowUp);
+    let mut down = input.just_pressed(KeyCode::ArrowDown);
+    let mut confirm = input.just_pressed(KeyCode::Enter)
+        || input.just_pressed(KeyCode::NumpadEnter)
+        || input.just_pressed(KeyCode::KeyJ)
+        || input.just_pressed(KeyCode::KeyK);
+    let mut back = input.just_pressed(KeyCode::Backspace);
+
+    for gamepad in &gamepads {
+        if gamepad.just_pressed(GamepadButton::DPadUp) {
+            up = true;
+        }
+        if gamepad.just_pressed(GamepadButton::DPadDown) {
+            down = true;
+        }
+        if gamepad.just_pressed(GamepadButton::South) || gamepad.just_pressed(GamepadButton::Start)
+        {
+            confirm = true;
+        }
+        if gamepad.just_pressed(GamepadButton::East) {
+            back = true;
+        }
+    }
+
+    let count = Difficulty::ALL.len();
+    let index = Difficulty::ALL
+        .iter()
+        .position(|d| *d == selected.0)
+        .unwrap_or(0);
+    if up {
+        selected.0 = Difficulty::ALL[(index + count - 1) % count];
+    } else if down {
+        selected.0 = Difficulty::ALL[(index + 1) % count];
+    }
+
+    if back {
+        next_menu.set(Menu::Main);
+    } else if confirm {
+        if resource_handles.is_all_done() {
+            next_screen.set(Screen::NextGoal);
+        } else {
+            next_screen.set(Screen::Loading);
+        }
+    }
+}
+
+fn update_menu_arrow(
+    selected: Res<SelectedDifficulty>,
+    mut q_arrow: Single<&mut Transform, With<MenuArrow>>,
+) {
+    if selected.is_changed() {
+        q_arrow.translation = arrow_position(selected.0);
+    }
+}
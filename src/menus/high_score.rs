@@ -1,9 +1,10 @@
 //! The high score menu.
 //!
-//! Displays the highest score achieved by the player.
+//! Displays the highest score achieved by the player on each difficulty,
+//! and the endless, daily and time attack records on a second page (left/right to switch).
 
 use crate::constants::{COLOR_GREEN, COLOR_YELLOW};
-use crate::screens::persistent::PersistentData;
+use crate::screens::{persistent::PersistentData, stats::Difficulty, time_attack::format_time};
 use crate::utils::love_to_bevy_coords;
 use crate::{menus::Menu, theme::prelude::*};
 use bevy::prelude::*;
@@ -11,7 +12,10 @@ use bevy::sprite::Anchor;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::HighScore), spawn_high_score_menu);
-    app.add_systems(Update, go_back.run_if(in_state(Menu::HighScore)));
+    app.add_systems(
+        Update,
+        (switch_page, go_back).run_if(in_state(Menu::HighScore)),
+    );
 }
 
 fn spawn_high_score_menu(
@@ -40,53 +44,166 @@ fn title_area(asset_server: &AssetServer) -> impl Bundle {
     )
 }
 
+/// 记录列表第一行的位置、行距与各列的 x 坐标 (相对面板左上角)
+const FIRST_ROW_Y: f32 = -38.0;
+const ROW_SPACING: f32 = 18.0;
+const COLUMNS: [f32; 3] = [47.0, 127.0, 197.0];
+
+/// 记录分两页显示：各难度最高分与各模式记录，左右键切换
+#[derive(Component)]
+struct RecordPage(usize);
+
+const PAGE_COUNT: usize = 2;
+
 fn panel_area(asset_server: &AssetServer, persistent: &Res<PersistentData>) -> impl Bundle {
     let font = asset_server.load("fonts/Kurland.ttf");
     let style = TextFont {
         font: font.clone(),
-        font_size: 20.0,
+        font_size: 15.0,
         ..default()
     };
+
+    // 每个难度一行: "Normal" "$1200" "Lv4"
+    let difficulties = Difficulty::ALL
+        .iter()
+        .map(|difficulty| {
+            let record = persistent.best(*difficulty);
+            [
+                difficulty.label().to_string(),
+                format!("${}", record.score),
+                format!("Lv{}", record.level),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let modes = vec![
+        [
+            "Endless".to_string(),
+            format!("Depth {}", persistent.endless_best_depth),
+            String::new(),
+        ],
+        [
+            "Daily".to_string(),
+            format!("${}", persistent.daily.best_score),
+            format!("Streak {}", persistent.daily.streak),
+        ],
+        [
+            "Time".to_string(),
+            persistent
+                .time_attack
+                .best_total
+                .map(format_time)
+                .unwrap_or_else(|| "--".to_string()),
+            String::new(),
+        ],
+    ];
+
     (
         Name::new("panel"),
         Sprite::from_image(asset_server.load("images/panel.png")),
         Transform::from_translation(love_to_bevy_coords(160.0 - 133.0, 80.0).extend(1.0)),
         Anchor::TOP_LEFT,
         children![
+            record_page(0, "High Score:", difficulties, style.clone()),
+            record_page(1, "Records:", modes, style),
+        ],
+    )
+}
+
+/// 一页记录：标题与每行按列对齐的文字
+fn record_page(
+    page: usize,
+    title: &'static str,
+    rows: Vec<[String; 3]>,
+    style: TextFont,
+) -> impl Bundle {
+    let cells = rows
+        .into_iter()
+        .enumerate()
+        .flat_map(|(row, cells)| {
+            cells
+                .into_iter()
+                .zip(COLUMNS)
+                .map(move |(text, x)| (row, x, text))
+        })
+        .map(|(row, x, text)| {
             (
-                Text2d::new("High Score:\n\n"),
-                style.clone(),
-                Transform::from_xyz(47.0, -10.0, 0.0),
-                Anchor::TOP_LEFT,
-                TextColor(COLOR_YELLOW),
-            ),
-            (
-                Text2d::new(format!("${}", persistent.high_score)),
+                Text2d::new(text),
                 style.clone(),
-                Transform::from_xyz(47.0, -60.0, 0.0),
+                Transform::from_xyz(x, FIRST_ROW_Y - row as f32 * ROW_SPACING, 0.0),
                 Anchor::TOP_LEFT,
                 TextColor(COLOR_GREEN),
-            ),
-            (
-                Text2d::new(format!("at Level {}", persistent.high_level)),
-                style,
-                Transform::from_xyz(47.0, -80.0, 0.0),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    (
+        Name::new(format!("Record Page {page}")),
+        RecordPage(page),
+        Transform::default(),
+        if page == 0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        },
+        Children::spawn((
+            Spawn((
+                Text2d::new(title),
+                TextFont {
+                    font_size: 20.0,
+                    ..style
+                },
+                Transform::from_xyz(47.0, -10.0, 0.0),
                 Anchor::TOP_LEFT,
                 TextColor(COLOR_YELLOW),
-            )
-        ],
+            )),
+            SpawnIter(cells.into_iter()),
+        )),
     )
 }
 
+/// 左右键切换记录页
+fn switch_page(
+    input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut q_pages: Query<(&RecordPage, &mut Visibility)>,
+) {
+    let mut step = input.any_just_pressed([KeyCode::ArrowLeft, KeyCode::ArrowRight]);
+    for gamepad in &gamepads {
+        step |= gamepad.any_just_pressed([GamepadButton::DPadLeft, GamepadButton::DPadRight]);
+    }
+    if !step {
+        return;
+    }
+
+    let current = q_pages
+        .iter()
+        .find(|(_, visibility)| **visibility != Visibility::Hidden)
+        .map_or(0, |(page, _)| page.0);
+    let next = (current + 1) % PAGE_COUNT;
+    for (page, mut visibility) in &mut q_pages {
+        *visibility = if page.0 == next {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
 fn go_back(
     input: Res<ButtonInput<KeyCode>>,
     gamepads: Query<&Gamepad>,
     mut next_menu: ResMut<NextState<Menu>>,
 ) {
-    let mut pressed = input.get_just_pressed().next().is_some();
+    // 左右键用于切换记录页
+    let page_keys = [KeyCode::ArrowLeft, KeyCode::ArrowRight];
+    let page_buttons = [GamepadButton::DPadLeft, GamepadButton::DPadRight];
+    let mut pressed = input.get_just_pressed().any(|key| !page_keys.contains(key));
     if !pressed {
         for gamepad in &gamepads {
-            if gamepad.get_just_pressed().next().is_some() {
+            if gamepad
+                .get_just_pressed()
+                .any(|button| !page_buttons.contains(button))
+            {
                 pressed = true;
                 break;
             }
@@ -0,0 +1,249 @@
+//! The level select menu.
+//!
+//! Lists every layout in levels.yaml with a thumbnail and starts a single practice level.
+
+use crate::config::{EntitiesConfig, EntityType, ImageAssets, LevelsConfig};
+use crate::constants::{COLOR_GREEN, COLOR_YELLOW};
+use crate::demo::level::{EntityHandle, LevelHandle};
+use crate::screens::practice::{PracticeOption, PracticeSetup, sort_level_ids};
+use crate::screens::stats::{GameMode, SelectedMode};
+use crate::utils::love_to_bevy_coords;
+use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+/// 第一项的 y 坐标与项间距 (LÖVE 坐标)
+const FIRST_ITEM_Y: f32 = 100.0;
+const ITEM_SPACING: f32 = 12.0;
+/// 缩略图左上角位置与缩放比例：矿区 (0, 40) - (320, 240) 缩小显示
+const THUMBNAIL_ORIGIN: Vec2 = Vec2::new(170.0, 110.0);
+const THUMBNAIL_SCALE: f32 = 0.44;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PracticeSetup>();
+    app.add_systems(OnEnter(Menu::LevelSelect), spawn_level_select_menu)
+        .add_systems(
+            Update,
+            (keyboard_input, update_option_rows, update_thumbnail)
+                .chain()
+                .run_if(in_state(Menu::LevelSelect)),
+        );
+}
+
+/// 当前选中的设置项
+#[derive(Resource, Default)]
+struct SelectedOption(usize);
+
+#[derive(Component)]
+struct OptionRow(usize);
+
+/// 缩略图容器，记录当前显示的关卡 ID
+#[derive(Component, Default)]
+struct Thumbnail(Option<String>);
+
+fn spawn_level_select_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(SelectedOption::default());
+
+    let title_style = TextFont {
+        font: asset_server.load("fonts/Kurland.ttf"),
+        font_size: 20.0,
+        ..default()
+    };
+    let row_style = TextFont {
+        font: asset_server.load("fonts/Pixel-Square-10-1.ttf"),
+        font_size: 10.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            widget::ui_root("Level Select Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::LevelSelect),
+            Sprite::from_image(asset_server.load("images/bg_start_menu.png")),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2d::new("Practice"),
+                title_style,
+                Transform::from_translation(love_to_bevy_coords(20.0, 78.0).extend(1.0)),
+                Anchor::TOP_LEFT,
+                TextColor(COLOR_GREEN),
+            ));
+
+            for index in 0..PracticeOption::ALL.len() {
+                parent.spawn((
+                    Text2d::default(),
+                    row_style.clone(),
+                    Transform::from_translation(
+                        love_to_bevy_coords(20.0, FIRST_ITEM_Y + index as f32 * ITEM_SPACING)
+                            .extend(1.0),
+                    ),
+                    Anchor::TOP_LEFT,
+                    TextColor(COLOR_YELLOW),
+                    OptionRow(index),
+                ));
+            }
+
+            // 缩略图背景
+            parent.spawn((
+                Name::new("Thumbnail"),
+                Sprite::from_color(
+                    Color::srgba(0.0, 0.0, 0.0, 0.5),
+                    Vec2::new(320.0, 200.0) * THUMBNAIL_SCALE,
+                ),
+                Transform::from_translation(
+                    love_to_bevy_coords(THUMBNAIL_ORIGIN.x, THUMBNAIL_ORIGIN.y).extend(1.0),
+                ),
+                Anchor::TOP_LEFT,
+                Thumbnail::default(),
+            ));
+        });
+}
+
+/// levels.yaml 中的全部关卡 ID，配置尚未加载时为空
+fn level_ids(level_handle: &LevelHandle, levels: &Assets<LevelsConfig>) -> Vec<String> {
+    level_handle
+        .get(levels)
+        .map(|config| sort_level_ids(config.levels.keys()))
+        .unwrap_or_default()
+}
+
+fn keyboard_input(
+    input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut selected: ResMut<SelectedOption>,
+    mut setup: ResMut<PracticeSetup>,
+    mut selected_mode: ResMut<SelectedMode>,
+    mut next_menu: ResMut<NextState<Menu>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    resource_handles: Res<ResourceHandles>,
+    level_handle: Res<LevelHandle>,
+    levels: Res<Assets<LevelsConfig>>,
+) {
+    let mut up = input.just_pressed(KeyCode::ArrowUp);
+    let mut down = input.just_pressed(KeyCode::ArrowDown);
+    let mut left = input.just_pressed(KeyCode::ArrowLeft);
+    let mut right = input.just_pressed(KeyCode::ArrowRight);
+    let mut confirm = input.just_pressed(KeyCode::Enter)
+        || input.just_pressed(KeyCode::NumpadEnter)
+        || input.just_pressed(KeyCode::KeyJ)
+        || input.just_pressed(KeyCode::KeyK);
+    let mut back = input.just_pressed(KeyCode::Backspace);
+
+    for gamepad in &gamepads {
+        up |= gamepad.just_pressed(GamepadButton::DPadUp);
+        down |= gamepad.just_pressed(GamepadButton::DPadDown);
+        left |= gamepad.just_pressed(GamepadButton::DPadLeft);
+        right |= gamepad.just_pressed(GamepadButton::DPadRight);
+        confirm |= gamepad.just_pressed(GamepadButton::South)
+            || gamepad.just_pressed(GamepadButton::Start);
+        back |= gamepad.just_pressed(GamepadButton::East);
+    }
+
+    let count = PracticeOption::ALL.len();
+    if up {
+        selected.0 = (selected.0 + count - 1) % count;
+    } else if down {
+        selected.0 = (selected.0 + 1) % count;
+    }
+
+    let levels = level_ids(&level_handle, &levels);
+    let option = PracticeOption::ALL[selected.0];
+    if left {
+        option.adjust(&mut setup, -1, &levels);
+    } else if right {
+        option.adjust(&mut setup, 1, &levels);
+    }
+
+    if back {
+        next_menu.set(Menu::Main);
+    } else if confirm && option != PracticeOption::Start {
+        // 在设置项上确认与右键相同：开关项切换，数值项增加
+        option.adjust(&mut setup, 1, &levels);
+    } else if confirm && levels.contains(&setup.level) {
+        selected_mode.0 = GameMode::Practice;
+        if resource_handles.is_all_done() {
+            next_screen.set(Screen::NextGoal);
+        } else {
+            next_screen.set(Screen::Loading);
+        }
+    }
+}
+
+fn update_option_rows(
+    selected: Res<SelectedOption>,
+    setup: Res<PracticeSetup>,
+    mut q_rows: Query<(&OptionRow, &mut Text2d, &mut TextColor)>,
+) {
+    for (row, mut text, mut color) in &mut q_rows {
+        let option = PracticeOption::ALL[row.0];
+        let label = option.label(&setup);
+        if text.0 != label {
+            text.0 = label;
+        }
+        color.0 = if row.0 == selected.0 {
+            COLOR_GREEN
+        } else {
+            COLOR_YELLOW
+        };
+    }
+}
+
+/// 选中的关卡变化时，按实体坐标重新绘制缩略图
+fn update_thumbnail(
+    mut commands: Commands,
+    setup: Res<PracticeSetup>,
+    mut q_thumbnail: Single<(Entity, &mut Thumbnail)>,
+    level_handle: Res<LevelHandle>,
+    levels: Res<Assets<LevelsConfig>>,
+    entity_handle: Res<EntityHandle>,
+    entities: Res<Assets<EntitiesConfig>>,
+    image_assets: Option<Res<ImageAssets>>,
+) {
+    let (thumbnail_entity, thumbnail) = &mut *q_thumbnail;
+    if thumbnail.0.as_ref() == Some(&setup.level) {
+        return;
+    }
+    let (Some(levels), Some(entities), Some(image_assets)) = (
+        level_handle.get(&levels),
+        entity_handle.get(&entities),
+        image_assets,
+    ) else {
+        return;
+    };
+
+    commands
+        .entity(*thumbnail_entity)
+        .despawn_related::<Children>();
+    thumbnail.0 = Some(setup.level.clone());
+    let Some(descriptor) = levels.levels.get(&setup.level) else {
+        return;
+    };
+
+    for level_entity in &descriptor.entities {
+        let (x, y) = (level_entity.pos.x, level_entity.pos.y - 40.0);
+        if !(0.0..=320.0).contains(&x) || !(0.0..=200.0).contains(&y) {
+            continue;
+        }
+        let Some(image) = image_assets.get_image(&level_entity.entity_id) else {
+            continue;
+        };
+
+        let mut sprite = Sprite::from_image(image);
+        // 巡逻实体使用精灵表，只取第一帧 (18x13)
+        if entities
+            .entities
+            .get(&level_entity.entity_id)
+            .is_some_and(|descriptor| descriptor.entity_type == EntityType::MoveAround)
+        {
+            sprite.rect = Some(Rect::new(0.0, 0.0, 18.0, 13.0));
+        }
+
+        commands.entity(*thumbnail_entity).with_child((
+            sprite,
+            Transform::from_xyz(x * THUMBNAIL_SCALE, -y * THUMBNAIL_SCALE, 1.0)
+                .with_scale(Vec3::splat(THUMBNAIL_SCALE)),
+        ));
+    }
+}
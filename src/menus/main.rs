@@ -2,8 +2,9 @@
 
 use crate::constants::COLOR_YELLOW;
 use crate::menus::MenuSelect;
+use crate::screens::stats::{GameMode, SelectedMode};
 use crate::utils::love_to_bevy_coords;
-use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use crate::{menus::Menu, theme::widget};
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 
@@ -26,8 +27,10 @@ fn spawn_main_menu(
         DespawnOnExit(Menu::Main),
         Sprite::from_image(asset_server.load("images/bg_start_menu.png")),
         children![
-            play_button(&asset_server),
-            score_button(&asset_server),
+            menu_button(&asset_server, "Start Game", MenuSelect::StartGame),
+            menu_button(&asset_server, "Modes", MenuSelect::Modes),
+            menu_button(&asset_server, "2 Players", MenuSelect::TwoPlayer),
+            menu_button(&asset_server, "High Score", MenuSelect::HighScore),
             developer_text(&asset_server),
             menu_arrow(&asset_server)
         ],
@@ -36,7 +39,7 @@ fn spawn_main_menu(
     next_item.set(MenuSelect::StartGame)
 }
 
-fn play_button(asset_server: &AssetServer) -> impl Bundle {
+fn menu_button(asset_server: &AssetServer, label: &str, item: MenuSelect) -> impl Bundle {
     let font = asset_server.load("fonts/Kurland.ttf");
     let style = TextFont {
         font: font.clone(),
@@ -45,26 +48,9 @@ fn play_button(asset_server: &AssetServer) -> impl Bundle {
     };
 
     (
-        Text2d::new("Start Game"),
+        Text2d::new(label),
         style,
-        Transform::from_translation(love_to_bevy_coords(30.0, 150.0).extend(1.0)),
-        Anchor::TOP_LEFT,
-        TextColor(COLOR_YELLOW),
-    )
-}
-
-fn score_button(asset_server: &AssetServer) -> impl Bundle {
-    let font = asset_server.load("fonts/Kurland.ttf");
-    let style = TextFont {
-        font: font.clone(),
-        font_size: 20.0,
-        ..default()
-    };
-
-    (
-        Text2d::new("High Score"),
-        style,
-        Transform::from_translation(love_to_bevy_coords(30.0, 170.0).extend(1.0)),
+        Transform::from_translation(love_to_bevy_coords(30.0, item_y(item)).extend(1.0)),
         Anchor::TOP_LEFT,
         TextColor(COLOR_YELLOW),
     )
@@ -87,6 +73,11 @@ fn developer_text(asset_server: &AssetServer) -> impl Bundle {
     )
 }
 
+/// 菜单项的 y 坐标 (LÖVE 坐标)
+fn item_y(item: MenuSelect) -> f32 {
+    130.0 + item.index() as f32 * 20.0
+}
+
 #[derive(Component)]
 struct MenuArrow;
 
@@ -94,7 +85,9 @@ fn menu_arrow(asset_server: &AssetServer) -> impl Bundle {
     (
         Name::new("Menu Arrow"),
         Sprite::from_image(asset_server.load("images/menu_arrow.png")),
-        Transform::from_translation(love_to_bevy_coords(5.0, 152.0).extend(1.0)),
+        Transform::from_translation(
+            love_to_bevy_coords(5.0, item_y(MenuSelect::StartGame) + 2.0).extend(1.0),
+        ),
         Anchor::TOP_LEFT,
         MenuArrow,
     )
@@ -106,8 +99,7 @@ fn keyboard_input(
     current_item: Res<State<MenuSelect>>,
     mut next_item: ResMut<NextState<MenuSelect>>,
     mut next_menu: ResMut<NextState<Menu>>,
-    mut next_screen: ResMut<NextState<Screen>>,
-    resource_handles: Res<ResourceHandles>,
+    mut selected_mode: ResMut<SelectedMode>,
 ) {
     let mut up = input.just_pressed(KeyCode::ArrowUp);
     let mut down = input.just_pressed(KeyCode::ArrowDown);
@@ -131,23 +123,23 @@ fn keyboard_input(
         }
     }
 
-    if up || down {
-        if current_item.get() == &MenuSelect::StartGame {
-            next_item.set(MenuSelect::HighScore)
-        } else {
-            next_item.set(MenuSelect::StartGame)
-        }
+    let count = MenuSelect::ALL.len();
+    let index = current_item.get().index();
+    if up {
+        next_item.set(MenuSelect::ALL[(index + count - 1) % count]);
+    } else if down {
+        next_item.set(MenuSelect::ALL[(index + 1) % count]);
     }
 
     if confirm {
-        if current_item.get() == &MenuSelect::StartGame {
-            if resource_handles.is_all_done() {
-                next_screen.set(Screen::NextGoal);
-            } else {
-                next_screen.set(Screen::Loading);
+        match current_item.get() {
+            MenuSelect::StartGame => {
+                selected_mode.0 = GameMode::Campaign;
+                next_menu.set(Menu::Difficulty)
             }
-        } else {
-            next_menu.set(Menu::HighScore)
+            MenuSelect::Modes => next_menu.set(Menu::Modes),
+            MenuSelect::TwoPlayer => next_menu.set(Menu::TwoPlayer),
+            MenuSelect::HighScore => next_menu.set(Menu::HighScore),
         }
     }
 }
@@ -164,11 +156,5 @@ fn update_menu_arrow(
         return;
     };
 
-    let transform = if entered == &MenuSelect::StartGame {
-        love_to_bevy_coords(5.0, 152.0).extend(1.0)
-    } else {
-        love_to_bevy_coords(5.0, 172.0).extend(1.0)
-    };
-
-    q_arrow.translation = transform;
+    q_arrow.translation = love_to_bevy_coords(5.0, item_y(*entered) + 2.0).extend(1.0);
 }
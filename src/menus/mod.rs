@@ -1,14 +1,25 @@
 //! The game's menus and transitions between them.
 
+mod difficulty;
 mod high_score;
+mod level_select;
 mod main;
+mod modes;
+mod online;
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>().init_state::<MenuSelect>();
 
-    app.add_plugins((main::plugin, high_score::plugin));
+    app.add_plugins((
+        main::plugin,
+        modes::plugin,
+        difficulty::plugin,
+        level_select::plugin,
+        online::plugin,
+        high_score::plugin,
+    ));
 }
 
 #[derive(States, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
@@ -16,6 +27,11 @@ pub enum Menu {
     #[default]
     None,
     Main,
+    Modes,
+    TwoPlayer,
+    Difficulty,
+    LevelSelect,
+    Online,
     HighScore,
 }
 
@@ -23,5 +39,21 @@ pub enum Menu {
 pub enum MenuSelect {
     #[default]
     StartGame,
+    Modes,
+    TwoPlayer,
     HighScore,
 }
+
+impl MenuSelect {
+    pub const ALL: [MenuSelect; 4] = [
+        MenuSelect::StartGame,
+        MenuSelect::Modes,
+        MenuSelect::TwoPlayer,
+        MenuSelect::HighScore,
+    ];
+
+    /// 在菜单中的序号
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|item| item == self).unwrap_or(0)
+    }
+}
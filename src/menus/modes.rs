@@ -0,0 +1,226 @@
+//! The game mode menus.
+//!
+//! "Modes" lists the single-player modes and "2 Players" the two-player modes,
+//! laid out like the difficulty menu so the main menu stays short.
+
+use crate::constants::{COLOR_GREEN, COLOR_YELLOW};
+use crate::screens::stats::{GameMode, SelectedMode};
+use crate::utils::love_to_bevy_coords;
+use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+/// 第一项的 y 坐标与项间距 (LÖVE 坐标)，与难度菜单一致
+const FIRST_ITEM_Y: f32 = 120.0;
+const ITEM_SPACING: f32 = 20.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SelectedModeItem>();
+    app.add_systems(OnEnter(Menu::Modes), spawn_mode_menu)
+        .add_systems(OnEnter(Menu::TwoPlayer), spawn_mode_menu)
+        .add_systems(
+            Update,
+            (keyboard_input, update_menu_arrow)
+                .chain()
+                .run_if(in_state(Menu::Modes).or(in_state(Menu::TwoPlayer))),
+        );
+}
+
+/// 模式菜单中的一项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModeItem {
+    Endless,
+    Daily,
+    TimeAttack,
+    Practice,
+    Versus,
+    Coop,
+    Online,
+}
+
+impl ModeItem {
+    fn label(&self) -> &'static str {
+        match self {
+            ModeItem::Endless => "Endless",
+            ModeItem::Daily => "Daily",
+            ModeItem::TimeAttack => "Time Attack",
+            ModeItem::Practice => "Practice",
+            ModeItem::Versus => "2P Versus",
+            ModeItem::Coop => "2P Co-op",
+            ModeItem::Online => "Online Race",
+        }
+    }
+}
+
+/// 菜单标题与列出的模式
+fn menu_items(menu: Menu) -> (&'static str, &'static [ModeItem]) {
+    match menu {
+        Menu::TwoPlayer => (
+            "2 Players",
+            &[ModeItem::Versus, ModeItem::Coop, ModeItem::Online],
+        ),
+        _ => (
+            "Modes",
+            &[
+                ModeItem::Endless,
+                ModeItem::Daily,
+                ModeItem::TimeAttack,
+                ModeItem::Practice,
+            ],
+        ),
+    }
+}
+
+/// 当前选中项的序号，进入菜单时回到第一项
+#[derive(Resource, Debug, Default)]
+struct SelectedModeItem(usize);
+
+#[derive(Component)]
+struct MenuArrow;
+
+fn spawn_mode_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    menu: Res<State<Menu>>,
+    mut selected: ResMut<SelectedModeItem>,
+) {
+    let font = asset_server.load("fonts/Kurland.ttf");
+    let style = TextFont {
+        font: font.clone(),
+        font_size: 20.0,
+        ..default()
+    };
+    let menu = *menu.get();
+    let (title, items) = menu_items(menu);
+    selected.0 = 0;
+
+    commands
+        .spawn((
+            widget::ui_root("Mode Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(menu),
+            Sprite::from_image(asset_server.load("images/bg_start_menu.png")),
+            children![(
+                Name::new("Menu Arrow"),
+                Sprite::from_image(asset_server.load("images/menu_arrow.png")),
+                Transform::from_translation(arrow_position(0)),
+                Anchor::TOP_LEFT,
+                MenuArrow,
+            )],
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2d::new(title),
+                style.clone(),
+                Transform::from_translation(love_to_bevy_coords(30.0, 95.0).extend(1.0)),
+                Anchor::TOP_LEFT,
+                TextColor(COLOR_GREEN),
+            ));
+
+            for (index, item) in items.iter().enumerate() {
+                parent.spawn((
+                    Text2d::new(item.label()),
+                    style.clone(),
+                    Transform::from_translation(
+                        love_to_bevy_coords(30.0, item_y(index)).extend(1.0),
+                    ),
+                    Anchor::TOP_LEFT,
+                    TextColor(COLOR_YELLOW),
+                ));
+            }
+        });
+}
+
+fn item_y(index: usize) -> f32 {
+    FIRST_ITEM_Y + index as f32 * ITEM_SPACING
+}
+
+fn arrow_position(index: usize) -> Vec3 {
+    love_to_bevy_coords(5.0, item_y(index) + 2.0).extend(1.0)
+}
+
+fn keyboard_input(
+    input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    menu: Res<State<Menu>>,
+    mut selected: ResMut<SelectedModeItem>,
+    mut selected_mode: ResMut<SelectedMode>,
+    mut next_menu: ResMut<NextState<Menu>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    resource_handles: Res<ResourceHandles>,
+) {
+    let mut up = input.just_pressed(KeyCode::ArrowUp);
+    let mut down = input.just_pressed(KeyCode::ArrowDown);
+    let mut confirm = input.just_pressed(KeyCode::Enter)
+        || input.just_pressed(KeyCode::NumpadEnter)
+        || input.just_pressed(KeyCode::KeyJ)
+        || input.just_pressed(KeyCode::KeyK);
+    let mut back = input.just_pressed(KeyCode::Backspace);
+
+    for gamepad in &gamepads {
+        if gamepad.just_pressed(GamepadButton::DPadUp) {
+            up = true;
+        }
+        if gamepad.just_pressed(GamepadButton::DPadDown) {
+            down = true;
+        }
+        if gamepad.just_pressed(GamepadButton::South) || gamepad.just_pressed(GamepadButton::Start)
+        {
+            confirm = true;
+        }
+        if gamepad.just_pressed(GamepadButton::East) {
+            back = true;
+        }
+    }
+
+    let (_, items) = menu_items(*menu.get());
+    let count = items.len();
+    if up {
+        selected.0 = (selected.0 + count - 1) % count;
+    } else if down {
+        selected.0 = (selected.0 + 1) % count;
+    }
+
+    if back {
+        next_menu.set(Menu::Main);
+        return;
+    }
+    if !confirm {
+        return;
+    }
+
+    match items[selected.0] {
+        ModeItem::Endless => {
+            selected_mode.0 = GameMode::Endless;
+            next_menu.set(Menu::Difficulty);
+        }
+        ModeItem::Coop => {
+            selected_mode.0 = GameMode::Coop;
+            next_menu.set(Menu::Difficulty);
+        }
+        item @ (ModeItem::Daily | ModeItem::TimeAttack | ModeItem::Versus) => {
+            // 每日挑战、限时挑战与对战固定难度，直接开始
+            selected_mode.0 = match item {
+                ModeItem::Daily => GameMode::Daily,
+                ModeItem::TimeAttack => GameMode::TimeAttack,
+                _ => GameMode::Versus,
+            };
+            if resource_handles.is_all_done() {
+                next_screen.set(Screen::NextGoal);
+            } else {
+                next_screen.set(Screen::Loading);
+            }
+        }
+        ModeItem::Practice => next_menu.set(Menu::LevelSelect),
+        ModeItem::Online => next_menu.set(Menu::Online),
+    }
+}
+
+fn update_menu_arrow(
+    selected: Res<SelectedModeItem>,
+    mut q_arrow: Single<&mut Transform, With<MenuArrow>>,
+) {
+    if selected.is_changed() {
+        q_arrow.translation = arrow_position(selected.0);
+    }
+}
@@ -0,0 +1,246 @@
+//! The online race lobby menu.
+//!
+//! Hosts a lobby on the relay server or joins one by its code, then starts once the rival arrives.
+
+use crate::constants::{COLOR_GREEN, COLOR_YELLOW};
+use crate::online::OnlineSession;
+use crate::online::protocol::{ClientMessage, LOBBY_CODE_CHARS, LOBBY_CODE_LEN};
+use crate::screens::stats::{GameMode, SelectedMode};
+use crate::utils::love_to_bevy_coords;
+use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+/// 第一项的 y 坐标与项间距 (LÖVE 坐标)
+const FIRST_ITEM_Y: f32 = 110.0;
+const ITEM_SPACING: f32 = 16.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Online), spawn_online_menu)
+        .add_systems(
+            Update,
+            (keyboard_input, update_rows, start_race)
+                .chain()
+                .run_if(in_state(Menu::Online)),
+        );
+}
+
+/// 大厅菜单状态：选中的行与输入的房间码
+#[derive(Resource, Default)]
+struct Lobby {
+    selected: usize,
+    code: String,
+}
+
+#[derive(Component)]
+struct LobbyRow(usize);
+
+#[derive(Component)]
+struct StatusText;
+
+const ROW_COUNT: usize = 2;
+
+fn spawn_online_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Lobby::default());
+
+    let title_style = TextFont {
+        font: asset_server.load("fonts/Kurland.ttf"),
+        font_size: 20.0,
+        ..default()
+    };
+    let row_style = TextFont {
+        font: asset_server.load("fonts/Pixel-Square-10-1.ttf"),
+        font_size: 10.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            widget::ui_root("Online Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Online),
+            Sprite::from_image(asset_server.load("images/bg_start_menu.png")),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2d::new("Online Race"),
+                title_style,
+                Transform::from_translation(love_to_bevy_coords(20.0, 78.0).extend(1.0)),
+                Anchor::TOP_LEFT,
+                TextColor(COLOR_GREEN),
+            ));
+
+            for index in 0..ROW_COUNT {
+                parent.spawn((
+                    Text2d::default(),
+                    row_style.clone(),
+                    Transform::from_translation(
+                        love_to_bevy_coords(20.0, FIRST_ITEM_Y + index as f32 * ITEM_SPACING)
+                            .extend(1.0),
+                    ),
+                    Anchor::TOP_LEFT,
+                    TextColor(COLOR_YELLOW),
+                    LobbyRow(index),
+                ));
+            }
+
+            parent.spawn((
+                Text2d::default(),
+                row_style,
+                Transform::from_translation(
+                    love_to_bevy_coords(20.0, FIRST_ITEM_Y + ROW_COUNT as f32 * ITEM_SPACING + 8.0)
+                        .extend(1.0),
+                ),
+                Anchor::TOP_LEFT,
+                TextColor(COLOR_GREEN),
+                StatusText,
+            ));
+        });
+}
+
+/// 字母键与对应的字符
+const LETTER_KEYS: [(KeyCode, char); 26] = [
+    (KeyCode::KeyA, 'A'),
+    (KeyCode::KeyB, 'B'),
+    (KeyCode::KeyC, 'C'),
+    (KeyCode::KeyD, 'D'),
+    (KeyCode::KeyE, 'E'),
+    (KeyCode::KeyF, 'F'),
+    (KeyCode::KeyG, 'G'),
+    (KeyCode::KeyH, 'H'),
+    (KeyCode::KeyI, 'I'),
+    (KeyCode::KeyJ, 'J'),
+    (KeyCode::KeyK, 'K'),
+    (KeyCode::KeyL, 'L'),
+    (KeyCode::KeyM, 'M'),
+    (KeyCode::KeyN, 'N'),
+    (KeyCode::KeyO, 'O'),
+    (KeyCode::KeyP, 'P'),
+    (KeyCode::KeyQ, 'Q'),
+    (KeyCode::KeyR, 'R'),
+    (KeyCode::KeyS, 'S'),
+    (KeyCode::KeyT, 'T'),
+    (KeyCode::KeyU, 'U'),
+    (KeyCode::KeyV, 'V'),
+    (KeyCode::KeyW, 'W'),
+    (KeyCode::KeyX, 'X'),
+    (KeyCode::KeyY, 'Y'),
+    (KeyCode::KeyZ, 'Z'),
+];
+
+/// 房间码字符对应的按键
+fn code_char(key: KeyCode) -> Option<char> {
+    LETTER_KEYS
+        .iter()
+        .find(|(letter_key, _)| *letter_key == key)
+        .map(|(_, letter)| *letter)
+        .filter(|letter| LOBBY_CODE_CHARS.contains(&(*letter as u8)))
+}
+
+fn keyboard_input(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut lobby: ResMut<Lobby>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    let mut up = input.just_pressed(KeyCode::ArrowUp);
+    let mut down = input.just_pressed(KeyCode::ArrowDown);
+    let mut confirm =
+        input.just_pressed(KeyCode::Enter) || input.just_pressed(KeyCode::NumpadEnter);
+    let erase = input.just_pressed(KeyCode::Backspace);
+    let mut back = erase && lobby.code.is_empty();
+
+    for gamepad in &gamepads {
+        up |= gamepad.just_pressed(GamepadButton::DPadUp);
+        down |= gamepad.just_pressed(GamepadButton::DPadDown);
+        confirm |= gamepad.just_pressed(GamepadButton::South)
+            || gamepad.just_pressed(GamepadButton::Start);
+        back |= gamepad.just_pressed(GamepadButton::East);
+    }
+
+    if up || down {
+        lobby.selected = (lobby.selected + 1) % ROW_COUNT;
+    }
+
+    // 在 Join 行输入房间码
+    if lobby.selected == 1 {
+        for key in input.get_just_pressed() {
+            if let Some(letter) = code_char(*key)
+                && lobby.code.len() < LOBBY_CODE_LEN
+            {
+                lobby.code.push(letter);
+            }
+        }
+        if erase {
+            lobby.code.pop();
+        }
+    }
+
+    if back {
+        commands.remove_resource::<OnlineSession>();
+        next_menu.set(Menu::Main);
+    } else if confirm {
+        let first = match lobby.selected {
+            0 => ClientMessage::Host,
+            _ if lobby.code.len() == LOBBY_CODE_LEN => ClientMessage::Join {
+                code: lobby.code.clone(),
+            },
+            _ => return,
+        };
+        commands.insert_resource(OnlineSession::connect(first));
+    }
+}
+
+fn update_rows(
+    lobby: Res<Lobby>,
+    session: Option<Res<OnlineSession>>,
+    mut q_rows: Query<(&LobbyRow, &mut Text2d, &mut TextColor), Without<StatusText>>,
+    mut q_status: Query<&mut Text2d, With<StatusText>>,
+) {
+    for (row, mut text, mut color) in &mut q_rows {
+        let label = match row.0 {
+            0 => "Host Race".to_string(),
+            _ => format!("Join: {:_<width$}", lobby.code, width = LOBBY_CODE_LEN),
+        };
+        if text.0 != label {
+            text.0 = label;
+        }
+        color.0 = if row.0 == lobby.selected {
+            COLOR_GREEN
+        } else {
+            COLOR_YELLOW
+        };
+    }
+
+    let status = session
+        .map(|session| session.status_text())
+        .unwrap_or_else(|| "Type a code to join, Enter to confirm".to_string());
+    for mut text in &mut q_status {
+        if text.0 != status {
+            text.0 = status.clone();
+        }
+    }
+}
+
+/// 对手到齐后开始竞速
+fn start_race(
+    session: Option<ResMut<OnlineSession>>,
+    mut selected_mode: ResMut<SelectedMode>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    resource_handles: Res<ResourceHandles>,
+) {
+    let Some(mut session) = session else {
+        return;
+    };
+    if !session.take_start() {
+        return;
+    }
+
+    selected_mode.0 = GameMode::Online;
+    if resource_handles.is_all_done() {
+        next_screen.set(Screen::NextGoal);
+    } else {
+        next_screen.set(Screen::Loading);
+    }
+}
@@ -0,0 +1,338 @@
+//! 联网竞速
+//!
+//! 两个客户端通过中继服务器 (`cargo run --bin relay`) 配对后，使用同一个种子进行同样的关卡序列：
+//! - 在主菜单的 Online Race 中创建房间 (得到房间码) 或输入房间码加入
+//! - 中继地址默认为 `127.0.0.1:7878`，可用环境变量 `GOLDMINER_RELAY` 修改
+//! - 抓到物品、过关与结束时发送 `RunEvent`，HUD 实时显示对手的金钱与关卡
+//! - 网络读写在后台线程中进行，通过通道与 `OnlineSession` 交换消息
+
+pub mod protocol;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpStream};
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+use crate::constants::{COLOR_DEEP_ORANGE, COLOR_GREEN};
+use crate::demo::hook::EntityCollected;
+use crate::screens::Screen;
+use crate::screens::made_goal::spawn_made_goal_ui;
+use crate::screens::stats::LevelStats;
+use crate::utils::love_to_bevy_coords;
+use protocol::{ClientMessage, DEFAULT_RELAY_ADDRESS, RunEvent, ServerMessage};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, poll_relay.run_if(resource_exists::<OnlineSession>));
+    app.add_systems(
+        Update,
+        (send_collected_events, update_rival_hud)
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<OnlineSession>)),
+    );
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        spawn_rival_hud.run_if(resource_exists::<OnlineSession>),
+    );
+    app.add_systems(
+        OnEnter(Screen::MadeGoal),
+        // 过关界面会把关卡数推进到下一关，在此之前发送刚通过的关卡
+        send_level_cleared
+            .before(spawn_made_goal_ui)
+            .run_if(resource_exists::<OnlineSession>),
+    );
+    app.add_systems(
+        OnEnter(Screen::GameOver),
+        send_game_over.run_if(resource_exists::<OnlineSession>),
+    );
+    app.add_systems(OnEnter(Screen::Title), close_session);
+}
+
+/// 对手的进度
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RivalProgress {
+    pub money: u32,
+    pub level: u32,
+    /// 对手已结束本局
+    pub finished: bool,
+    /// 对手已断开
+    pub left: bool,
+}
+
+impl RivalProgress {
+    /// 按对手事件更新进度
+    pub fn apply(&mut self, event: &RunEvent) {
+        match *event {
+            RunEvent::EntityCollected { money, .. } => self.money = money,
+            RunEvent::LevelCleared { level, money } => {
+                self.level = self.level.max(level + 1);
+                self.money = money;
+            }
+            RunEvent::GameOver { level, money } => {
+                self.level = level;
+                self.money = money;
+                self.finished = true;
+            }
+        }
+    }
+
+    /// HUD 与结束界面显示的文字
+    pub fn summary(&self) -> String {
+        let state = if self.left {
+            " (left)"
+        } else if self.finished {
+            " (done)"
+        } else {
+            ""
+        };
+        format!("Rival L{} ${}{state}", self.level.max(1), self.money)
+    }
+}
+
+/// 连接状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionStatus {
+    Connecting,
+    /// 已创建房间，等待对手加入
+    Waiting {
+        code: String,
+    },
+    /// 双方到齐，使用该种子开始
+    Ready {
+        seed: u64,
+    },
+    /// 竞速进行中
+    Racing {
+        seed: u64,
+    },
+    Failed(String),
+}
+
+/// 与中继服务器的连接，存在期间即处于联网竞速中；回到标题界面时断开
+#[derive(Resource)]
+pub struct OnlineSession {
+    outgoing: Sender<ClientMessage>,
+    incoming: Mutex<Receiver<ServerMessage>>,
+    pub status: SessionStatus,
+    pub rival: RivalProgress,
+}
+
+impl OnlineSession {
+    /// 在后台线程中连接中继并发送第一条消息 (`Host` 或 `Join`)
+    pub fn connect(first: ClientMessage) -> Self {
+        let address =
+            std::env::var("GOLDMINER_RELAY").unwrap_or_else(|_| DEFAULT_RELAY_ADDRESS.to_string());
+        let (outgoing, outgoing_rx) = mpsc::channel();
+        let (incoming_tx, incoming) = mpsc::channel();
+        let _ = outgoing.send(first);
+
+        let error_tx = incoming_tx.clone();
+        let spawned = thread::Builder::new()
+            .name("online-relay".to_string())
+            .spawn(move || {
+                if let Err(err) = run_connection(&address, outgoing_rx, incoming_tx) {
+                    let _ = error_tx.send(ServerMessage::Error {
+                        message: format!("{address}: {err}"),
+                    });
+                }
+            });
+        let status = match spawned {
+            Ok(_) => SessionStatus::Connecting,
+            // 不支持线程的平台 (网页) 无法联网
+            Err(err) => SessionStatus::Failed(err.to_string()),
+        };
+
+        Self {
+            outgoing,
+            incoming: Mutex::new(incoming),
+            status,
+            rival: RivalProgress::default(),
+        }
+    }
+
+    pub fn send(&self, event: RunEvent) {
+        let _ = self.outgoing.send(ClientMessage::Event(event));
+    }
+
+    /// 对手到齐后开始竞速，返回是否开始
+    pub fn take_start(&mut self) -> bool {
+        let SessionStatus::Ready { seed } = self.status else {
+            return false;
+        };
+        self.status = SessionStatus::Racing { seed };
+        true
+    }
+
+    /// 竞速使用的种子
+    pub fn seed(&self) -> Option<u64> {
+        match self.status {
+            SessionStatus::Ready { seed } | SessionStatus::Racing { seed } => Some(seed),
+            _ => None,
+        }
+    }
+
+    /// 菜单中显示的状态文字
+    pub fn status_text(&self) -> String {
+        match &self.status {
+            SessionStatus::Connecting => "Connecting...".to_string(),
+            SessionStatus::Waiting { code } => format!("Lobby code: {code}\nWaiting for rival..."),
+            SessionStatus::Ready { .. } | SessionStatus::Racing { .. } => {
+                "Rival found!".to_string()
+            }
+            SessionStatus::Failed(message) => format!("Error: {message}"),
+        }
+    }
+}
+
+/// 后台线程：读取中继消息写入通道，同时把通道中的消息发给中继；会话移除后断开
+fn run_connection(
+    address: &str,
+    outgoing: Receiver<ClientMessage>,
+    incoming: Sender<ServerMessage>,
+) -> std::io::Result<()> {
+    let mut stream = TcpStream::connect(address)?;
+    let reader = BufReader::new(stream.try_clone()?);
+    thread::spawn(move || {
+        for line in reader.lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if let Some(message) = protocol::decode(&line)
+                && incoming.send(message).is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    for message in outgoing {
+        stream.write_all(protocol::encode(&message).as_bytes())?;
+    }
+    stream.shutdown(Shutdown::Both)
+}
+
+fn poll_relay(mut session: ResMut<OnlineSession>) {
+    let messages: Vec<ServerMessage> = session.incoming.lock().unwrap().try_iter().collect();
+    for message in messages {
+        match message {
+            ServerMessage::LobbyCreated { code } => {
+                session.status = SessionStatus::Waiting { code };
+            }
+            ServerMessage::Start { seed } => session.status = SessionStatus::Ready { seed },
+            ServerMessage::Peer(event) => session.rival.apply(&event),
+            ServerMessage::PeerLeft => session.rival.left = true,
+            ServerMessage::Error { message } => {
+                warn!("Online race: {message}");
+                session.status = SessionStatus::Failed(message);
+            }
+        }
+    }
+}
+
+fn close_session(mut commands: Commands) {
+    commands.remove_resource::<OnlineSession>();
+}
+
+fn send_collected_events(
+    mut collected: MessageReader<EntityCollected>,
+    session: Res<OnlineSession>,
+    stats: Res<LevelStats>,
+) {
+    for event in collected.read() {
+        session.send(RunEvent::EntityCollected {
+            entity_id: event.entity_id.clone(),
+            bonus: event.bonus,
+            money: stats.money,
+        });
+    }
+}
+
+fn send_level_cleared(session: Res<OnlineSession>, stats: Res<LevelStats>) {
+    session.send(RunEvent::LevelCleared {
+        level: stats.level,
+        money: stats.money,
+    });
+}
+
+fn send_game_over(session: Res<OnlineSession>, stats: Res<LevelStats>) {
+    session.send(RunEvent::GameOver {
+        level: stats.level,
+        money: stats.money,
+    });
+}
+
+#[derive(Component)]
+struct RivalText;
+
+fn spawn_rival_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let style = TextFont {
+        font: asset_server.load("fonts/visitor1.ttf"),
+        font_size: 10.0,
+        ..default()
+    };
+    commands
+        .spawn((
+            DespawnOnExit(Screen::Gameplay),
+            Text2d::new("VS "),
+            style.clone(),
+            TextColor(COLOR_DEEP_ORANGE),
+            Transform::from_translation(love_to_bevy_coords(110.0, 25.0).extend(10.0)),
+            Anchor::TOP_LEFT,
+        ))
+        .with_child((
+            TextSpan::default(),
+            style,
+            TextColor(COLOR_GREEN),
+            RivalText,
+        ));
+}
+
+fn update_rival_hud(
+    session: Res<OnlineSession>,
+    mut q_text: Query<&mut TextSpan, With<RivalText>>,
+) {
+    let summary = session.rival.summary();
+    for mut text in &mut q_text {
+        if text.0 != summary {
+            text.0 = summary.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rival_progress_follows_events() {
+        let mut rival = RivalProgress::default();
+        rival.apply(&RunEvent::EntityCollected {
+            entity_id: "BigGold".to_string(),
+            bonus: 500,
+            money: 500,
+        });
+        rival.apply(&RunEvent::LevelCleared {
+            level: 1,
+            money: 700,
+        });
+        assert_eq!(rival.summary(), "Rival L2 $700");
+
+        rival.apply(&RunEvent::GameOver {
+            level: 2,
+            money: 900,
+        });
+        assert_eq!(rival.summary(), "Rival L2 $900 (done)");
+
+        let line = protocol::encode(&ClientMessage::Join {
+            code: "ABCD".to_string(),
+        });
+        assert_eq!(
+            protocol::decode::<ClientMessage>(&line),
+            Some(ClientMessage::Join {
+                code: "ABCD".to_string()
+            })
+        );
+    }
+}
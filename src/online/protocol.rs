@@ -0,0 +1,75 @@
+//! 联网竞速的消息协议
+//!
+//! 客户端与中继服务器之间每行一条 JSON 消息。本文件只依赖 serde，
+//! 同时被游戏与中继服务器 (`src/bin/relay.rs`) 使用。
+
+use serde::{Deserialize, Serialize};
+
+/// 中继服务器的默认地址
+pub const DEFAULT_RELAY_ADDRESS: &str = "127.0.0.1:7878";
+/// 房间码长度
+pub const LOBBY_CODE_LEN: usize = 4;
+/// 房间码使用的字符 (去掉容易混淆的 I 与 O)
+pub const LOBBY_CODE_CHARS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/// 一局中发生的事件，由中继转发给对手
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum RunEvent {
+    /// 抓到物品并结算
+    EntityCollected {
+        entity_id: String,
+        bonus: u32,
+        /// 结算后的总金钱
+        money: u32,
+    },
+    /// 达成目标进入下一关
+    LevelCleared { level: u32, money: u32 },
+    /// 本局结束
+    GameOver { level: u32, money: u32 },
+}
+
+/// 客户端发给中继的消息
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", content = "data")]
+pub enum ClientMessage {
+    /// 创建房间
+    Host,
+    /// 按房间码加入
+    Join { code: String },
+    /// 本局事件
+    Event(RunEvent),
+}
+
+/// 中继发给客户端的消息
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", content = "data")]
+pub enum ServerMessage {
+    /// 房间已创建，等待对手加入
+    LobbyCreated {
+        code: String,
+    },
+    /// 双方到齐，使用同一个种子开始
+    Start {
+        seed: u64,
+    },
+    /// 对手的事件
+    Peer(RunEvent),
+    /// 对手断开连接
+    PeerLeft,
+    Error {
+        message: String,
+    },
+}
+
+/// 编码为一行 JSON (包含换行符)
+pub fn encode<T: Serialize>(message: &T) -> String {
+    let mut line = serde_json::to_string(message).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+/// 解析一行 JSON
+pub fn decode<'a, T: Deserialize<'a>>(line: &'a str) -> Option<T> {
+    serde_json::from_str(line.trim()).ok()
+}
@@ -0,0 +1,223 @@
+//! 演示模式 (attract mode)
+//!
+//! 主菜单无操作一段时间后，由机器人操作 1P 自动进行一关演示：
+//! - 进入演示前保存玩家的 `LevelStats`、`PlayerResource`、`HookStats` 与操作来源，回到标题界面时原样恢复
+//! - 演示中按任意键或手柄按键回到主菜单，演示关卡结束 (时间耗尽或跳过) 时同样回到主菜单，
+//!   不进入结算、商店与高分界面
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use rand::Rng;
+
+use crate::asset_tracking::ResourceHandles;
+use crate::config::{GameplayConfig, ProgressionConfig};
+use crate::constants::COLOR_YELLOW;
+use crate::demo::bot::{Controller, Controllers};
+use crate::demo::hook::HookStats;
+use crate::demo::level::{GameplayHandle, ProgressionHandle};
+use crate::demo::player::{PlayerResource, PlayerSlot};
+use crate::menus::Menu;
+use crate::screens::Screen;
+use crate::screens::coop::CoopState;
+use crate::screens::stats::{Difficulty, LevelStats};
+use crate::screens::versus::VersusState;
+use crate::utils::love_to_bevy_coords;
+
+/// 主菜单无操作多少秒后开始演示
+const ATTRACT_IDLE_SECONDS: f32 = 30.0;
+/// 演示关卡从第 1 关到该关之间随机选择
+const ATTRACT_MAX_LEVEL: u32 = 5;
+/// "Press any key" 的闪烁间隔 (秒)
+const BLINK_SECONDS: f32 = 0.6;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<IdleTimer>();
+    app.add_systems(OnEnter(Menu::Main), reset_idle_timer);
+    app.add_systems(
+        Update,
+        start_attract
+            .run_if(in_state(Screen::Title).and(in_state(Menu::Main)))
+            .in_set(crate::AppSystems::TickTimers),
+    );
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        spawn_attract_overlay.run_if(resource_exists::<AttractMode>),
+    );
+    app.add_systems(
+        Update,
+        blink_overlay.run_if(in_state(Screen::Gameplay).and(resource_exists::<AttractMode>)),
+    );
+    // 在 PostUpdate 中覆盖本帧的界面切换，演示关卡不会进入结算界面
+    app.add_systems(
+        PostUpdate,
+        end_attract.run_if(in_state(Screen::Gameplay).and(resource_exists::<AttractMode>)),
+    );
+    app.add_systems(
+        OnEnter(Screen::Title),
+        restore_player.run_if(resource_exists::<AttractMode>),
+    );
+}
+
+/// 主菜单的无操作计时
+#[derive(Resource, Debug, Default)]
+struct IdleTimer {
+    elapsed: f32,
+}
+
+impl IdleTimer {
+    /// 推进计时，有输入时清零；返回是否达到开始演示的时间
+    fn tick(&mut self, delta: f32, any_input: bool) -> bool {
+        self.elapsed = if any_input { 0.0 } else { self.elapsed + delta };
+        self.elapsed >= ATTRACT_IDLE_SECONDS
+    }
+}
+
+/// 演示进行中；保存进入演示前的玩家状态
+#[derive(Resource)]
+struct AttractMode {
+    stats: LevelStats,
+    player: PlayerResource,
+    hook_stats: HookStats,
+    controllers: Controllers,
+}
+
+#[derive(Component)]
+struct AttractOverlay(Timer);
+
+/// 本帧是否有任意键盘、鼠标或手柄按键按下
+fn any_input(
+    keys: &ButtonInput<KeyCode>,
+    mouse: &ButtonInput<MouseButton>,
+    gamepads: &Query<&Gamepad>,
+) -> bool {
+    keys.get_just_pressed().next().is_some()
+        || mouse.get_just_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some())
+}
+
+fn reset_idle_timer(mut idle: ResMut<IdleTimer>) {
+    *idle = IdleTimer::default();
+}
+
+fn start_attract(
+    mut commands: Commands,
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut idle: ResMut<IdleTimer>,
+    mut stats: ResMut<LevelStats>,
+    mut player: ResMut<PlayerResource>,
+    mut hook_stats: ResMut<HookStats>,
+    mut controllers: ResMut<Controllers>,
+    resource_handles: Res<ResourceHandles>,
+    gameplay_handle: Res<GameplayHandle>,
+    gameplay: Res<Assets<GameplayConfig>>,
+    progression_handle: Res<ProgressionHandle>,
+    progression: Res<Assets<ProgressionConfig>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let input = any_input(&keys, &mouse, &gamepads);
+    if !idle.tick(time.delta_secs(), input) || !resource_handles.is_all_done() {
+        return;
+    }
+    *idle = IdleTimer::default();
+
+    commands.insert_resource(AttractMode {
+        stats: stats.clone(),
+        player: player.clone(),
+        hook_stats: hook_stats.clone(),
+        controllers: controllers.clone(),
+    });
+    // 演示为单人关卡
+    commands.remove_resource::<VersusState>();
+    commands.remove_resource::<CoopState>();
+
+    let progression = progression_handle.get(&progression);
+    let custom = gameplay_handle.get(&gameplay).custom_difficulty;
+    let difficulty = Difficulty::Normal;
+    *stats = LevelStats::new_run(difficulty, difficulty.scaling(&custom), &progression.goal);
+    stats.is_first_init = false;
+    let mut rng = rand::rng();
+    for level in 1..=rng.random_range(1..=ATTRACT_MAX_LEVEL) {
+        stats.level = level;
+        stats.update_goal(&progression.goal);
+    }
+    stats.calculate_real_level(&progression, &mut rng);
+    *player = PlayerResource::default();
+    *hook_stats = HookStats::default();
+    controllers.set(PlayerSlot(0), Controller::Bot);
+    controllers.set(PlayerSlot(1), Controller::Human);
+
+    info!("Attract mode: demo level {}", stats.real_level_str);
+    next_screen.set(Screen::Gameplay);
+}
+
+fn spawn_attract_overlay(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Name::new("Attract Overlay"),
+        DespawnOnExit(Screen::Gameplay),
+        Text2d::new("DEMO - Press any key"),
+        TextFont {
+            font: asset_server.load("fonts/Kurland.ttf"),
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(COLOR_YELLOW),
+        Transform::from_translation(love_to_bevy_coords(160.0, 200.0).extend(20.0)),
+        Anchor::CENTER,
+        AttractOverlay(Timer::from_seconds(BLINK_SECONDS, TimerMode::Repeating)),
+    ));
+}
+
+fn blink_overlay(time: Res<Time>, mut q_overlay: Query<(&mut AttractOverlay, &mut Visibility)>) {
+    for (mut overlay, mut visibility) in &mut q_overlay {
+        if overlay.0.tick(time.delta()).just_finished() {
+            visibility.toggle_visible_hidden();
+        }
+    }
+}
+
+/// 有输入或演示关卡要切换界面时回到标题界面
+fn end_attract(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if any_input(&keys, &mouse, &gamepads) || !matches!(*next_screen, NextState::Unchanged) {
+        next_screen.set(Screen::Title);
+    }
+}
+
+/// 恢复进入演示前的玩家状态
+fn restore_player(
+    mut commands: Commands,
+    attract: Res<AttractMode>,
+    mut stats: ResMut<LevelStats>,
+    mut player: ResMut<PlayerResource>,
+    mut hook_stats: ResMut<HookStats>,
+    mut controllers: ResMut<Controllers>,
+) {
+    *stats = attract.stats.clone();
+    *player = attract.player.clone();
+    *hook_stats = attract.hook_stats.clone();
+    *controllers = attract.controllers.clone();
+    commands.remove_resource::<AttractMode>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_timer_resets_on_input() {
+        let mut idle = IdleTimer::default();
+        assert!(!idle.tick(ATTRACT_IDLE_SECONDS - 1.0, false));
+        assert!(!idle.tick(2.0, true));
+        assert!(!idle.tick(ATTRACT_IDLE_SECONDS - 1.0, false));
+        assert!(idle.tick(1.0, false));
+    }
+}
@@ -0,0 +1,82 @@
+//! 双人合作模式
+//!
+//! 两名矿工并排操作各自的钩子，共同完成同一个目标金额：
+//! - 按键与对战模式相同 (1P 使用 S/W 与第一个手柄，2P 使用方向键与第二个手柄)
+//! - 两人抓到的物品都计入 `LevelStats.money`，连击也由两人共同累计
+//! - 炸药放在全局 `PlayerResource` 中共用，力量与道具效果各自独立
+//! - 商店共用一个货架，Tab 切换购买者，道具归购买者所有并记录每人的花费
+//! - 合作期间存在 `CoopState` 资源，其他模式没有该资源
+
+use bevy::prelude::*;
+
+use crate::demo::player::{PlayerResource, PlayerSlot};
+
+/// 合作中一名玩家的状态
+#[derive(Debug, Clone, Default)]
+pub struct CoopPlayer {
+    /// 独立的道具栏 (炸药数量不使用，见 `Inventories`)
+    pub inventory: PlayerResource,
+    /// 在商店中花费的金钱
+    pub spent: u32,
+    /// 在商店中购买的道具数量
+    pub purchases: u32,
+}
+
+/// 合作状态，开始合作时插入，开始其他模式时移除
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CoopState {
+    pub players: [CoopPlayer; 2],
+    /// 商店中当前的购买者
+    pub shopper: PlayerSlot,
+}
+
+impl CoopState {
+    pub fn player(&self, slot: PlayerSlot) -> &CoopPlayer {
+        &self.players[slot.0]
+    }
+
+    pub fn player_mut(&mut self, slot: PlayerSlot) -> &mut CoopPlayer {
+        &mut self.players[slot.0]
+    }
+
+    /// 切换商店中的购买者
+    pub fn switch_shopper(&mut self) {
+        self.shopper = PlayerSlot((self.shopper.0 + 1) % self.players.len());
+    }
+
+    /// 记录当前购买者的一次购买
+    pub fn record_purchase(&mut self, price: u32) {
+        let shopper = self.player_mut(self.shopper);
+        shopper.spent += price;
+        shopper.purchases += 1;
+    }
+
+    /// 每人的花费，例如 "1P spent $120  2P spent $0"
+    pub fn spending_summary(&self) -> String {
+        (0..self.players.len())
+            .map(PlayerSlot)
+            .map(|slot| format!("{} spent ${}", slot.label(), self.player(slot).spent))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn purchases_are_split_by_shopper() {
+        let mut coop = CoopState::default();
+        coop.record_purchase(120);
+        coop.switch_shopper();
+        coop.record_purchase(30);
+        coop.record_purchase(50);
+        coop.switch_shopper();
+
+        assert_eq!(coop.shopper, PlayerSlot(0));
+        assert_eq!(coop.player(PlayerSlot(0)).spent, 120);
+        assert_eq!(coop.player(PlayerSlot(1)).purchases, 2);
+        assert_eq!(coop.spending_summary(), "1P spent $120  2P spent $80");
+    }
+}
@@ -0,0 +1,61 @@
+//! 每日挑战
+//!
+//! 以当天日期计算种子，同一天所有玩家得到相同的关卡序列、商店货架与价格：
+//! - 日期按玩家本地时区的日历日计算，完全离线，不需要服务器
+//! - 每天只有第一次挑战计分，之后的挑战只作练习
+//! - 存档记录每日挑战最高分与连续参加天数 (见 `DailyRecord`)
+
+use chrono::{Days, Local, NaiveDate};
+
+/// 今天的日期：本地日历日期自 1970-01-01 起的天数 (网页版使用浏览器时区)
+pub fn today() -> u32 {
+    days_since_epoch(Local::now().date_naive())
+}
+
+fn epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()
+}
+
+fn days_since_epoch(date: NaiveDate) -> u32 {
+    date.signed_duration_since(epoch()).num_days().max(0) as u32
+}
+
+/// 由日期计算当天的种子 (SplitMix64)，同一天总是得到相同的值
+pub fn daily_seed(day: u32) -> u64 {
+    let mut z = (day as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// 将天数格式化为 "YYYY-MM-DD"
+pub fn format_date(day: u32) -> String {
+    (epoch() + Days::new(day as u64))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_is_stable_per_day() {
+        assert_eq!(daily_seed(20744), daily_seed(20744));
+        assert_ne!(daily_seed(20744), daily_seed(20745));
+    }
+
+    #[test]
+    fn formats_days_as_calendar_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(19782), "2024-02-29");
+        assert_eq!(format_date(20744), "2026-10-18");
+    }
+
+    #[test]
+    fn local_dates_count_days_since_epoch() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        assert_eq!(days_since_epoch(date), 20744);
+        assert_eq!(format_date(days_since_epoch(date)), "2026-10-18");
+    }
+}
@@ -0,0 +1,156 @@
+//! 无尽模式
+//!
+//! 每通过一关随机叠加一个修饰，难度逐关上升：
+//! - 地鼠更快、石头更多、时间更短、关卡更暗、商店更贵
+//! - 当前修饰显示在下一关目标界面
+//! - 最深到达的关卡单独记录在存档中
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use rand::Rng;
+
+use crate::screens::{Screen, stats::LevelStats};
+use crate::utils::love_to_bevy_coords;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_darkness);
+}
+
+/// 每层“更暗”修饰增加的遮罩透明度与上限
+const DARKNESS_PER_STACK: f32 = 0.15;
+const MAX_DARKNESS: f32 = 0.6;
+
+/// 无尽模式的关卡修饰
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndlessModifier {
+    FasterMoles,
+    MoreRocks,
+    ShorterTimer,
+    DarkerLevel,
+    PricierShop,
+}
+
+impl EndlessModifier {
+    pub const ALL: [EndlessModifier; 5] = [
+        EndlessModifier::FasterMoles,
+        EndlessModifier::MoreRocks,
+        EndlessModifier::ShorterTimer,
+        EndlessModifier::DarkerLevel,
+        EndlessModifier::PricierShop,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EndlessModifier::FasterMoles => "Faster Moles",
+            EndlessModifier::MoreRocks => "More Rocks",
+            EndlessModifier::ShorterTimer => "Shorter Timer",
+            EndlessModifier::DarkerLevel => "Darker Level",
+            EndlessModifier::PricierShop => "Pricier Shop",
+        }
+    }
+}
+
+/// 本局已获得的修饰，同一修饰可以叠加
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EndlessModifiers {
+    active: Vec<EndlessModifier>,
+}
+
+impl EndlessModifiers {
+    /// 随机叠加一个修饰
+    pub fn add_random(&mut self, rng: &mut impl Rng) -> EndlessModifier {
+        let modifier = EndlessModifier::ALL[rng.random_range(0..EndlessModifier::ALL.len())];
+        self.add(modifier);
+        modifier
+    }
+
+    pub fn add(&mut self, modifier: EndlessModifier) {
+        self.active.push(modifier);
+    }
+
+    /// 某个修饰的叠加层数
+    pub fn stacks(&self, modifier: EndlessModifier) -> u32 {
+        self.active.iter().filter(|m| **m == modifier).count() as u32
+    }
+
+    /// 地鼠移动速度倍率：每层 +25%
+    pub fn mole_speed_scale(&self) -> f32 {
+        1.0 + 0.25 * self.stacks(EndlessModifier::FasterMoles) as f32
+    }
+
+    /// 额外生成的石头数量：每层 2 块
+    pub fn extra_rocks(&self) -> usize {
+        2 * self.stacks(EndlessModifier::MoreRocks) as usize
+    }
+
+    /// 关卡时长倍率：每层 -10%，最低一半
+    pub fn timer_scale(&self) -> f32 {
+        (1.0 - 0.1 * self.stacks(EndlessModifier::ShorterTimer) as f32).max(0.5)
+    }
+
+    /// 遮罩透明度
+    pub fn darkness(&self) -> f32 {
+        (DARKNESS_PER_STACK * self.stacks(EndlessModifier::DarkerLevel) as f32).min(MAX_DARKNESS)
+    }
+
+    /// 商店价格倍率：每层 +20%
+    pub fn price_scale(&self) -> f32 {
+        1.0 + 0.2 * self.stacks(EndlessModifier::PricierShop) as f32
+    }
+
+    /// 用于界面展示的修饰列表，例如 "Faster Moles x2"
+    pub fn summary(&self) -> Vec<String> {
+        EndlessModifier::ALL
+            .iter()
+            .filter_map(|modifier| match self.stacks(*modifier) {
+                0 => None,
+                1 => Some(modifier.label().to_string()),
+                stacks => Some(format!("{} x{stacks}", modifier.label())),
+            })
+            .collect()
+    }
+}
+
+/// “更暗”修饰：覆盖矿区的半透明遮罩
+fn spawn_darkness(mut commands: Commands, stats: Res<LevelStats>) {
+    let darkness = stats.modifiers.darkness();
+    if darkness <= 0.0 {
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Endless Darkness"),
+        Sprite::from_color(
+            Color::srgba(0.0, 0.0, 0.0, darkness),
+            Vec2::new(320.0, 200.0),
+        ),
+        Transform::from_translation(love_to_bevy_coords(0.0, 40.0).extend(5.0)),
+        Anchor::TOP_LEFT,
+        DespawnOnExit(Screen::Gameplay),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modifiers_stack_and_clamp() {
+        let mut modifiers = EndlessModifiers::default();
+        for _ in 0..6 {
+            modifiers.add(EndlessModifier::ShorterTimer);
+        }
+        modifiers.add(EndlessModifier::DarkerLevel);
+        modifiers.add(EndlessModifier::FasterMoles);
+        modifiers.add(EndlessModifier::FasterMoles);
+
+        assert_eq!(modifiers.timer_scale(), 0.5);
+        assert_eq!(modifiers.darkness(), DARKNESS_PER_STACK);
+        assert_eq!(modifiers.mole_speed_scale(), 1.5);
+        assert_eq!(modifiers.extra_rocks(), 0);
+        assert_eq!(
+            modifiers.summary(),
+            vec!["Faster Moles x2", "Shorter Timer x6", "Darker Level"]
+        );
+    }
+}
@@ -2,8 +2,16 @@
 
 use crate::config::ImageAssets;
 use crate::constants::COLOR_YELLOW;
+use crate::demo::hook::HookStats;
 use crate::demo::player::PlayerResource;
-use crate::screens::{Screen, persistent::PersistentData, stats::LevelStats};
+use crate::online::OnlineSession;
+use crate::screens::{
+    Screen,
+    coop::CoopState,
+    persistent::PersistentData,
+    stats::{GameMode, LevelStats},
+    time_attack::format_time,
+};
 use crate::utils::love_to_bevy_coords;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
@@ -27,9 +35,13 @@ fn spawn_game_over_ui(
     image_assets: Res<ImageAssets>,
     stats: Res<LevelStats>,
     persistent: Res<PersistentData>,
+    coop: Option<Res<CoopState>>,
+    online: Option<Res<OnlineSession>>,
 ) {
     // 检查是否刷新最高分（但不立即更新，等按键时再更新）
-    let is_new_high_score = stats.money > persistent.high_score;
+    // 无尽模式与每日挑战单独记录，不参与各难度的最高分排名
+    let is_new_high_score =
+        stats.mode == GameMode::Campaign && stats.money > persistent.best(stats.difficulty).score;
     commands.insert_resource(IsNewHighScore(is_new_high_score));
 
     // 背景
@@ -62,7 +74,53 @@ fn spawn_game_over_ui(
     let font = asset_server.load("fonts/Kurland.ttf");
     commands.spawn((
         Name::new("GameOver Text"),
-        Text2d::new("You didn't reach the\ngoal!"),
+        Text2d::new(match stats.mode {
+            GameMode::Campaign => "You didn't reach the\ngoal!".to_string(),
+            GameMode::Endless => {
+                let best = persistent.endless_best_depth.max(stats.level);
+                format!("You reached depth {}!\nBest depth: {best}", stats.level)
+            }
+            GameMode::Daily if stats.daily_scored => {
+                let best = persistent.daily.best_score.max(stats.money);
+                format!(
+                    "Daily score: ${}\nBest: ${best}  Streak: {}",
+                    stats.money, persistent.daily.streak
+                )
+            }
+            GameMode::Daily => format!("Practice score: ${}\nCome back tomorrow!", stats.money),
+            GameMode::Versus => "Match over!".to_string(),
+            GameMode::Online => format!(
+                "You L{} ${}\n{}",
+                stats.level,
+                stats.money,
+                online
+                    .map(|online| online.rival.summary())
+                    .unwrap_or_default()
+            ),
+            GameMode::Coop => format!(
+                "Team reached level {}!\n{}",
+                stats.level,
+                coop.map(|coop| coop.spending_summary()).unwrap_or_default()
+            ),
+            GameMode::Practice => format!(
+                "Practice {} over\nMoney: ${}",
+                stats.real_level_str, stats.money
+            ),
+            GameMode::TimeAttack if stats.time_attack.finished => format!(
+                "Cleared in {}!\nBest: {}",
+                format_time(stats.time_attack.total()),
+                persistent
+                    .time_attack
+                    .best_total
+                    .map(format_time)
+                    .unwrap_or_default()
+            ),
+            GameMode::TimeAttack => format!(
+                "Failed on level {}\nTime: {}",
+                stats.level,
+                format_time(stats.time_attack.total() + stats.time_attack.elapsed)
+            ),
+        }),
         TextFont {
             font: font.clone(),
             font_size: 20.0,
@@ -110,17 +168,33 @@ fn check_keyboard_input(
 
     if pressed {
         if is_new_high_score.0 {
-            persistent.high_score = stats.money;
-            persistent.high_level = stats.level;
+            persistent.record(stats.difficulty, stats.money, stats.level);
             persistent.save();
             next_screen.set(Screen::NewHighScore);
         } else {
+            if stats.mode == GameMode::Endless && stats.level > persistent.endless_best_depth {
+                persistent.endless_best_depth = stats.level;
+                persistent.save();
+            }
+            if stats.mode == GameMode::Daily && stats.daily_scored {
+                persistent.daily.finish_attempt(stats.money);
+                persistent.save();
+            }
             next_screen.set(Screen::Title);
         }
     }
 }
 
-fn reset_game_state(mut stats: ResMut<LevelStats>, mut player: ResMut<PlayerResource>) {
-    *stats = LevelStats::default();
+fn reset_game_state(
+    mut stats: ResMut<LevelStats>,
+    mut player: ResMut<PlayerResource>,
+    mut hook_stats: ResMut<HookStats>,
+) {
+    // 保留本局难度，新纪录界面按记录时使用的难度显示
+    *stats = LevelStats {
+        difficulty: stats.difficulty,
+        ..default()
+    };
     *player = PlayerResource::default();
+    *hook_stats = HookStats::default();
 }
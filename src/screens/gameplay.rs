@@ -1,10 +1,18 @@
 use crate::{
+    demo::player::Inventories,
     menus::Menu,
-    screens::{Screen, stats::LevelStats},
+    screens::{
+        Screen,
+        stats::{GameMode, LevelStats},
+    },
 };
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
+/// 秒表道具增加的开局时间 (秒)
+pub const STOPWATCH_BONUS_SECONDS: f32 = 15.0;
+
 pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), apply_stopwatch);
     // Toggle pause on key press.
     app.add_systems(
         Update,
@@ -20,19 +28,39 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnExit(Screen::Gameplay), close_menu);
 }
 
+/// 持有秒表时，关卡开始即获得额外时间 (双人模式中任意一人持有即可)
+fn apply_stopwatch(inventories: Inventories, mut stats: ResMut<LevelStats>) {
+    if inventories
+        .slots()
+        .any(|slot| inventories.get(slot).has_stopwatch)
+    {
+        stats.add_time(STOPWATCH_BONUS_SECONDS);
+    }
+}
+
 fn update_gameplay_timer(
     time: Res<Time>,
     mut stats: ResMut<LevelStats>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
+    if !stats.timed {
+        return;
+    }
+
     stats.timer -= time.delta_secs();
     if stats.timer <= 0.0 {
         stats.timer = 0.0;
-        if stats.reach_goal() {
-            next_screen.set(Screen::MadeGoal);
-        } else {
-            next_screen.set(Screen::GameOver);
-        }
+        next_screen.set(level_end_screen(&stats));
+    }
+}
+
+/// 关卡结束后进入的界面：练习模式只进行一关，对战模式每回合结束后显示比分
+pub fn level_end_screen(stats: &LevelStats) -> Screen {
+    match stats.mode {
+        GameMode::Practice => Screen::GameOver,
+        GameMode::Versus => Screen::VersusResult,
+        _ if stats.reach_goal() => Screen::MadeGoal,
+        _ => Screen::GameOver,
     }
 }
 
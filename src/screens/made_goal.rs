@@ -1,9 +1,15 @@
 //! 达成目标过渡界面
 
 use crate::audio::{AudioAssets, TransitionMusicStatus, play_transition_music};
-use crate::config::ImageAssets;
-use crate::constants::COLOR_YELLOW;
-use crate::screens::{Screen, stats::LevelStats};
+use crate::config::{ImageAssets, ProgressionConfig};
+use crate::constants::{COLOR_GREEN, COLOR_YELLOW};
+use crate::demo::level::ProgressionHandle;
+use crate::screens::{
+    Screen,
+    persistent::PersistentData,
+    stats::{GameMode, LevelStats, RngStream},
+    time_attack::format_time,
+};
 use crate::utils::love_to_bevy_coords;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
@@ -17,19 +23,65 @@ pub(super) fn plugin(app: &mut App) {
 struct MadeGoalTimer {
     timer: Timer,
     music_finished: bool,
+    /// 过渡结束后进入的界面：通常为商店，限时挑战完成全部关卡后直接结束
+    next: Screen,
 }
 
-fn spawn_made_goal_ui(
+/// 生成过关界面，并把关卡数推进到下一关
+pub fn spawn_made_goal_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     image_assets: Res<ImageAssets>,
     mut stats: ResMut<LevelStats>,
     audio_assets: Res<AudioAssets>,
     mut transition_music: ResMut<TransitionMusicStatus>,
+    progression_handle: Res<ProgressionHandle>,
+    progression: Res<Assets<ProgressionConfig>>,
+    mut persistent: ResMut<PersistentData>,
 ) {
-    // 增加等级并计算实际关卡配置
+    let progression = progression_handle.get(&progression);
+
+    // 限时挑战：记录本关分段用时，与存档中的最好成绩比较
+    let mut next = Screen::Shop;
+    let mut split_text = None;
+    if stats.mode == GameMode::TimeAttack {
+        let split = stats.time_attack.finish_level();
+        let best = persistent
+            .time_attack
+            .best_splits
+            .get(&stats.real_level_str)
+            .copied();
+        let is_best = persistent
+            .time_attack
+            .record_split(&stats.real_level_str, split);
+        let mut lines = vec![format!("Split {}", format_time(split))];
+        lines.push(match best {
+            Some(best) if is_best => format!("New best! (was {})", format_time(best)),
+            Some(best) => format!("Best {}", format_time(best)),
+            None => "First clear!".to_string(),
+        });
+
+        if stats.level as usize >= progression.time_attack.len() {
+            stats.time_attack.finished = true;
+            persistent
+                .time_attack
+                .record_total(stats.time_attack.total());
+            next = Screen::GameOver;
+        }
+        persistent.save();
+        split_text = Some(lines.join("\n"));
+    }
+
+    // 增加等级并按关卡池计算实际关卡配置
     stats.level += 1;
-    stats.calculate_real_level();
+    let mut rng = stats.rng(RngStream::Level);
+    stats.calculate_real_level(&progression, &mut rng);
+
+    // 无尽模式：每通过一关叠加一个随机修饰
+    if stats.mode == GameMode::Endless {
+        let modifier = stats.modifiers.add_random(&mut rng);
+        info!("Endless modifier added: {}", modifier.label());
+    }
     stats.reset_timer();
 
     play_transition_music(
@@ -70,7 +122,11 @@ fn spawn_made_goal_ui(
     let font = asset_server.load("fonts/Kurland.ttf");
     commands.spawn((
         Name::new("Goal Text"),
-        Text2d::new("You made it to\nthe next Level!"),
+        Text2d::new(if stats.time_attack.finished {
+            "You cleared\nall the Levels!"
+        } else {
+            "You made it to\nthe next Level!"
+        }),
         TextFont {
             font: font.clone(),
             font_size: 20.0,
@@ -82,11 +138,29 @@ fn spawn_made_goal_ui(
         DespawnOnExit(Screen::MadeGoal),
     ));
 
+    // 限时挑战的分段用时
+    if let Some(text) = split_text {
+        commands.spawn((
+            Name::new("Split Text"),
+            Text2d::new(text),
+            TextFont {
+                font: asset_server.load("fonts/Pixel-Square-10-1.ttf"),
+                font_size: 10.0,
+                ..default()
+            },
+            TextColor(COLOR_GREEN),
+            Transform::from_translation(love_to_bevy_coords(70.0, 150.0).extend(1.0)),
+            Anchor::TOP_LEFT,
+            DespawnOnExit(Screen::MadeGoal),
+        ));
+    }
+
     // 延迟计时器（音乐播放完成后开始计时）
     commands.spawn((
         MadeGoalTimer {
             timer: Timer::from_seconds(0.5, TimerMode::Once),
             music_finished: false,
+            next,
         },
         DespawnOnExit(Screen::MadeGoal),
     ));
@@ -107,7 +181,7 @@ fn check_transition(
         }
 
         if timer.timer.tick(time.delta()).just_finished() {
-            next_screen.set(Screen::Shop);
+            next_screen.set(timer.next);
             return;
         }
     }
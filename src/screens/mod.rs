@@ -1,28 +1,39 @@
 //! The game's main screen states and transitions between them.
 
+mod attract;
+pub mod coop;
+pub mod daily;
+pub mod endless;
 mod game_over;
-mod gameplay;
+pub mod gameplay;
 mod loading;
-mod made_goal;
+pub mod made_goal;
 mod new_high_score;
 mod next_goal;
 pub mod persistent;
-mod shop;
+pub mod practice;
+pub mod shop;
 mod splash;
 pub mod stats;
+pub mod time_attack;
 mod title;
+pub mod versus;
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
     app.init_resource::<stats::LevelStats>();
+    app.init_resource::<stats::SelectedDifficulty>();
+    app.init_resource::<stats::SelectedMode>();
     app.init_resource::<persistent::PersistentData>();
 
     app.add_systems(Startup, load_persistent_data);
 
     app.add_plugins((
         gameplay::plugin,
+        endless::plugin,
+        time_attack::plugin,
         loading::plugin,
         splash::plugin,
         title::plugin,
@@ -31,6 +42,8 @@ pub(super) fn plugin(app: &mut App) {
         shop::plugin,
         game_over::plugin,
         new_high_score::plugin,
+        versus::plugin,
+        attract::plugin,
     ));
 
     app.add_systems(Update, handle_global_exit);
@@ -69,4 +82,5 @@ pub enum Screen {
     Shop,
     GameOver,
     NewHighScore,
+    VersusResult,
 }
@@ -2,7 +2,7 @@
 
 use crate::config::ImageAssets;
 use crate::constants::{COLOR_GREEN, COLOR_YELLOW};
-use crate::screens::{Screen, persistent::PersistentData};
+use crate::screens::{Screen, persistent::PersistentData, stats::LevelStats};
 use crate::utils::love_to_bevy_coords;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
@@ -20,7 +20,11 @@ fn spawn_new_high_score_ui(
     asset_server: Res<AssetServer>,
     image_assets: Res<ImageAssets>,
     persistent: Res<PersistentData>,
+    stats: Res<LevelStats>,
 ) {
+    // 与 GameOver 记录最高分时使用同一个难度 (离开 GameOver 重置时保留)
+    let record = persistent.best(stats.difficulty);
+
     // 背景
     commands.spawn((
         Name::new("Goal Background"),
@@ -51,7 +55,7 @@ fn spawn_new_high_score_ui(
     let font = asset_server.load("fonts/Kurland.ttf");
     commands.spawn((
         Name::new("NewHighScore Label"),
-        Text2d::new("New High Score:"),
+        Text2d::new(format!("New {} Record:", stats.difficulty.label())),
         TextFont {
             font: font.clone(),
             font_size: 20.0,
@@ -66,7 +70,7 @@ fn spawn_new_high_score_ui(
     // 金额 (绿色)
     commands.spawn((
         Name::new("HighScore Amount"),
-        Text2d::new(format!("${}", persistent.high_score)),
+        Text2d::new(format!("${}", record.score)),
         TextFont {
             font: font.clone(),
             font_size: 25.0,
@@ -81,7 +85,7 @@ fn spawn_new_high_score_ui(
     // at LevelX (黄色)
     commands.spawn((
         Name::new("HighScore Level"),
-        Text2d::new(format!("at Level{}", persistent.high_level)),
+        Text2d::new(format!("at Level{}", record.level)),
         TextFont {
             font: font.clone(),
             font_size: 20.0,
@@ -1,22 +1,137 @@
 //! 显示下一关目标界面
 
 use crate::audio::{AudioAssets, TransitionMusicStatus, play_transition_music};
-use crate::config::ImageAssets;
+use crate::config::{GameplayConfig, ImageAssets, ProgressionConfig};
 use crate::constants::{COLOR_GREEN, COLOR_YELLOW};
+use crate::demo::hook::HookStats;
+use crate::demo::level::{GameplayHandle, ProgressionHandle};
 use crate::demo::player::PlayerResource;
-use crate::screens::{Screen, stats::LevelStats};
+use crate::online::OnlineSession;
+use crate::screens::{
+    Screen,
+    coop::CoopState,
+    daily,
+    persistent::PersistentData,
+    practice::PracticeSetup,
+    stats::{Difficulty, GameMode, LevelStats, RngStream, SelectedDifficulty, SelectedMode},
+    time_attack::format_time,
+    versus::{VERSUS_ROUNDS, VersusState},
+};
 use crate::utils::love_to_bevy_coords;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::NextGoal), spawn_next_goal_ui);
+    app.add_systems(
+        OnEnter(Screen::NextGoal),
+        (start_new_run, spawn_next_goal_ui).chain(),
+    );
     app.add_systems(Update, check_transition.run_if(in_state(Screen::NextGoal)));
 }
 
 #[derive(Component)]
 struct NextGoalTimer(Timer);
 
+/// 如果是第一次进入（新游戏），按选择的模式与难度重置所有游戏状态
+fn start_new_run(
+    mut commands: Commands,
+    mut stats: ResMut<LevelStats>,
+    mut player: ResMut<PlayerResource>,
+    mut hook_stats: ResMut<HookStats>,
+    mut persistent: ResMut<PersistentData>,
+    selected: Res<SelectedDifficulty>,
+    selected_mode: Res<SelectedMode>,
+    gameplay_handle: Res<GameplayHandle>,
+    gameplay: Res<Assets<GameplayConfig>>,
+    progression_handle: Res<ProgressionHandle>,
+    progression: Res<Assets<ProgressionConfig>>,
+    practice: Res<PracticeSetup>,
+    online: Option<Res<OnlineSession>>,
+) {
+    if !stats.is_first_init {
+        return;
+    }
+
+    let progression = progression_handle.get(&progression);
+    let custom = gameplay_handle.get(&gameplay).custom_difficulty;
+    // 每日挑战、限时挑战与练习模式固定使用 Normal 难度
+    let difficulty = match selected_mode.0 {
+        GameMode::Daily
+        | GameMode::TimeAttack
+        | GameMode::Practice
+        | GameMode::Versus
+        | GameMode::Online => Difficulty::Normal,
+        _ => selected.0,
+    };
+
+    *stats = LevelStats::new_run(difficulty, difficulty.scaling(&custom), &progression.goal);
+    stats.mode = selected_mode.0;
+    // 每局都有种子，遥测日志可以据此复现；每日挑战与联网竞速下面替换为共享种子
+    stats.seed = Some(rand::random());
+    *player = PlayerResource::default();
+    *hook_stats = HookStats::default();
+
+    // 对战期间存在 VersusState，其他模式确保移除
+    if stats.mode == GameMode::Versus {
+        commands.insert_resource(VersusState::default());
+    } else {
+        commands.remove_resource::<VersusState>();
+    }
+    // 合作期间存在 CoopState
+    if stats.mode == GameMode::Coop {
+        commands.insert_resource(CoopState::default());
+    } else {
+        commands.remove_resource::<CoopState>();
+    }
+
+    // 练习模式：直接进入选中的关卡，目标金额与按正常进度到达该关卡编号时一致
+    if stats.mode == GameMode::Practice {
+        stats.real_level_str = practice.level.clone();
+        stats.timed = practice.timed;
+        let layout = stats.layout_number();
+        for level in 1..layout {
+            stats.level = level;
+            stats.update_goal(&progression.goal);
+        }
+        stats.level = layout;
+        practice.apply(&mut player);
+    }
+
+    if stats.mode == GameMode::TimeAttack {
+        let mut rng = stats.rng(RngStream::Level);
+        stats.calculate_real_level(&progression, &mut rng);
+    }
+
+    // 联网竞速：双方使用中继下发的同一个种子
+    if stats.mode == GameMode::Online {
+        if let Some(seed) = online.and_then(|online| online.seed()) {
+            stats.seed = Some(seed);
+        }
+        let mut rng = stats.rng(RngStream::Level);
+        stats.calculate_real_level(&progression, &mut rng);
+    }
+
+    if stats.mode == GameMode::Daily {
+        let today = daily::today();
+        stats.daily_day = today;
+        stats.seed = Some(daily::daily_seed(today));
+        let mut rng = stats.rng(RngStream::Level);
+        stats.calculate_real_level(&progression, &mut rng);
+
+        // 每天只有第一次挑战计分，开始时即记录，中途退出也算一次
+        stats.daily_scored = persistent.daily.can_attempt(today);
+        if stats.daily_scored {
+            persistent.daily.start_attempt(today);
+            persistent.save();
+        }
+        info!(
+            "Daily challenge {} (scored: {})",
+            daily::format_date(today),
+            stats.daily_scored
+        );
+    }
+}
+
 fn spawn_next_goal_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -24,18 +139,18 @@ fn spawn_next_goal_ui(
     mut stats: ResMut<LevelStats>,
     audio_assets: Res<AudioAssets>,
     mut transition_music: ResMut<TransitionMusicStatus>,
-    mut player: ResMut<PlayerResource>,
+    progression_handle: Res<ProgressionHandle>,
+    progression: Res<Assets<ProgressionConfig>>,
 ) {
-    // 如果是第一次进入（新游戏），重置所有游戏状态
-    if stats.is_first_init {
-        *stats = LevelStats::default();
-        *player = PlayerResource::default();
-    }
+    let curve = progression_handle.get(&progression).goal;
 
     // 更新目标金额
-    stats.update_goal();
+    stats.update_goal(&curve);
 
-    let goal_text = if stats.is_first_init {
+    let goal_text = if stats.mode == GameMode::Versus {
+        stats.is_first_init = false;
+        "Versus Round"
+    } else if stats.is_first_init {
         stats.is_first_init = false;
         "Your First Goal is"
     } else {
@@ -95,7 +210,11 @@ fn spawn_next_goal_ui(
     // 金额文字
     commands.spawn((
         Name::new("Goal Amount"),
-        Text2d::new(format!("${}", stats.goal)),
+        Text2d::new(if stats.mode == GameMode::Versus {
+            format!("{}/{VERSUS_ROUNDS}", stats.level)
+        } else {
+            format!("${}", stats.goal)
+        }),
         TextFont {
             font: font.clone(),
             font_size: 30.0,
@@ -107,6 +226,51 @@ fn spawn_next_goal_ui(
         DespawnOnExit(Screen::NextGoal),
     ));
 
+    // 无尽模式列出当前叠加的修饰，每日挑战显示日期与是否计分，限时挑战显示进度与已用时间
+    let mode_text = match stats.mode {
+        GameMode::Campaign => None,
+        GameMode::Endless => Some(
+            std::iter::once("Endless".to_string())
+                .chain(stats.modifiers.summary())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+        GameMode::Daily => Some(format!(
+            "Daily {}\n{}",
+            daily::format_date(stats.daily_day),
+            if stats.daily_scored {
+                "Scored run"
+            } else {
+                "Practice"
+            }
+        )),
+        GameMode::Practice => Some(format!("Practice {}", stats.real_level_str)),
+        GameMode::Versus => Some("1P: S / W\n2P: Down / Up".to_string()),
+        GameMode::Coop => Some("Co-op\n1P: S / W\n2P: Down / Up".to_string()),
+        GameMode::Online => Some("Online Race".to_string()),
+        GameMode::TimeAttack => Some(format!(
+            "Time Attack {}/{}\nTime {}",
+            stats.level,
+            progression_handle.get(&progression).time_attack.len(),
+            format_time(stats.time_attack.total())
+        )),
+    };
+    if let Some(text) = mode_text {
+        commands.spawn((
+            Name::new("Mode Info"),
+            Text2d::new(text),
+            TextFont {
+                font: asset_server.load("fonts/Pixel-Square-10-1.ttf"),
+                font_size: 10.0,
+                ..default()
+            },
+            TextColor(COLOR_YELLOW),
+            Transform::from_translation(love_to_bevy_coords(190.0, 125.0).extend(1.0)),
+            Anchor::TOP_LEFT,
+            DespawnOnExit(Screen::NextGoal),
+        ));
+    }
+
     // 转换计时器（备用，用于处理音乐加载失败的情况）
     commands.spawn((
         NextGoalTimer(Timer::from_seconds(5.0, TimerMode::Once)),
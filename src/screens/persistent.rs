@@ -1,17 +1,139 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
+use crate::screens::stats::Difficulty;
+
 const SAVE_FILE: &str = "savedata.txt";
+/// 遥测日志目录，与存档位于同一目录
+pub const TELEMETRY_DIR: &str = "telemetry";
+
+/// 单个难度的最高分记录
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighScore {
+    pub score: u32,
+    pub level: u32,
+}
 
 #[derive(Resource, Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PersistentData {
+    /// Normal 难度的最高分 (兼容旧存档)
+    #[serde(default)]
     pub high_score: u32,
+    #[serde(default)]
     pub high_level: u32,
+    /// 各难度的最高分
+    #[serde(default)]
+    pub high_scores: BTreeMap<Difficulty, HighScore>,
+    /// 无尽模式最深到达的关卡
+    #[serde(default)]
+    pub endless_best_depth: u32,
+    /// 每日挑战记录
+    #[serde(default)]
+    pub daily: DailyRecord,
+    /// 限时挑战记录
+    #[serde(default)]
+    pub time_attack: TimeAttackRecord,
+    /// 是否记录遥测日志 (见 `telemetry`)
+    #[serde(default)]
+    pub telemetry: bool,
+}
+
+/// 每日挑战记录，日期为本地日历日期自 1970-01-01 起的天数
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyRecord {
+    /// 最近一次计分挑战的日期
+    pub last_day: Option<u32>,
+    /// 最近一次计分挑战的得分
+    pub last_score: u32,
+    /// 每日挑战历史最高分
+    pub best_score: u32,
+    /// 连续参加每日挑战的天数
+    pub streak: u32,
+}
+
+impl DailyRecord {
+    /// 今天是否还可以计分
+    pub fn can_attempt(&self, today: u32) -> bool {
+        self.last_day.is_none_or(|day| day < today)
+    }
+
+    /// 开始今天的计分挑战：昨天参加过则连续天数 +1，否则重新计数
+    pub fn start_attempt(&mut self, today: u32) {
+        self.streak = match self.last_day {
+            Some(day) if day + 1 == today => self.streak + 1,
+            _ => 1,
+        };
+        self.last_day = Some(today);
+        self.last_score = 0;
+    }
+
+    /// 结束计分挑战，返回是否刷新最高分
+    pub fn finish_attempt(&mut self, score: u32) -> bool {
+        self.last_score = score;
+        let is_best = score > self.best_score;
+        self.best_score = self.best_score.max(score);
+        is_best
+    }
+}
+
+/// 限时挑战的最好成绩 (秒)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimeAttackRecord {
+    /// 各关卡 (例如 "L3_1") 的最好分段用时
+    pub best_splits: BTreeMap<String, f32>,
+    /// 完成全部关卡的最好总用时
+    pub best_total: Option<f32>,
+}
+
+impl TimeAttackRecord {
+    /// 记录关卡分段用时，返回是否刷新该关最好成绩
+    pub fn record_split(&mut self, level: &str, seconds: f32) -> bool {
+        let is_best = self
+            .best_splits
+            .get(level)
+            .is_none_or(|best| seconds < *best);
+        if is_best {
+            self.best_splits.insert(level.to_string(), seconds);
+        }
+        is_best
+    }
+
+    /// 记录总用时，返回是否刷新最好成绩
+    pub fn record_total(&mut self, seconds: f32) -> bool {
+        let is_best = self.best_total.is_none_or(|best| seconds < best);
+        if is_best {
+            self.best_total = Some(seconds);
+        }
+        is_best
+    }
 }
 
 impl PersistentData {
+    /// 指定难度的最高分，旧存档中的记录视为 Normal 难度
+    pub fn best(&self, difficulty: Difficulty) -> HighScore {
+        match self.high_scores.get(&difficulty) {
+            Some(record) => *record,
+            None if difficulty == Difficulty::Normal => HighScore {
+                score: self.high_score,
+                level: self.high_level,
+            },
+            None => HighScore::default(),
+        }
+    }
+
+    /// 记录指定难度的新最高分
+    pub fn record(&mut self, difficulty: Difficulty, score: u32, level: u32) {
+        self.high_scores
+            .insert(difficulty, HighScore { score, level });
+        if difficulty == Difficulty::Normal {
+            self.high_score = score;
+            self.high_level = level;
+        }
+    }
+
     pub fn load() -> Self {
         let path = Path::new(SAVE_FILE);
         if path.exists() {
@@ -60,3 +182,73 @@ impl PersistentData {
         Self::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_save_counts_as_normal_difficulty() {
+        let data: PersistentData =
+            serde_json::from_str(r#"{"high_score": 1200, "high_level": 4}"#).unwrap();
+
+        assert_eq!(
+            data.best(Difficulty::Normal),
+            HighScore {
+                score: 1200,
+                level: 4
+            }
+        );
+        assert_eq!(data.best(Difficulty::Hard), HighScore::default());
+    }
+
+    #[test]
+    fn records_are_kept_per_difficulty() {
+        let mut data = PersistentData::default();
+
+        data.record(Difficulty::Hard, 900, 3);
+        data.record(Difficulty::Easy, 2000, 6);
+
+        let json = serde_json::to_string(&data).unwrap();
+        let loaded: PersistentData = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.best(Difficulty::Hard).score, 900);
+        assert_eq!(loaded.best(Difficulty::Easy).level, 6);
+        assert_eq!(loaded.best(Difficulty::Normal), HighScore::default());
+    }
+
+    #[test]
+    fn daily_attempts_track_streak_and_best() {
+        let mut daily = DailyRecord::default();
+
+        assert!(daily.can_attempt(100));
+        daily.start_attempt(100);
+        assert!(!daily.can_attempt(100));
+        assert!(daily.finish_attempt(800));
+
+        daily.start_attempt(101);
+        assert!(!daily.finish_attempt(500));
+        assert_eq!(
+            (daily.streak, daily.best_score, daily.last_score),
+            (2, 800, 500)
+        );
+
+        // 中断一天后重新计数
+        daily.start_attempt(103);
+        assert_eq!(daily.streak, 1);
+    }
+
+    #[test]
+    fn time_attack_keeps_fastest_times() {
+        let mut record = TimeAttackRecord::default();
+
+        assert!(record.record_split("L1_1", 20.0));
+        assert!(!record.record_split("L1_1", 25.0));
+        assert!(record.record_split("L1_1", 18.5));
+        assert!(record.record_total(90.0));
+        assert!(!record.record_total(95.0));
+
+        assert_eq!(record.best_splits["L1_1"], 18.5);
+        assert_eq!(record.best_total, Some(90.0));
+    }
+}
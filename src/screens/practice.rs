@@ -0,0 +1,196 @@
+//! 练习模式
+//!
+//! 从关卡选择菜单直接进入 levels.yaml 中的任意关卡 (包括 `LDEBUG`)：
+//! - 可选择是否计时、开局携带的炸药、力量与商店道具
+//! - 只进行一关，结束后回到标题界面，不影响任何最高分记录
+
+use bevy::prelude::*;
+
+use crate::demo::player::PlayerResource;
+
+/// 炸药数量上限 (与 HUD 图标数量一致)
+const MAX_DYNAMITE: i32 = 12;
+/// 力量范围与调整步长 (与力量道具的上限一致)
+const MIN_STRENGTH: f32 = 1.0;
+const MAX_STRENGTH: f32 = 6.0;
+const STRENGTH_STEP: f32 = 0.5;
+
+/// 关卡选择菜单中的练习设置，开始练习时写入 `LevelStats` 与 `PlayerResource`
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct PracticeSetup {
+    /// 关卡 ID (例如 "L7_2")
+    pub level: String,
+    /// 是否计时，不计时的关卡只能按 Skip 结束
+    pub timed: bool,
+    pub dynamite: i32,
+    pub strength: f32,
+    pub strength_drink: bool,
+    pub lucky_clover: bool,
+    pub rock_collectors_book: bool,
+    pub gem_polish: bool,
+    pub stopwatch: bool,
+}
+
+impl Default for PracticeSetup {
+    fn default() -> Self {
+        Self {
+            level: "L1_1".to_string(),
+            timed: true,
+            dynamite: 0,
+            strength: MIN_STRENGTH,
+            strength_drink: false,
+            lucky_clover: false,
+            rock_collectors_book: false,
+            gem_polish: false,
+            stopwatch: false,
+        }
+    }
+}
+
+impl PracticeSetup {
+    /// 开局道具与力量写入玩家状态
+    pub fn apply(&self, player: &mut PlayerResource) {
+        player.dynamite_count = self.dynamite;
+        player.strength = self.strength;
+        player.has_strength_drink = self.strength_drink;
+        player.has_lucky_clover = self.lucky_clover;
+        player.has_rock_collectors_book = self.rock_collectors_book;
+        player.has_gem_polish = self.gem_polish;
+        player.has_stopwatch = self.stopwatch;
+    }
+}
+
+/// 关卡选择菜单中的设置项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PracticeOption {
+    Level,
+    Timer,
+    Dynamite,
+    Strength,
+    StrengthDrink,
+    LuckyClover,
+    RockCollectorsBook,
+    GemPolish,
+    Stopwatch,
+    Start,
+}
+
+impl PracticeOption {
+    pub const ALL: [PracticeOption; 10] = [
+        PracticeOption::Level,
+        PracticeOption::Timer,
+        PracticeOption::Dynamite,
+        PracticeOption::Strength,
+        PracticeOption::StrengthDrink,
+        PracticeOption::LuckyClover,
+        PracticeOption::RockCollectorsBook,
+        PracticeOption::GemPolish,
+        PracticeOption::Stopwatch,
+        PracticeOption::Start,
+    ];
+
+    /// 菜单中显示的文字，例如 "Dynamite: 3"
+    pub fn label(&self, setup: &PracticeSetup) -> String {
+        let on_off = |value: bool| if value { "On" } else { "Off" };
+        match self {
+            PracticeOption::Level => format!("Level: < {} >", setup.level),
+            PracticeOption::Timer => format!("Timer: {}", on_off(setup.timed)),
+            PracticeOption::Dynamite => format!("Dynamite: {}", setup.dynamite),
+            PracticeOption::Strength => format!("Strength: {:.1}", setup.strength),
+            PracticeOption::StrengthDrink => {
+                format!("Strength Drink: {}", on_off(setup.strength_drink))
+            }
+            PracticeOption::LuckyClover => format!("Lucky Clover: {}", on_off(setup.lucky_clover)),
+            PracticeOption::RockCollectorsBook => {
+                format!("Rock Book: {}", on_off(setup.rock_collectors_book))
+            }
+            PracticeOption::GemPolish => format!("Gem Polish: {}", on_off(setup.gem_polish)),
+            PracticeOption::Stopwatch => format!("Stopwatch: {}", on_off(setup.stopwatch)),
+            PracticeOption::Start => "Start".to_string(),
+        }
+    }
+
+    /// 左右键调整设置：关卡循环切换，数值在范围内增减，开关项切换
+    pub fn adjust(&self, setup: &mut PracticeSetup, delta: i32, levels: &[String]) {
+        match self {
+            PracticeOption::Level => {
+                if levels.is_empty() {
+                    return;
+                }
+                let count = levels.len() as i32;
+                let index = levels
+                    .iter()
+                    .position(|id| *id == setup.level)
+                    .map_or(0, |index| (index as i32 + delta).rem_euclid(count));
+                setup.level = levels[index as usize].clone();
+            }
+            PracticeOption::Timer => setup.timed = !setup.timed,
+            PracticeOption::Dynamite => {
+                setup.dynamite = (setup.dynamite + delta).clamp(0, MAX_DYNAMITE);
+            }
+            PracticeOption::Strength => {
+                setup.strength = (setup.strength + delta as f32 * STRENGTH_STEP)
+                    .clamp(MIN_STRENGTH, MAX_STRENGTH);
+            }
+            PracticeOption::StrengthDrink => setup.strength_drink = !setup.strength_drink,
+            PracticeOption::LuckyClover => setup.lucky_clover = !setup.lucky_clover,
+            PracticeOption::RockCollectorsBook => {
+                setup.rock_collectors_book = !setup.rock_collectors_book;
+            }
+            PracticeOption::GemPolish => setup.gem_polish = !setup.gem_polish,
+            PracticeOption::Stopwatch => setup.stopwatch = !setup.stopwatch,
+            PracticeOption::Start => {}
+        }
+    }
+}
+
+/// 按关卡编号与变体排序 ("L2_1" 在 "L10_1" 之前)，其他 ID (例如 "LDEBUG") 排在最后
+pub fn sort_level_ids<'a>(ids: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+    let key = |id: &String| {
+        let numbers = id.strip_prefix('L').and_then(|rest| {
+            let (layout, variant) = rest.split_once('_')?;
+            Some((layout.parse::<u32>().ok()?, variant.parse::<u32>().ok()?))
+        });
+        (numbers.is_none(), numbers, id.clone())
+    };
+
+    let mut ids: Vec<String> = ids.into_iter().cloned().collect();
+    ids.sort_by_cached_key(key);
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_ids_sort_numerically_with_debug_last() {
+        let ids = ["L10_1", "LDEBUG", "L2_3", "L2_1", "L1_1"].map(String::from);
+
+        assert_eq!(
+            sort_level_ids(&ids),
+            vec!["L1_1", "L2_1", "L2_3", "L10_1", "LDEBUG"]
+        );
+    }
+
+    #[test]
+    fn adjusting_options_wraps_levels_and_clamps_values() {
+        let levels = ["L1_1", "L1_2", "LDEBUG"].map(String::from);
+        let mut setup = PracticeSetup::default();
+
+        PracticeOption::Level.adjust(&mut setup, -1, &levels);
+        assert_eq!(setup.level, "LDEBUG");
+        PracticeOption::Level.adjust(&mut setup, 1, &levels);
+        assert_eq!(setup.level, "L1_1");
+
+        for _ in 0..20 {
+            PracticeOption::Dynamite.adjust(&mut setup, 1, &levels);
+            PracticeOption::Strength.adjust(&mut setup, 1, &levels);
+        }
+        PracticeOption::Timer.adjust(&mut setup, 1, &levels);
+
+        assert_eq!(setup.dynamite, MAX_DYNAMITE);
+        assert_eq!(setup.strength, MAX_STRENGTH);
+        assert!(!setup.timed);
+    }
+}
@@ -5,8 +5,15 @@ use crate::config::ImageAssets;
 use crate::constants::{
     COLOR_GREEN, COLOR_YELLOW, SHOPKEEPER_FRAMES, SHOPKEEPER_HEIGHT, SHOPKEEPER_WIDTH,
 };
-use crate::demo::player::PlayerResource;
-use crate::screens::{Screen, stats::LevelStats};
+use crate::demo::bot::{self, Controllers};
+use crate::demo::hook::{HookStats, HookUpgrade};
+use crate::demo::player::{PlayerResource, PlayerSlot};
+use crate::screens::{
+    Screen,
+    coop::CoopState,
+    stats::{LevelStats, RngStream},
+};
+use crate::telemetry::{ShopEntry, TelemetryEvent};
 use crate::utils::love_to_bevy_coords;
 use bevy::prelude::*;
 use rand::Rng;
@@ -15,7 +22,9 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Shop), spawn_shop_ui);
     app.add_systems(
         Update,
-        (handle_shop_input, update_shop_ui).run_if(in_state(Screen::Shop)),
+        (handle_shop_input, update_shop_ui, update_coop_split)
+            .chain()
+            .run_if(in_state(Screen::Shop)),
     );
 }
 
@@ -27,6 +36,8 @@ pub enum PropType {
     LuckyClover,
     RockCollectorsBook,
     GemPolish,
+    Stopwatch,
+    HookUpgrade(HookUpgrade),
 }
 
 impl PropType {
@@ -37,36 +48,152 @@ impl PropType {
             PropType::LuckyClover => "2x luck on bags",
             PropType::RockCollectorsBook => "3x rock value",
             PropType::GemPolish => "1.5x diamond value",
+            PropType::Stopwatch => "+15s next level",
+            PropType::HookUpgrade(HookUpgrade::LongerRope) => "Longer rope",
+            PropType::HookUpgrade(HookUpgrade::FasterDescent) => "Faster hook descent",
+            PropType::HookUpgrade(HookUpgrade::WiderClaw) => "Wider claw",
+            PropType::HookUpgrade(HookUpgrade::SlowerSwing) => "Slower hook swing",
+        }
+    }
+
+    /// 道具图片，钩子升级没有图片时以文字显示
+    fn image_id(&self) -> Option<&'static str> {
+        match self {
+            PropType::Dynamite => Some("Dynamite"),
+            PropType::StrengthDrink => Some("StrengthDrink"),
+            PropType::LuckyClover => Some("LuckyClover"),
+            PropType::RockCollectorsBook => Some("RockCollectorsBook"),
+            PropType::GemPolish => Some("GemPolish"),
+            PropType::Stopwatch => Some("Stopwatch"),
+            PropType::HookUpgrade(_) => None,
         }
     }
 
-    fn image_id(&self) -> &'static str {
+    fn label(&self) -> &'static str {
+        match self {
+            PropType::HookUpgrade(HookUpgrade::LongerRope) => "Rope+",
+            PropType::HookUpgrade(HookUpgrade::FasterDescent) => "Dive+",
+            PropType::HookUpgrade(HookUpgrade::WiderClaw) => "Claw+",
+            PropType::HookUpgrade(HookUpgrade::SlowerSwing) => "Swing-",
+            _ => "",
+        }
+    }
+
+    /// 道具名称，用于统计与报告
+    pub fn name(&self) -> &'static str {
         match self {
             PropType::Dynamite => "Dynamite",
             PropType::StrengthDrink => "StrengthDrink",
             PropType::LuckyClover => "LuckyClover",
             PropType::RockCollectorsBook => "RockCollectorsBook",
             PropType::GemPolish => "GemPolish",
+            PropType::Stopwatch => "Stopwatch",
+            PropType::HookUpgrade(HookUpgrade::LongerRope) => "LongerRope",
+            PropType::HookUpgrade(HookUpgrade::FasterDescent) => "FasterDescent",
+            PropType::HookUpgrade(HookUpgrade::WiderClaw) => "WiderClaw",
+            PropType::HookUpgrade(HookUpgrade::SlowerSwing) => "SlowerSwing",
         }
     }
 
-    fn get_price(&self, level: u32) -> u32 {
-        let mut rng = rand::rng();
+    /// 购买后把道具效果应用到持有者与钩子参数
+    pub fn apply(&self, owner: &mut PlayerResource, hook_stats: &mut HookStats) {
+        match self {
+            PropType::Dynamite => {
+                // 炸药数量 +1，上限 12
+                owner.dynamite_count = (owner.dynamite_count + 1).min(12);
+            }
+            PropType::StrengthDrink => {
+                owner.has_strength_drink = true;
+            }
+            PropType::LuckyClover => {
+                owner.has_lucky_clover = true;
+            }
+            PropType::RockCollectorsBook => {
+                owner.has_rock_collectors_book = true;
+            }
+            PropType::GemPolish => {
+                owner.has_gem_polish = true;
+            }
+            PropType::Stopwatch => {
+                owner.has_stopwatch = true;
+            }
+            PropType::HookUpgrade(upgrade) => {
+                hook_stats.upgrade(*upgrade);
+            }
+        }
+    }
+
+    fn get_price(&self, level: u32, hook_stats: &HookStats, rng: &mut impl Rng) -> u32 {
         match self {
             PropType::Dynamite => rng.random_range(1..=300) + 1 + level * 2,
             PropType::StrengthDrink => rng.random_range(100..=400),
             PropType::LuckyClover => rng.random_range(1..=(level * 50).max(1)) + 1 + level * 2,
             PropType::RockCollectorsBook => rng.random_range(1..=150) + 1,
             PropType::GemPolish => rng.random_range(201..=(level * 100 + 201)),
+            PropType::Stopwatch => rng.random_range(50..=200) + level * 5,
+            PropType::HookUpgrade(upgrade) => {
+                // 按下一级计价，等级越高越贵
+                upgrade.base_price(hook_stats.tier(*upgrade) + 1) + rng.random_range(0..=50)
+            }
         }
     }
 }
 
 /// 商店中的道具实例
-#[derive(Clone)]
-struct ShopItem {
-    prop_type: PropType,
-    price: u32,
+#[derive(Clone, Debug)]
+pub struct ShopItem {
+    pub prop_type: PropType,
+    pub price: u32,
+}
+
+/// 本次商店的货架与价格：有种子时由种子与关卡决定
+pub fn roll_shop_items(stats: &LevelStats, hook_stats: &HookStats) -> Vec<ShopItem> {
+    let mut rng = stats.rng(RngStream::Shop);
+    let all_props = [
+        PropType::Dynamite,
+        PropType::StrengthDrink,
+        PropType::LuckyClover,
+        PropType::RockCollectorsBook,
+        PropType::GemPolish,
+        PropType::Stopwatch,
+    ];
+
+    let mut items = Vec::new();
+    for prop in all_props.iter() {
+        // 约 66% 概率出现
+        if rng.random_range(1..=3) >= 2 {
+            items.push(ShopItem {
+                prop_type: *prop,
+                price: stats.scale_price(prop.get_price(stats.level, hook_stats, &mut rng)),
+            });
+        }
+    }
+
+    // 每次随机提供一项未满级的钩子升级
+    let upgrades: Vec<HookUpgrade> = HookUpgrade::ALL
+        .into_iter()
+        .filter(|upgrade| hook_stats.can_upgrade(*upgrade))
+        .collect();
+    if !upgrades.is_empty() {
+        let prop = PropType::HookUpgrade(upgrades[rng.random_range(0..upgrades.len())]);
+        items.push(ShopItem {
+            prop_type: prop,
+            price: stats.scale_price(prop.get_price(stats.level, hook_stats, &mut rng)),
+        });
+    }
+    // 确保至少有一个商品
+    if items.is_empty() {
+        items.push(ShopItem {
+            prop_type: PropType::Dynamite,
+            price: stats.scale_price(PropType::Dynamite.get_price(
+                stats.level,
+                hook_stats,
+                &mut rng,
+            )),
+        });
+    }
+
+    items
 }
 
 /// 商店状态资源
@@ -110,9 +237,12 @@ struct ShopDescriptionText;
 struct ShopMoneyText;
 #[derive(Component)]
 struct ShopkeeperSprite;
+/// 合作模式中每人的花费
+#[derive(Component)]
+struct ShopSplitText;
 #[derive(Component)]
 struct ShopDynamicItem;
-const SHOP_ITEM_PADDING: f32 = 50.0;
+const SHOP_ITEM_PADDING: f32 = 44.0;
 const DEFAULT_DIALOGUE_TEXT: &str = "Left/Right: select\nEnter: buy\nSpace: exit";
 const NO_MONEY_DIALOGUE_TEXT: &str = "You don't seem to have any money\n:(";
 
@@ -122,34 +252,20 @@ fn spawn_shop_ui(
     image_assets: Res<ImageAssets>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     stats: Res<LevelStats>,
+    hook_stats: Res<HookStats>,
+    coop: Option<Res<CoopState>>,
 ) {
-    // 初始化商店状态
-    let mut rng = rand::rng();
-    let all_props = [
-        PropType::Dynamite,
-        PropType::StrengthDrink,
-        PropType::LuckyClover,
-        PropType::RockCollectorsBook,
-        PropType::GemPolish,
-    ];
-
-    let mut items = Vec::new();
-    for prop in all_props.iter() {
-        // 约 66% 概率出现
-        if rng.random_range(1..=3) >= 2 {
-            items.push(ShopItem {
-                prop_type: *prop,
-                price: prop.get_price(stats.level),
-            });
-        }
-    }
-    // 确保至少有一个商品
-    if items.is_empty() {
-        items.push(ShopItem {
-            prop_type: PropType::Dynamite,
-            price: PropType::Dynamite.get_price(stats.level),
-        });
-    }
+    // 初始化商店状态：每日挑战中货架与价格由种子决定
+    let items = roll_shop_items(&stats, &hook_stats);
+    commands.write_message(TelemetryEvent::ShopOffers {
+        items: items
+            .iter()
+            .map(|item| ShopEntry {
+                item: item.prop_type.name().to_string(),
+                price: item.price,
+            })
+            .collect(),
+    });
 
     commands.insert_resource(ShopState {
         items: items.clone(),
@@ -272,10 +388,28 @@ fn spawn_shop_ui(
         DespawnOnExit(Screen::Shop),
     ));
 
+    // 合作模式：购买者与每人的花费
+    if let Some(coop) = &coop {
+        commands.spawn((
+            Name::new("Coop Split"),
+            Text2d::new(coop.spending_summary()),
+            TextFont {
+                font: font.clone(),
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(COLOR_YELLOW),
+            Transform::from_translation(love_to_bevy_coords(160.0, 58.0).extend(1.0)),
+            bevy::sprite::Anchor::CENTER,
+            ShopSplitText,
+            DespawnOnExit(Screen::Shop),
+        ));
+    }
+
     // 显示玩家金钱
     commands.spawn((
         Name::new("Player Money"),
-        Text2d::new(format!("Your Money: ${}", stats.money)),
+        Text2d::new(money_text(&stats, coop.as_deref())),
         TextFont {
             font: font.clone(),
             font_size: 14.0,
@@ -300,15 +434,33 @@ fn spawn_shop_items(
     for (i, item) in items.iter().enumerate() {
         let x = 30.0 + i as f32 * SHOP_ITEM_PADDING;
 
-        commands.spawn((
-            Name::new(format!("Shop Item {i}")),
-            Sprite::from_image(image_assets.get_image(item.prop_type.image_id()).unwrap()),
-            Transform::from_translation(love_to_bevy_coords(x, 160.0).extend(1.0)),
-            bevy::sprite::Anchor::CENTER,
-            ShopItemSprite,
-            ShopDynamicItem,
-            DespawnOnExit(Screen::Shop),
-        ));
+        if let Some(image_id) = item.prop_type.image_id() {
+            commands.spawn((
+                Name::new(format!("Shop Item {i}")),
+                Sprite::from_image(image_assets.get_image(image_id).unwrap()),
+                Transform::from_translation(love_to_bevy_coords(x, 160.0).extend(1.0)),
+                bevy::sprite::Anchor::CENTER,
+                ShopItemSprite,
+                ShopDynamicItem,
+                DespawnOnExit(Screen::Shop),
+            ));
+        } else {
+            commands.spawn((
+                Name::new(format!("Shop Item {i}")),
+                Text2d::new(item.prop_type.label()),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(COLOR_YELLOW),
+                Transform::from_translation(love_to_bevy_coords(x, 160.0).extend(1.0)),
+                bevy::sprite::Anchor::CENTER,
+                ShopItemSprite,
+                ShopDynamicItem,
+                DespawnOnExit(Screen::Shop),
+            ));
+        }
 
         commands.spawn((
             Name::new(format!("Shop Price {i}")),
@@ -336,6 +488,9 @@ fn handle_shop_input(
     mut stats: ResMut<LevelStats>,
     mut shop_state: ResMut<ShopState>,
     mut player: ResMut<PlayerResource>,
+    mut hook_stats: ResMut<HookStats>,
+    mut coop: Option<ResMut<CoopState>>,
+    controllers: Res<Controllers>,
 ) {
     if shop_state.is_finish_shopping {
         return;
@@ -348,6 +503,7 @@ fn handle_shop_input(
         || input.just_pressed(KeyCode::KeyJ)
         || input.just_pressed(KeyCode::KeyK);
     let mut exit_shop = input.just_pressed(KeyCode::Space);
+    let mut switch_shopper = input.just_pressed(KeyCode::Tab);
 
     for gamepad in &gamepads {
         if gamepad.just_pressed(GamepadButton::DPadLeft) {
@@ -365,6 +521,29 @@ fn handle_shop_input(
         if gamepad.just_pressed(GamepadButton::Select) {
             exit_shop = true;
         }
+        if gamepad.just_pressed(GamepadButton::North) {
+            switch_shopper = true;
+        }
+    }
+
+    // 1P 由机器人操作时：逐格移向要买的商品后购买，没有要买的就离开
+    if controllers.is_bot(PlayerSlot(0)) {
+        let items: Vec<(PropType, u32)> = shop_state
+            .items
+            .iter()
+            .map(|item| (item.prop_type, item.price))
+            .collect();
+        match bot::shop_choice(&items, stats.money, &player) {
+            Some(index) if index < shop_state.selector_index => left = true,
+            Some(index) if index > shop_state.selector_index => right = true,
+            Some(_) => buy = true,
+            None => exit_shop = true,
+        }
+    }
+
+    // 合作模式切换购买者
+    if switch_shopper && let Some(coop) = coop.as_deref_mut() {
+        coop.switch_shopper();
     }
 
     // 左右切换选择
@@ -385,6 +564,10 @@ fn handle_shop_input(
         {
             stats.money -= item.price;
             shop_state.player_bought = true;
+            commands.write_message(TelemetryEvent::ShopPurchase {
+                item: item.prop_type.name().to_string(),
+                price: item.price,
+            });
             shop_state.dialogue_text = DEFAULT_DIALOGUE_TEXT.to_string();
 
             // 播放购买音效
@@ -392,25 +575,20 @@ fn handle_shop_input(
                 commands.spawn(sound_effect(audio));
             }
 
-            // 应用道具效果到 PlayerResource
-            match item.prop_type {
-                PropType::Dynamite => {
-                    // 炸药数量 +1，上限 12
-                    player.dynamite_count = (player.dynamite_count + 1).min(12);
-                }
-                PropType::StrengthDrink => {
-                    player.has_strength_drink = true;
-                }
-                PropType::LuckyClover => {
-                    player.has_lucky_clover = true;
+            // 应用道具效果到 PlayerResource：合作模式中道具归购买者，炸药放入共用库存
+            let owner = match coop.as_deref_mut() {
+                Some(coop) => {
+                    coop.record_purchase(item.price);
+                    let shopper = coop.shopper;
+                    if item.prop_type == PropType::Dynamite {
+                        &mut *player
+                    } else {
+                        &mut coop.player_mut(shopper).inventory
+                    }
                 }
-                PropType::RockCollectorsBook => {
-                    player.has_rock_collectors_book = true;
-                }
-                PropType::GemPolish => {
-                    player.has_gem_polish = true;
-                }
-            }
+                None => &mut *player,
+            };
+            item.prop_type.apply(owner, &mut hook_stats);
 
             // 移除商品
             shop_state.items.remove(selector_index);
@@ -471,6 +649,7 @@ fn update_shop_ui(
     >,
     mut q_shopkeeper: Query<&mut Sprite, With<ShopkeeperSprite>>,
     q_dynamic_items: Query<Entity, With<ShopDynamicItem>>,
+    coop: Option<Res<CoopState>>,
 ) {
     if shop_state.items_dirty {
         for entity in &q_dynamic_items {
@@ -511,7 +690,7 @@ fn update_shop_ui(
     }
 
     if let Ok(mut text) = q_money.single_mut() {
-        text.0 = format!("Your Money: ${}", stats.money);
+        text.0 = money_text(&stats, coop.as_deref());
     }
 
     // 处理完成购物计时器
@@ -542,3 +721,30 @@ fn update_shop_ui(
         text.0 = shop_state.dialogue_text.clone();
     }
 }
+
+/// 金钱文字，合作模式显示共同金钱与当前购买者
+fn money_text(stats: &LevelStats, coop: Option<&CoopState>) -> String {
+    match coop {
+        Some(coop) => format!(
+            "Team Money: ${}  Buyer: {} (Tab)",
+            stats.money,
+            coop.shopper.label()
+        ),
+        None => format!("Your Money: ${}", stats.money),
+    }
+}
+
+fn update_coop_split(
+    coop: Option<Res<CoopState>>,
+    mut q_split: Query<&mut Text2d, With<ShopSplitText>>,
+) {
+    let Some(coop) = coop else {
+        return;
+    };
+    for mut text in &mut q_split {
+        let summary = coop.spending_summary();
+        if text.0 != summary {
+            text.0 = summary;
+        }
+    }
+}
@@ -1,7 +1,108 @@
 use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{DifficultyScaling, GoalCurve, ProgressionConfig};
+use crate::screens::endless::EndlessModifiers;
+use crate::screens::time_attack::TimeAttackRun;
 
 const LEVEL_DURATION_SECONDS: f32 = 60.0;
 
+/// 难度预设
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    /// 使用 gameplay.yaml 中的 `customDifficulty`
+    Custom,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Custom,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Custom => "Custom",
+        }
+    }
+
+    /// 预设对应的缩放系数，Custom 直接使用配置值
+    pub fn scaling(&self, custom: &DifficultyScaling) -> DifficultyScaling {
+        match self {
+            Difficulty::Easy => DifficultyScaling {
+                time_scale: 1.25,
+                goal_scale: 0.8,
+                price_scale: 0.8,
+                swing_scale: 0.85,
+                bag_luck: 1.5,
+            },
+            Difficulty::Normal => DifficultyScaling::default(),
+            Difficulty::Hard => DifficultyScaling {
+                time_scale: 0.85,
+                goal_scale: 1.25,
+                price_scale: 1.25,
+                swing_scale: 1.2,
+                bag_luck: 0.5,
+            },
+            Difficulty::Custom => custom.clone(),
+        }
+    }
+}
+
+/// 主菜单中选择的难度，开始新游戏时写入 `LevelStats`
+#[derive(Resource, Debug, Default)]
+pub struct SelectedDifficulty(pub Difficulty);
+
+/// 游戏模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GameMode {
+    /// 普通模式：按目标金额逐关推进
+    #[default]
+    Campaign,
+    /// 无尽模式：每通过一关叠加一个修饰
+    Endless,
+    /// 每日挑战：以日期为种子，固定 Normal 难度
+    Daily,
+    /// 限时挑战：依次通过固定关卡，比拼用时
+    TimeAttack,
+    /// 练习模式：只进行关卡选择菜单中选中的一关
+    Practice,
+    /// 双人对战：两名玩家争抢同一关卡中的物品
+    Versus,
+    /// 双人合作：两名玩家共同完成同一个目标金额
+    Coop,
+    /// 联网竞速：与对手使用同一个种子进行同样的关卡序列
+    Online,
+}
+
+/// 本局随机数的用途：不同用途使用独立的序列，购买道具不会影响之后的关卡
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RngStream {
+    /// 关卡布局与变体
+    Level,
+    /// 商店货架与价格
+    Shop,
+    /// 抓取结算的额外效果判定 (仅模拟器使用，游戏中每次结算独立随机)
+    Payout,
+}
+
+/// 主菜单中选择的模式，开始新游戏时写入 `LevelStats`
+#[derive(Resource, Debug, Default)]
+pub struct SelectedMode(pub GameMode);
+
 #[derive(Resource, Debug, Clone)]
 pub struct LevelStats {
     pub money: u32,
@@ -14,6 +115,26 @@ pub struct LevelStats {
     pub is_first_init: bool,
     /// 实际关卡配置ID (如 "L1_1", "L3_2")
     pub real_level_str: String,
+    /// 本局最高连击数
+    pub best_combo: u32,
+    /// 本局难度
+    pub difficulty: Difficulty,
+    /// 本局难度的缩放系数
+    pub scaling: DifficultyScaling,
+    /// 本局模式
+    pub mode: GameMode,
+    /// 无尽模式已叠加的修饰
+    pub modifiers: EndlessModifiers,
+    /// 本局种子：开始新游戏时随机生成，每日挑战使用日期种子，联网竞速使用中继下发的种子
+    pub seed: Option<u64>,
+    /// 每日挑战中本局是否计分 (每天只有第一次挑战计分)
+    pub daily_scored: bool,
+    /// 每日挑战本局的日期 (自 1970-01-01 起的天数)，跨过午夜仍显示开始时的日期
+    pub daily_day: u32,
+    /// 限时挑战的计时
+    pub time_attack: TimeAttackRun,
+    /// 关卡是否计时 (练习模式可以关闭)
+    pub timed: bool,
 }
 
 impl Default for LevelStats {
@@ -27,47 +148,131 @@ impl Default for LevelStats {
             timer: LEVEL_DURATION_SECONDS,
             is_first_init: true,
             real_level_str: "L1_1".to_string(),
+            best_combo: 0,
+            difficulty: Difficulty::Normal,
+            scaling: DifficultyScaling::default(),
+            mode: GameMode::Campaign,
+            modifiers: EndlessModifiers::default(),
+            seed: None,
+            daily_scored: false,
+            daily_day: 0,
+            time_attack: TimeAttackRun::default(),
+            timed: true,
         }
     }
 }
 
 impl LevelStats {
-    /// 计算并更新下一关的目标金额
+    /// 以指定难度和目标曲线开始新的一局
+    pub fn new_run(difficulty: Difficulty, scaling: DifficultyScaling, curve: &GoalCurve) -> Self {
+        let mut stats = Self {
+            difficulty,
+            scaling,
+            goal: curve.initial,
+            goal_addon: curve.initial_addon,
+            ..default()
+        };
+        stats.reset_timer();
+        stats
+    }
+
+    /// 按目标曲线计算并更新下一关的目标金额 (默认曲线与原版一致)
     /// 第 1 关: goal = 375 + 275 = 650
     /// 第 2-9 关: goal_addon += 270, 然后 goal += goal_addon
     /// 第 10+ 关: goal_addon 不再增加, goal 继续 += goal_addon
-    pub fn update_goal(&mut self) {
-        if self.level > 1 && self.level <= 9 {
-            self.goal_addon += 270;
+    /// 每关的增幅与覆盖值按难度的 `goal_scale` 缩放
+    pub fn update_goal(&mut self, curve: &GoalCurve) {
+        if self.level > 1 && self.level <= curve.addon_growth_until {
+            self.goal_addon += curve.addon_step;
         }
-        self.goal += self.goal_addon;
+        self.goal += self.scale_goal(self.goal_addon);
+
+        if let Some(goal) = curve.overrides.get(&self.level) {
+            self.goal = self.scale_goal(*goal);
+        }
+    }
+
+    fn scale_goal(&self, amount: u32) -> u32 {
+        (amount as f32 * self.scaling.goal_scale).round() as u32
     }
 
     pub fn reach_goal(&self) -> bool {
         self.money >= self.goal
     }
 
+    /// 是否可以按 Skip 结束本关：达成目标后，练习模式随时可以
+    pub fn can_skip(&self) -> bool {
+        self.reach_goal() || self.mode == GameMode::Practice
+    }
+
     pub fn reset_timer(&mut self) {
-        self.timer = LEVEL_DURATION_SECONDS;
+        self.timer =
+            LEVEL_DURATION_SECONDS * self.scaling.time_scale * self.modifiers.timer_scale();
     }
 
-    /// 计算实际关卡配置
-    /// 前3关正常递增，之后在3-9之间循环
-    pub fn calculate_real_level(&mut self) {
-        let real_level = if self.level <= 3 {
-            self.level
-        } else {
-            ((self.level - 3) % 7) + 3
+    /// 按难度与无尽模式修饰缩放商店价格
+    pub fn scale_price(&self, price: u32) -> u32 {
+        (price as f32 * self.scaling.price_scale * self.modifiers.price_scale()).round() as u32
+    }
+
+    /// 增加剩余时间 (计时物品、秒表道具)
+    pub fn add_time(&mut self, seconds: f32) {
+        self.timer += seconds;
+    }
+
+    /// 当前关卡指定用途的随机数：有种子时由种子、关卡与用途决定，否则使用系统随机数
+    pub fn rng(&self, stream: RngStream) -> StdRng {
+        match self.seed {
+            Some(seed) => {
+                let level = (self.level as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+                let stream = match stream {
+                    RngStream::Level => 0,
+                    RngStream::Shop => 0xD1B5_4A32_D192_ED03,
+                    RngStream::Payout => 0x94D0_49BB_1331_11EB,
+                };
+                StdRng::seed_from_u64(seed ^ level ^ stream)
+            }
+            None => StdRng::seed_from_u64(rand::random()),
+        }
+    }
+
+    /// 按 progression.yaml 的关卡池计算实际关卡配置，限时挑战使用固定的关卡列表
+    pub fn calculate_real_level(
+        &mut self,
+        progression: &ProgressionConfig,
+        rng: &mut impl rand::Rng,
+    ) {
+        self.real_level_str = match self.mode {
+            GameMode::TimeAttack => progression
+                .time_attack
+                .get(self.level as usize - 1)
+                .cloned()
+                .unwrap_or_else(|| progression.pick_layout(self.level, rng)),
+            _ => progression.pick_layout(self.level, rng),
         };
-        // 随机变体 1-3
-        let variant = rand::random::<u32>() % 3 + 1;
-        self.real_level_str = format!("L{real_level}_{variant}");
+    }
+
+    /// 实际关卡的布局编号 ("L4_2" -> 4)
+    pub fn layout_number(&self) -> u32 {
+        self.real_level_str
+            .chars()
+            .skip(1) // 跳过 'L'
+            .take_while(|c| c.is_ascii_digit())
+            .collect::<String>()
+            .parse()
+            .unwrap_or(1)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{LEVEL_DURATION_SECONDS, LevelStats};
+    use super::{Difficulty, GameMode, LEVEL_DURATION_SECONDS, LevelStats, RngStream};
+    use crate::config::{
+        DifficultyScaling, GoalCurve, PoolOrder, ProgressionConfig, ProgressionStage,
+    };
+    use crate::screens::endless::EndlessModifier;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
 
     #[test]
     fn reset_timer_restores_original_sixty_second_level_duration() {
@@ -80,4 +285,180 @@ mod tests {
 
         assert_eq!(stats.timer, LEVEL_DURATION_SECONDS);
     }
+
+    #[test]
+    fn add_time_extends_remaining_timer() {
+        let mut stats = LevelStats {
+            timer: 3.0,
+            ..Default::default()
+        };
+
+        stats.add_time(5.0);
+
+        assert_eq!(stats.timer, 8.0);
+    }
+
+    #[test]
+    fn hard_run_scales_timer_goal_and_prices() {
+        let custom = DifficultyScaling::default();
+        let curve = GoalCurve::default();
+        let mut stats =
+            LevelStats::new_run(Difficulty::Hard, Difficulty::Hard.scaling(&custom), &curve);
+
+        stats.update_goal(&curve);
+
+        assert_eq!(stats.timer, LEVEL_DURATION_SECONDS * 0.85);
+        assert_eq!(stats.goal, 375 + 344);
+        assert_eq!(stats.scale_price(100), 125);
+    }
+
+    #[test]
+    fn custom_difficulty_uses_configured_scaling() {
+        let custom = DifficultyScaling {
+            time_scale: 2.0,
+            ..Default::default()
+        };
+
+        let stats = LevelStats::new_run(
+            Difficulty::Custom,
+            Difficulty::Custom.scaling(&custom),
+            &GoalCurve::default(),
+        );
+
+        assert_eq!(stats.timer, LEVEL_DURATION_SECONDS * 2.0);
+        assert_eq!(stats.scale_price(80), 80);
+    }
+
+    #[test]
+    fn default_goal_curve_matches_original_formula() {
+        let curve = GoalCurve::default();
+        let mut stats = LevelStats::default();
+        let mut goals = Vec::new();
+
+        for level in 1..=11 {
+            stats.level = level;
+            stats.update_goal(&curve);
+            goals.push(stats.goal);
+        }
+
+        assert_eq!(goals[0], 650);
+        assert_eq!(goals[1], 650 + 545);
+        // 第 10 关起增幅不再增加
+        assert_eq!(goals[10] - goals[9], goals[9] - goals[8]);
+    }
+
+    #[test]
+    fn goal_overrides_replace_formula_for_that_level() {
+        let curve = GoalCurve {
+            overrides: [(2, 5000)].into(),
+            ..Default::default()
+        };
+        let mut stats = LevelStats::default();
+
+        stats.update_goal(&curve);
+        stats.level = 2;
+        stats.update_goal(&curve);
+
+        assert_eq!(stats.goal, 5000);
+    }
+
+    #[test]
+    fn default_progression_loops_like_original_levels() {
+        let progression = ProgressionConfig::default();
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut stats = LevelStats::default();
+
+        let layouts: Vec<u32> = (1..=11)
+            .map(|level| {
+                stats.level = level;
+                stats.calculate_real_level(&progression, &mut rng);
+                stats.layout_number()
+            })
+            .collect();
+
+        assert_eq!(layouts, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 3, 4]);
+        assert_eq!(progression.background_for(4), "LevelB");
+        assert_eq!(progression.background_for(10), "LevelE");
+    }
+
+    #[test]
+    fn variant_weights_and_random_pool_are_respected() {
+        let mut progression = ProgressionConfig::default();
+        progression.stages[1].pool = vec![10];
+        progression.stages[1].order = PoolOrder::Random;
+        progression.stages[1].variant_weights = vec![0, 0, 1];
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut stats = LevelStats {
+            level: 12,
+            ..Default::default()
+        };
+
+        stats.calculate_real_level(&progression, &mut rng);
+
+        assert_eq!(stats.real_level_str, "L10_3");
+    }
+
+    #[test]
+    fn endless_modifiers_shorten_timer_and_raise_prices() {
+        let mut stats = LevelStats::default();
+        stats.modifiers.add(EndlessModifier::ShorterTimer);
+        stats.modifiers.add(EndlessModifier::PricierShop);
+
+        stats.reset_timer();
+
+        assert_eq!(stats.timer, LEVEL_DURATION_SECONDS * 0.9);
+        assert_eq!(stats.scale_price(100), 120);
+    }
+
+    #[test]
+    fn seeded_runs_repeat_level_sequence() {
+        let progression = ProgressionConfig {
+            stages: vec![ProgressionStage {
+                pool: vec![1, 2, 3, 4, 5],
+                order: PoolOrder::Random,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let sequence = |seed| {
+            let mut stats = LevelStats {
+                seed: Some(seed),
+                ..Default::default()
+            };
+            (1..=8)
+                .map(|level| {
+                    stats.level = level;
+                    let mut rng = stats.rng(RngStream::Level);
+                    stats.calculate_real_level(&progression, &mut rng);
+                    stats.real_level_str.clone()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(sequence(42), sequence(42));
+        assert_ne!(sequence(42), sequence(43));
+    }
+
+    #[test]
+    fn time_attack_follows_fixed_level_list() {
+        let progression = ProgressionConfig {
+            time_attack: vec!["L7_2".to_string(), "L3_1".to_string()],
+            ..Default::default()
+        };
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut stats = LevelStats {
+            mode: GameMode::TimeAttack,
+            ..Default::default()
+        };
+
+        let layouts: Vec<String> = (1..=2)
+            .map(|level| {
+                stats.level = level;
+                stats.calculate_real_level(&progression, &mut rng);
+                stats.real_level_str.clone()
+            })
+            .collect();
+
+        assert_eq!(layouts, vec!["L7_2", "L3_1"]);
+    }
 }
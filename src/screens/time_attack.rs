@@ -0,0 +1,95 @@
+//! 限时挑战模式
+//!
+//! 依次进行 progression.yaml 中 `timeAttack` 列出的手工关卡，比拼通关用时：
+//! - 达成目标后立即进入下一关，不需要等待计时结束或按 Skip
+//! - 只计算关卡内的时间，商店与过渡界面不计时
+//! - 每关用时 (分段) 与总用时的最好成绩记录在存档中 (见 `TimeAttackRecord`)
+
+use bevy::prelude::*;
+
+use crate::screens::{
+    Screen,
+    stats::{GameMode, LevelStats},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_time_attack.run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// 本局限时挑战的计时
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimeAttackRun {
+    /// 当前关卡已用时间 (秒)
+    pub elapsed: f32,
+    /// 已完成关卡的分段用时
+    pub splits: Vec<f32>,
+    /// 是否已完成全部关卡
+    pub finished: bool,
+}
+
+impl TimeAttackRun {
+    /// 结束当前关卡，返回本关分段用时
+    pub fn finish_level(&mut self) -> f32 {
+        let split = self.elapsed;
+        self.splits.push(split);
+        self.elapsed = 0.0;
+        split
+    }
+
+    /// 已完成关卡的总用时
+    pub fn total(&self) -> f32 {
+        self.splits.iter().sum()
+    }
+}
+
+/// 将秒数格式化为 "1:05.25"
+pub fn format_time(seconds: f32) -> String {
+    let centis = (seconds.max(0.0) * 100.0).round() as u32;
+    format!(
+        "{}:{:02}.{:02}",
+        centis / 6000,
+        centis / 100 % 60,
+        centis % 100
+    )
+}
+
+/// 累计关卡用时，达成目标后立即过关
+fn update_time_attack(
+    time: Res<Time>,
+    mut stats: ResMut<LevelStats>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if stats.mode != GameMode::TimeAttack {
+        return;
+    }
+
+    stats.time_attack.elapsed += time.delta_secs();
+    if stats.reach_goal() {
+        next_screen.set(Screen::MadeGoal);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_add_up_to_total() {
+        let mut run = TimeAttackRun {
+            elapsed: 12.5,
+            ..Default::default()
+        };
+
+        assert_eq!(run.finish_level(), 12.5);
+        run.elapsed = 30.25;
+        run.finish_level();
+
+        assert_eq!(run.elapsed, 0.0);
+        assert_eq!(run.total(), 42.75);
+        assert_eq!(format_time(run.total()), "0:42.75");
+        assert_eq!(format_time(65.25), "1:05.25");
+    }
+}
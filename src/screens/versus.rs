@@ -0,0 +1,220 @@
+//! 双人对战模式
+//!
+//! 两名矿工并排站在地面上，各自操作独立的钩子，争抢同一关卡中的物品：
+//! - 1P 使用 S 发射、W 扔炸药与第一个手柄，2P 使用方向键与第二个手柄
+//! - 道具 (炸药、力量) 与金钱分别结算，连击在对战中关闭
+//! - 每回合计时结束后显示比分，共 `VERSUS_ROUNDS` 回合，不经过商店
+//! - 对战期间存在 `VersusState` 资源，单人模式没有该资源
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+use crate::config::{ImageAssets, ProgressionConfig};
+use crate::constants::{COLOR_GREEN, COLOR_YELLOW};
+use crate::demo::hook::HookStats;
+use crate::demo::level::ProgressionHandle;
+use crate::demo::player::{PlayerResource, PlayerSlot};
+use crate::screens::{
+    Screen,
+    stats::{LevelStats, RngStream},
+};
+use crate::utils::love_to_bevy_coords;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::VersusResult), spawn_result_ui);
+    app.add_systems(
+        Update,
+        check_keyboard_input.run_if(in_state(Screen::VersusResult)),
+    );
+}
+
+/// 一场对战的回合数
+pub const VERSUS_ROUNDS: u32 = 3;
+
+/// 对战中一名玩家的状态
+#[derive(Debug, Clone, Default)]
+pub struct VersusPlayer {
+    /// 独立的道具栏
+    pub inventory: PlayerResource,
+    /// 累计金钱
+    pub money: u32,
+}
+
+/// 对战状态，开始对战时插入，对战结束后移除
+#[derive(Resource, Debug, Clone, Default)]
+pub struct VersusState {
+    pub players: [VersusPlayer; 2],
+}
+
+/// 对战结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersusOutcome {
+    Winner(PlayerSlot),
+    Draw,
+}
+
+impl VersusState {
+    pub fn player(&self, slot: PlayerSlot) -> &VersusPlayer {
+        &self.players[slot.0]
+    }
+
+    pub fn player_mut(&mut self, slot: PlayerSlot) -> &mut VersusPlayer {
+        &mut self.players[slot.0]
+    }
+
+    /// 按累计金钱判定胜负
+    pub fn outcome(&self) -> VersusOutcome {
+        let [first, second] = &self.players;
+        match first.money.cmp(&second.money) {
+            std::cmp::Ordering::Greater => VersusOutcome::Winner(PlayerSlot(0)),
+            std::cmp::Ordering::Less => VersusOutcome::Winner(PlayerSlot(1)),
+            std::cmp::Ordering::Equal => VersusOutcome::Draw,
+        }
+    }
+}
+
+fn spawn_result_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    image_assets: Res<ImageAssets>,
+    stats: Res<LevelStats>,
+    versus: Res<VersusState>,
+) {
+    let is_final = stats.level >= VERSUS_ROUNDS;
+
+    // 背景
+    commands.spawn((
+        Name::new("Goal Background"),
+        Sprite::from_image(image_assets.get_image("Goal").unwrap()),
+        Transform::from_translation(Vec3::new(0.0, 0.0, -1.0)),
+        DespawnOnExit(Screen::VersusResult),
+    ));
+
+    // 标题
+    commands.spawn((
+        Name::new("Goal Title"),
+        Sprite::from_image(image_assets.get_image("Title").unwrap()),
+        Transform::from_translation(love_to_bevy_coords(54.0, 20.0).extend(0.0)),
+        Anchor::TOP_LEFT,
+        DespawnOnExit(Screen::VersusResult),
+    ));
+
+    // 面板
+    commands.spawn((
+        Name::new("Goal Panel"),
+        Sprite::from_image(image_assets.get_image("Panel").unwrap()),
+        Transform::from_translation(love_to_bevy_coords(27.0, 80.0).extend(0.0)),
+        Anchor::TOP_LEFT,
+        DespawnOnExit(Screen::VersusResult),
+    ));
+
+    let font = asset_server.load("fonts/Kurland.ttf");
+    let headline = if is_final {
+        match versus.outcome() {
+            VersusOutcome::Winner(slot) => format!("{} Wins!", slot.label()),
+            VersusOutcome::Draw => "Draw!".to_string(),
+        }
+    } else {
+        format!("Round {}/{VERSUS_ROUNDS}", stats.level)
+    };
+    commands.spawn((
+        Name::new("Versus Headline"),
+        Text2d::new(headline),
+        TextFont {
+            font: font.clone(),
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(COLOR_YELLOW),
+        Transform::from_translation(love_to_bevy_coords(70.0, 100.0).extend(1.0)),
+        Anchor::TOP_LEFT,
+        DespawnOnExit(Screen::VersusResult),
+    ));
+
+    // 比分
+    commands.spawn((
+        Name::new("Versus Score"),
+        Text2d::new(format!(
+            "1P ${}   2P ${}",
+            versus.player(PlayerSlot(0)).money,
+            versus.player(PlayerSlot(1)).money
+        )),
+        TextFont {
+            font: font.clone(),
+            font_size: 15.0,
+            ..default()
+        },
+        TextColor(COLOR_GREEN),
+        Transform::from_translation(love_to_bevy_coords(70.0, 130.0).extend(1.0)),
+        Anchor::TOP_LEFT,
+        DespawnOnExit(Screen::VersusResult),
+    ));
+
+    // 提示按键
+    commands.spawn((
+        Name::new("Press Any Key"),
+        Text2d::new("Press Any Key to Continue"),
+        TextFont {
+            font,
+            font_size: 15.0,
+            ..default()
+        },
+        TextColor(COLOR_YELLOW),
+        Transform::from_translation(love_to_bevy_coords(160.0, 170.0).extend(1.0)),
+        Anchor::CENTER,
+        DespawnOnExit(Screen::VersusResult),
+    ));
+}
+
+/// 按任意键进入下一回合，最后一回合结束后回到标题界面并重置状态
+fn check_keyboard_input(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut stats: ResMut<LevelStats>,
+    mut player: ResMut<PlayerResource>,
+    mut hook_stats: ResMut<HookStats>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    progression_handle: Res<ProgressionHandle>,
+    progression: Res<Assets<ProgressionConfig>>,
+) {
+    let pressed = input.get_just_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some());
+    if !pressed {
+        return;
+    }
+
+    if stats.level >= VERSUS_ROUNDS {
+        commands.remove_resource::<VersusState>();
+        *stats = LevelStats::default();
+        *player = PlayerResource::default();
+        *hook_stats = HookStats::default();
+        next_screen.set(Screen::Title);
+        return;
+    }
+
+    stats.level += 1;
+    let mut rng = stats.rng(RngStream::Level);
+    stats.calculate_real_level(&progression_handle.get(&progression), &mut rng);
+    stats.reset_timer();
+    next_screen.set(Screen::NextGoal);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome_follows_total_money() {
+        let mut versus = VersusState::default();
+        assert_eq!(versus.outcome(), VersusOutcome::Draw);
+
+        versus.player_mut(PlayerSlot(1)).money = 500;
+        assert_eq!(versus.outcome(), VersusOutcome::Winner(PlayerSlot(1)));
+
+        versus.player_mut(PlayerSlot(0)).money = 800;
+        assert_eq!(versus.outcome(), VersusOutcome::Winner(PlayerSlot(0)));
+    }
+}
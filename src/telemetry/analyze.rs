@@ -0,0 +1,504 @@
+//! 遥测日志汇总：`goldminer telemetry [目录或文件...] [--format csv|json] [--out FILE]`
+//!
+//! 不指定路径时读取存档旁的 `telemetry/` 目录。有机器人参与的关卡 (包括演示模式) 与
+//! 没有结束记录的关卡不计入；无法解析的行跳过并计数。
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, bail};
+use serde::Serialize;
+
+use super::{LevelOutcome, TELEMETRY_DIR, TelemetryEvent, TelemetryRecord, is_session_log};
+
+/// 一次完整的关卡游玩
+#[derive(Debug, Clone, PartialEq)]
+struct LevelPlay {
+    level: u32,
+    level_id: String,
+    outcome: LevelOutcome,
+    goal_timer: Option<f32>,
+    /// (实体 ID, 收益, 回收时间)
+    grabs: Vec<(String, i32, f32)>,
+    dynamite: Vec<Option<String>>,
+}
+
+/// 一个关卡 ID 的汇总
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LevelSummary {
+    pub level_id: String,
+    pub plays: u32,
+    pub clears: u32,
+    pub clear_rate: f32,
+    /// 达成目标时剩余时间的均值 (没有达成过则为空)
+    pub goal_timer_mean: Option<f32>,
+    pub grabs_mean: f32,
+    pub payout_per_grab: f32,
+    pub reel_time_mean: f32,
+    pub dynamite_mean: f32,
+}
+
+/// 一种实体的抓取汇总
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct EntitySummary {
+    pub entity_id: String,
+    pub grabs: u32,
+    pub payout_mean: f32,
+    pub reel_time_mean: f32,
+    /// 抓住后用炸药炸掉的次数
+    pub dynamited: u32,
+}
+
+/// 一种道具在商店中的上架与购买次数
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ItemSummary {
+    pub item: String,
+    pub offered: u32,
+    pub purchased: u32,
+    pub purchase_rate: f32,
+    pub price_mean: f32,
+}
+
+/// 所有日志的汇总
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Summary {
+    pub sessions: u32,
+    /// 无法解析而跳过的行数
+    pub skipped_lines: u32,
+    pub levels: Vec<LevelSummary>,
+    /// 按抓取次数从多到少排列
+    pub entities: Vec<EntitySummary>,
+    /// 按上架次数从多到少排列
+    pub items: Vec<ItemSummary>,
+}
+
+impl Summary {
+    /// 由各会话日志的内容生成汇总
+    pub fn new<'a>(sessions: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut session_count = 0;
+        let mut skipped_lines = 0;
+        let mut plays = Vec::new();
+        // (上架次数, 购买次数, 上架价格之和)
+        let mut items: HashMap<String, (u32, u32, u64)> = HashMap::new();
+
+        for content in sessions {
+            session_count += 1;
+            let mut events = Vec::new();
+            for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                match serde_json::from_str::<TelemetryRecord>(line) {
+                    Ok(record) => events.push(record.event),
+                    Err(_) => skipped_lines += 1,
+                }
+            }
+            collect_session(&events, &mut plays, &mut items);
+        }
+
+        Self {
+            sessions: session_count,
+            skipped_lines,
+            levels: level_summaries(&plays),
+            entities: entity_summaries(&plays),
+            items: item_summaries(items),
+        }
+    }
+
+    /// 每个关卡 ID 一行的 CSV
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "level_id,plays,clears,clear_rate,goal_timer_mean,grabs_mean,payout_per_grab,\
+             reel_time_mean,dynamite_mean\n",
+        );
+        for level in &self.levels {
+            csv.push_str(&format!(
+                "{},{},{},{:.3},{},{:.1},{:.0},{:.2},{:.2}\n",
+                level.level_id,
+                level.plays,
+                level.clears,
+                level.clear_rate,
+                level
+                    .goal_timer_mean
+                    .map(|timer| format!("{timer:.1}"))
+                    .unwrap_or_default(),
+                level.grabs_mean,
+                level.payout_per_grab,
+                level.reel_time_mean,
+                level.dynamite_mean,
+            ));
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self).map(|json| json + "\n")
+    }
+}
+
+/// 按顺序把一个会话的事件拆成关卡游玩，并统计商店
+fn collect_session(
+    events: &[TelemetryEvent],
+    plays: &mut Vec<LevelPlay>,
+    items: &mut HashMap<String, (u32, u32, u64)>,
+) {
+    let mut current: Option<LevelPlay> = None;
+    // 最近一关是否由机器人参与，商店同样跳过
+    let mut bot = false;
+
+    for event in events {
+        match event {
+            TelemetryEvent::LevelStart {
+                level,
+                level_id,
+                bot: is_bot,
+                ..
+            } => {
+                bot = *is_bot;
+                current = (!bot).then(|| LevelPlay {
+                    level: *level,
+                    level_id: level_id.clone(),
+                    outcome: LevelOutcome::Abandoned,
+                    goal_timer: None,
+                    grabs: Vec::new(),
+                    dynamite: Vec::new(),
+                });
+            }
+            TelemetryEvent::Grab {
+                entity_id,
+                reel_time,
+                payout,
+                ..
+            } => {
+                if let Some(play) = current.as_mut() {
+                    play.grabs.push((entity_id.clone(), *payout, *reel_time));
+                }
+            }
+            TelemetryEvent::Dynamite { entity_id, .. } => {
+                if let Some(play) = current.as_mut() {
+                    play.dynamite.push(entity_id.clone());
+                }
+            }
+            TelemetryEvent::GoalReached { timer } => {
+                if let Some(play) = current.as_mut() {
+                    play.goal_timer = Some(*timer);
+                }
+            }
+            TelemetryEvent::LevelEnd { outcome, .. } => {
+                if let Some(mut play) = current.take() {
+                    play.outcome = *outcome;
+                    plays.push(play);
+                }
+            }
+            TelemetryEvent::ShopOffers { items: offers } if !bot => {
+                for offer in offers {
+                    let entry = items.entry(offer.item.clone()).or_default();
+                    entry.0 += 1;
+                    entry.2 += offer.price as u64;
+                }
+            }
+            TelemetryEvent::ShopPurchase { item, .. } if !bot => {
+                items.entry(item.clone()).or_default().1 += 1;
+            }
+            TelemetryEvent::ShopOffers { .. } | TelemetryEvent::ShopPurchase { .. } => {}
+        }
+    }
+}
+
+fn mean(sum: f32, count: usize) -> f32 {
+    if count == 0 { 0.0 } else { sum / count as f32 }
+}
+
+fn level_summaries(plays: &[LevelPlay]) -> Vec<LevelSummary> {
+    let mut by_id: BTreeMap<&str, Vec<&LevelPlay>> = BTreeMap::new();
+    for play in plays {
+        by_id.entry(&play.level_id).or_default().push(play);
+    }
+
+    let mut levels: Vec<(u32, LevelSummary)> = by_id
+        .into_iter()
+        .map(|(level_id, plays)| {
+            let count = plays.len();
+            let clears = plays
+                .iter()
+                .filter(|play| play.outcome == LevelOutcome::Cleared)
+                .count();
+            let goal_timers: Vec<f32> = plays.iter().filter_map(|play| play.goal_timer).collect();
+            let grabs: Vec<_> = plays.iter().flat_map(|play| &play.grabs).collect();
+            let summary = LevelSummary {
+                level_id: level_id.to_string(),
+                plays: count as u32,
+                clears: clears as u32,
+                clear_rate: mean(clears as f32, count),
+                goal_timer_mean: (!goal_timers.is_empty())
+                    .then(|| mean(goal_timers.iter().sum(), goal_timers.len())),
+                grabs_mean: mean(grabs.len() as f32, count),
+                payout_per_grab: mean(
+                    grabs.iter().map(|(_, payout, _)| *payout as f32).sum(),
+                    grabs.len(),
+                ),
+                reel_time_mean: mean(grabs.iter().map(|(.., reel)| *reel).sum(), grabs.len()),
+                dynamite_mean: mean(
+                    plays.iter().map(|play| play.dynamite.len() as f32).sum(),
+                    count,
+                ),
+            };
+            let level = plays.iter().map(|play| play.level).min().unwrap_or(0);
+            (level, summary)
+        })
+        .collect();
+    // 按关卡序号排列，同一关按 ID 排列
+    levels.sort_by(|(a, a_summary), (b, b_summary)| {
+        a.cmp(b)
+            .then_with(|| a_summary.level_id.cmp(&b_summary.level_id))
+    });
+    levels.into_iter().map(|(_, summary)| summary).collect()
+}
+
+fn entity_summaries(plays: &[LevelPlay]) -> Vec<EntitySummary> {
+    fn entry<'a>(
+        entities: &'a mut BTreeMap<String, EntitySummary>,
+        id: &str,
+    ) -> &'a mut EntitySummary {
+        entities
+            .entry(id.to_string())
+            .or_insert_with(|| EntitySummary {
+                entity_id: id.to_string(),
+                grabs: 0,
+                payout_mean: 0.0,
+                reel_time_mean: 0.0,
+                dynamited: 0,
+            })
+    }
+
+    // 先累加总和，最后除以次数
+    let mut entities = BTreeMap::new();
+    for play in plays {
+        for (id, payout, reel) in &play.grabs {
+            let summary = entry(&mut entities, id);
+            summary.grabs += 1;
+            summary.payout_mean += *payout as f32;
+            summary.reel_time_mean += reel;
+        }
+        for id in play.dynamite.iter().flatten() {
+            entry(&mut entities, id).dynamited += 1;
+        }
+    }
+
+    let mut entities: Vec<EntitySummary> = entities
+        .into_values()
+        .map(|mut summary| {
+            summary.payout_mean = mean(summary.payout_mean, summary.grabs as usize);
+            summary.reel_time_mean = mean(summary.reel_time_mean, summary.grabs as usize);
+            summary
+        })
+        .collect();
+    entities.sort_by_key(|summary| std::cmp::Reverse(summary.grabs));
+    entities
+}
+
+fn item_summaries(items: HashMap<String, (u32, u32, u64)>) -> Vec<ItemSummary> {
+    let mut items: Vec<ItemSummary> = items
+        .into_iter()
+        .map(|(item, (offered, purchased, price_sum))| ItemSummary {
+            item,
+            offered,
+            purchased,
+            purchase_rate: mean(purchased as f32, offered as usize),
+            price_mean: mean(price_sum as f32, offered as usize),
+        })
+        .collect();
+    items.sort_by(|a, b| b.offered.cmp(&a.offered).then_with(|| a.item.cmp(&b.item)));
+    items
+}
+
+/// 展开参数中的目录，得到所有会话日志文件
+fn session_files(paths: &[PathBuf]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut logs: Vec<PathBuf> = fs::read_dir(path)
+                .with_context(|| format!("failed to read {}", path.display()))?
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(is_session_log)
+                })
+                .collect();
+            logs.sort();
+            files.extend(logs);
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+enum Format {
+    Csv,
+    Json,
+}
+
+/// 解析命令行参数并输出汇总
+pub fn run_cli(args: &[String]) -> anyhow::Result<()> {
+    let mut format = Format::Csv;
+    let mut out: Option<PathBuf> = None;
+    let mut paths = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .with_context(|| format!("missing value for {arg}"))
+        };
+        match arg.as_str() {
+            "--format" => {
+                format = match value()?.as_str() {
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    other => bail!("unknown format {other} (expected csv or json)"),
+                };
+            }
+            "--out" => out = Some(PathBuf::from(value()?)),
+            other if other.starts_with("--") => bail!(
+                "unknown option {other}\nusage: goldminer telemetry [DIR|FILE...] \
+                 [--format csv|json] [--out FILE]"
+            ),
+            path => paths.push(PathBuf::from(path)),
+        }
+    }
+    if paths.is_empty() {
+        paths.push(Path::new(TELEMETRY_DIR).to_path_buf());
+    }
+
+    let files = session_files(&paths)?;
+    if files.is_empty() {
+        bail!("no telemetry logs found");
+    }
+    let contents = files
+        .iter()
+        .map(|file| {
+            fs::read_to_string(file).with_context(|| format!("failed to read {}", file.display()))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let summary = Summary::new(contents.iter().map(String::as_str));
+    let content = match format {
+        Format::Csv => summary.to_csv(),
+        Format::Json => summary.to_json()?,
+    };
+
+    match out {
+        Some(path) => fs::write(&path, content)
+            .with_context(|| format!("failed to write {}", path.display()))?,
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::ShopEntry;
+
+    fn session(events: Vec<TelemetryEvent>) -> String {
+        events
+            .into_iter()
+            .map(|event| {
+                serde_json::to_string(&TelemetryRecord { time: 0.0, event }).unwrap() + "\n"
+            })
+            .collect()
+    }
+
+    fn start(level_id: &str, bot: bool) -> TelemetryEvent {
+        TelemetryEvent::LevelStart {
+            level: 1,
+            level_id: level_id.to_string(),
+            seed: None,
+            mode: "Campaign".to_string(),
+            difficulty: "Normal".to_string(),
+            goal: 650,
+            money: 0,
+            bot,
+        }
+    }
+
+    fn grab(entity_id: &str, payout: i32) -> TelemetryEvent {
+        TelemetryEvent::Grab {
+            player: 0,
+            entity_id: entity_id.to_string(),
+            angle: 0.0,
+            reel_time: 2.0,
+            payout,
+            effects: Vec::new(),
+        }
+    }
+
+    fn end(outcome: LevelOutcome) -> TelemetryEvent {
+        TelemetryEvent::LevelEnd {
+            outcome,
+            money: 0,
+            timer: 0.0,
+        }
+    }
+
+    #[test]
+    fn summary_aggregates_sessions_and_skips_bots() {
+        let first = session(vec![
+            start("L1_1", false),
+            grab("GoldBig", 500),
+            grab("Rock", 20),
+            TelemetryEvent::GoalReached { timer: 20.0 },
+            end(LevelOutcome::Cleared),
+            TelemetryEvent::ShopOffers {
+                items: vec![ShopEntry {
+                    item: "Dynamite".to_string(),
+                    price: 100,
+                }],
+            },
+            TelemetryEvent::ShopPurchase {
+                item: "Dynamite".to_string(),
+                price: 100,
+            },
+            // 没有结束记录的关卡不计入
+            start("L2_1", false),
+            grab("GoldBig", 500),
+        ]);
+        let second = session(vec![
+            start("L1_1", false),
+            TelemetryEvent::Dynamite {
+                player: 0,
+                entity_id: Some("Rock".to_string()),
+            },
+            end(LevelOutcome::Failed),
+            start("L1_1", true),
+            grab("GoldBig", 500),
+            end(LevelOutcome::Cleared),
+        ]) + "not json\n";
+
+        let summary = Summary::new([first.as_str(), second.as_str()]);
+        assert_eq!((summary.sessions, summary.skipped_lines), (2, 1));
+        assert_eq!(summary.levels.len(), 1);
+        let level = &summary.levels[0];
+        assert_eq!((level.plays, level.clears), (2, 1));
+        assert_eq!(level.goal_timer_mean, Some(20.0));
+        assert_eq!(level.grabs_mean, 1.0);
+        assert_eq!(level.payout_per_grab, 260.0);
+        assert_eq!(level.dynamite_mean, 0.5);
+
+        let rock = summary
+            .entities
+            .iter()
+            .find(|entity| entity.entity_id == "Rock")
+            .unwrap();
+        assert_eq!((rock.grabs, rock.dynamited), (1, 1));
+        assert_eq!(summary.items[0].purchase_rate, 1.0);
+        assert!(
+            summary
+                .to_csv()
+                .lines()
+                .nth(1)
+                .unwrap()
+                .starts_with("L1_1,2,1,0.500,20.0,")
+        );
+    }
+}
@@ -0,0 +1,376 @@
+//! 本地游戏遥测日志 (默认关闭)
+//!
+//! 开启后每次启动游戏写入一个 JSON Lines 会话日志，供平衡调整使用：
+//! - 在主菜单按 T 开关 (保存在存档中)，或设置环境变量 `GOLDMINER_TELEMETRY=1`
+//! - 日志位于存档旁的 `telemetry/` 目录，超过 `MAX_SESSION_LOGS` 个时删除最旧的
+//! - 每行一条记录：关卡开始 (关卡 ID 与种子)、每次抓取 (实体、角度、回收时间、收益)、炸药、
+//!   达成目标时的剩余时间、商店货架与购买、关卡结果
+//! - `goldminer telemetry [目录或文件...]` 汇总多个日志 (见 `analyze`)
+
+pub mod analyze;
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use serde::{Deserialize, Serialize};
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use crate::constants::COLOR_YELLOW;
+use crate::demo::bot::Controllers;
+use crate::menus::Menu;
+use crate::screens::Screen;
+use crate::screens::persistent::{PersistentData, TELEMETRY_DIR};
+use crate::screens::stats::LevelStats;
+use crate::utils::love_to_bevy_coords;
+
+/// 最多保留的会话日志数量
+const MAX_SESSION_LOGS: usize = 20;
+const SESSION_PREFIX: &str = "session-";
+const SESSION_EXTENSION: &str = "jsonl";
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_message::<TelemetryEvent>();
+    app.insert_resource(TelemetryLog {
+        env_opt_in: std::env::var("GOLDMINER_TELEMETRY").is_ok_and(|value| value == "1"),
+        ..default()
+    });
+    app.init_resource::<GoalTracker>();
+
+    app.add_systems(OnEnter(Menu::Main), spawn_toggle_text);
+    app.add_systems(Update, toggle_telemetry.run_if(in_state(Menu::Main)));
+
+    app.add_systems(OnEnter(Screen::Gameplay), record_level_start);
+    app.add_systems(
+        Update,
+        record_goal_reached
+            .after(crate::AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), record_level_end);
+    app.add_systems(Last, write_events);
+}
+
+/// 一条遥测事件；游戏各处通过 `commands.write_message` 发送，开启遥测时写入日志
+#[derive(Message, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    /// 进入关卡；之后的事件都属于这一关，直到 `LevelEnd`
+    LevelStart {
+        level: u32,
+        level_id: String,
+        seed: Option<u64>,
+        mode: String,
+        difficulty: String,
+        goal: u32,
+        money: u32,
+        /// 是否有玩家由机器人操作 (包括演示模式)
+        bot: bool,
+    },
+    /// 一次抓取结算
+    Grab {
+        player: usize,
+        entity_id: String,
+        /// 发射时的钩子角度
+        angle: f32,
+        /// 从碰到物品到收回的秒数
+        reel_time: f32,
+        /// 获得的金钱 (福袋等效果为 0)
+        payout: i32,
+        effects: Vec<String>,
+    },
+    /// 使用炸药：炸掉抓住的物品，或空钩时扔出 (`entity_id` 为空)
+    Dynamite {
+        player: usize,
+        entity_id: Option<String>,
+    },
+    /// 本关首次达到目标金额
+    GoalReached {
+        timer: f32,
+    },
+    LevelEnd {
+        outcome: LevelOutcome,
+        money: u32,
+        timer: f32,
+    },
+    /// 商店货架
+    ShopOffers {
+        items: Vec<ShopEntry>,
+    },
+    ShopPurchase {
+        item: String,
+        price: u32,
+    },
+}
+
+/// 关卡结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LevelOutcome {
+    Cleared,
+    /// 时间耗尽仍未达到目标
+    Failed,
+    /// 未达到目标就离开关卡 (返回主菜单、练习中跳过等)
+    Abandoned,
+}
+
+impl LevelOutcome {
+    pub fn new(reached_goal: bool, timer: f32) -> Self {
+        if reached_goal {
+            LevelOutcome::Cleared
+        } else if timer <= 0.0 {
+            LevelOutcome::Failed
+        } else {
+            LevelOutcome::Abandoned
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShopEntry {
+    pub item: String,
+    pub price: u32,
+}
+
+/// 日志中的一行：距会话开始的秒数与事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryRecord {
+    pub time: f64,
+    #[serde(flatten)]
+    pub event: TelemetryEvent,
+}
+
+/// 当前会话的日志文件，在第一条事件写入时创建
+#[derive(Resource, Default)]
+struct TelemetryLog {
+    env_opt_in: bool,
+    file: Option<File>,
+    /// 创建或写入失败后本次运行不再尝试
+    failed: bool,
+}
+
+impl TelemetryLog {
+    fn enabled(&self, persistent: &PersistentData) -> bool {
+        !self.failed && (self.env_opt_in || persistent.telemetry)
+    }
+}
+
+/// 本关是否已经记录过达成目标
+#[derive(Resource, Default)]
+struct GoalTracker {
+    reached: bool,
+}
+
+#[derive(Component)]
+struct TelemetryToggleText;
+
+fn toggle_label(enabled: bool) -> String {
+    format!("T: Telemetry {}", if enabled { "On" } else { "Off" })
+}
+
+fn spawn_toggle_text(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    persistent: Res<PersistentData>,
+    log: Res<TelemetryLog>,
+) {
+    commands.spawn((
+        Name::new("Telemetry Toggle"),
+        DespawnOnExit(Menu::Main),
+        TelemetryToggleText,
+        Text2d::new(toggle_label(log.enabled(&persistent))),
+        TextFont {
+            font: asset_server.load("fonts/Pixel-Square-10-1.ttf"),
+            font_size: 10.0,
+            ..default()
+        },
+        TextColor(COLOR_YELLOW),
+        Transform::from_translation(love_to_bevy_coords(315.0, 5.0).extend(3.0)),
+        Anchor::TOP_RIGHT,
+    ));
+}
+
+fn toggle_telemetry(
+    input: Res<ButtonInput<KeyCode>>,
+    mut persistent: ResMut<PersistentData>,
+    log: Res<TelemetryLog>,
+    mut q_text: Query<&mut Text2d, With<TelemetryToggleText>>,
+) {
+    if !input.just_pressed(KeyCode::KeyT) {
+        return;
+    }
+    persistent.telemetry = !persistent.telemetry;
+    persistent.save();
+    for mut text in &mut q_text {
+        text.0 = toggle_label(log.enabled(&persistent));
+    }
+}
+
+fn record_level_start(
+    mut commands: Commands,
+    stats: Res<LevelStats>,
+    controllers: Res<Controllers>,
+    mut tracker: ResMut<GoalTracker>,
+) {
+    tracker.reached = stats.reach_goal();
+    commands.write_message(TelemetryEvent::LevelStart {
+        level: stats.level,
+        level_id: stats.real_level_str.clone(),
+        seed: stats.seed,
+        mode: format!("{:?}", stats.mode),
+        difficulty: stats.difficulty.label().to_string(),
+        goal: stats.goal,
+        money: stats.money,
+        bot: controllers.any_bot(),
+    });
+}
+
+fn record_goal_reached(
+    mut commands: Commands,
+    stats: Res<LevelStats>,
+    mut tracker: ResMut<GoalTracker>,
+) {
+    if !tracker.reached && stats.reach_goal() {
+        tracker.reached = true;
+        commands.write_message(TelemetryEvent::GoalReached { timer: stats.timer });
+    }
+}
+
+fn record_level_end(mut commands: Commands, stats: Res<LevelStats>) {
+    commands.write_message(TelemetryEvent::LevelEnd {
+        outcome: LevelOutcome::new(stats.reach_goal(), stats.timer),
+        money: stats.money,
+        timer: stats.timer,
+    });
+}
+
+/// 把本帧的事件追加到会话日志；未开启遥测时丢弃
+fn write_events(
+    mut events: MessageReader<TelemetryEvent>,
+    persistent: Res<PersistentData>,
+    mut log: ResMut<TelemetryLog>,
+    time: Res<Time<Real>>,
+) {
+    if !log.enabled(&persistent) {
+        events.clear();
+        log.file = None;
+        return;
+    }
+    if events.is_empty() {
+        return;
+    }
+
+    if log.file.is_none() {
+        match open_session_log(Path::new(TELEMETRY_DIR)) {
+            Ok((file, path)) => {
+                info!("Telemetry: writing {}", path.display());
+                log.file = Some(file);
+            }
+            Err(e) => {
+                warn!("Failed to create telemetry log: {}", e);
+                log.failed = true;
+                events.clear();
+                return;
+            }
+        }
+    }
+
+    let mut lines = String::new();
+    for event in events.read() {
+        let record = TelemetryRecord {
+            time: time.elapsed_secs_f64(),
+            event: event.clone(),
+        };
+        match serde_json::to_string(&record) {
+            Ok(line) => {
+                lines.push_str(&line);
+                lines.push('\n');
+            }
+            Err(e) => warn!("Failed to serialize telemetry event: {}", e),
+        }
+    }
+    if let Some(file) = log.file.as_mut()
+        && let Err(e) = file.write_all(lines.as_bytes())
+    {
+        warn!("Failed to write telemetry log: {}", e);
+        log.file = None;
+        log.failed = true;
+    }
+}
+
+/// 删除超出上限的旧日志后创建本次会话的日志
+fn open_session_log(dir: &Path) -> std::io::Result<(File, PathBuf)> {
+    fs::create_dir_all(dir)?;
+    let names = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    for name in logs_to_remove(names, MAX_SESSION_LOGS) {
+        if let Err(e) = fs::remove_file(dir.join(&name)) {
+            warn!("Failed to remove old telemetry log {}: {}", name, e);
+        }
+    }
+
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let path = dir.join(format!("{SESSION_PREFIX}{secs:012}.{SESSION_EXTENSION}"));
+    let file = OpenOptions::new().create(true).append(true).open(&path)?;
+    Ok((file, path))
+}
+
+fn is_session_log(name: &str) -> bool {
+    name.starts_with(SESSION_PREFIX) && name.ends_with(&format!(".{SESSION_EXTENSION}"))
+}
+
+/// 为新日志腾出位置需要删除的旧日志 (文件名带时间戳，按名称排序即按时间排序)
+fn logs_to_remove(mut names: Vec<String>, cap: usize) -> Vec<String> {
+    names.retain(|name| is_session_log(name));
+    names.sort();
+    let excess = (names.len() + 1).saturating_sub(cap);
+    names.truncate(excess);
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_removes_oldest_sessions_only() {
+        let names = vec![
+            "session-000000000300.jsonl".to_string(),
+            "notes.txt".to_string(),
+            "session-000000000100.jsonl".to_string(),
+            "session-000000000200.jsonl".to_string(),
+        ];
+        assert_eq!(
+            logs_to_remove(names.clone(), 3),
+            vec!["session-000000000100.jsonl".to_string()]
+        );
+        assert!(logs_to_remove(names, 4).is_empty());
+    }
+
+    #[test]
+    fn records_round_trip_as_flat_json() {
+        let record = TelemetryRecord {
+            time: 1.5,
+            event: TelemetryEvent::Dynamite {
+                player: 0,
+                entity_id: Some("Rock".to_string()),
+            },
+        };
+        let line = serde_json::to_string(&record).unwrap();
+        assert_eq!(
+            line,
+            r#"{"time":1.5,"event":"dynamite","player":0,"entity_id":"Rock"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<TelemetryRecord>(&line).unwrap(),
+            record
+        );
+    }
+}
@@ -10,3 +10,8 @@ pub fn love_to_bevy_coords(x: f32, y: f32) -> Vec2 {
         VIRTUAL_HEIGHT / 2.0 - y, // Y: 翻转并转到中心
     )
 }
+
+/// convert bevy coord to love coord
+pub fn bevy_to_love_coords(pos: Vec2) -> Vec2 {
+    Vec2::new(pos.x + VIRTUAL_WIDTH / 2.0, VIRTUAL_HEIGHT / 2.0 - pos.y)
+}